@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::Result;
+use ktensor::Tensor;
+use ktensor::npy::{read_npz_entries, write_npz_entries};
+use ktensor::tensor::TensorRngState;
+use crate::{VariableData, Optimizer};
+use crate::error::KDeZeroError;
+
+/// Everything needed to resume training
+///
+/// # Fields
+///
+/// * `epoch` - The number of finished epochs
+/// * `model` - The data of the parameters of the optimizer
+/// * `optimizer` - The state dict of the optimizer
+/// * `rng` - The named states of the random number generators like the one of a `DataLoader`
+///
+/// # Note
+///
+/// The checkpoint is saved as a `.npz` archive whose entries are
+/// `epoch`, `model/<name>`, `optimizer/<name>` and `rng/<name>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub epoch: usize,
+    pub model: HashMap<String, VariableData>,
+    pub optimizer: HashMap<String, VariableData>,
+    pub rng: HashMap<String, TensorRngState>,
+}
+
+impl Checkpoint {
+    /// Capture the parameters of the optimizer and the optimizer
+    pub fn from_optimizer(epoch: usize, optimizer: &Optimizer) -> Result<Self> {
+        Ok(Self {
+            epoch,
+            model: optimizer.params_state_dict(),
+            optimizer: optimizer.state_dict(),
+            rng: HashMap::new(),
+        })
+    }
+
+    pub fn add_rng_state(&mut self, name: &str, state: TensorRngState) {
+        self.rng.insert(name.to_string(), state);
+    }
+
+    /// Restore the parameters of the optimizer strictly, and then the optimizer
+    ///
+    /// # Note
+    ///
+    /// Attach the scheduler to the optimizer before, so that the schedule resumes from the saved step.
+    pub fn restore_optimizer(&self, optimizer: &mut Optimizer) -> Result<()> {
+        optimizer.load_params_state_dict(&self.model, true)?;
+        optimizer.load_state_dict(&self.optimizer)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut entries = vec![("epoch".to_string(), Tensor::scalar(self.epoch as u64).to_npy_bytes())];
+        for (prefix, state) in [("model", &self.model), ("optimizer", &self.optimizer)] {
+            let mut names = state.keys().collect::<Vec<_>>();
+            names.sort();
+            for name in names {
+                entries.push((format!("{}/{}", prefix, name), state[name].to_npy_bytes()?));
+            }
+        }
+        let mut names = self.rng.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            entries.push((format!("rng/{}", name), self.rng[name].to_tensor().to_npy_bytes()));
+        }
+        write_npz_entries(path, &entries)
+    }
+
+    /// Load a checkpoint written by `save`
+    ///
+    /// # Note
+    ///
+    /// If the archive has an entry which is not written by `save`,
+    /// `KDeZeroError::StateKeyMismatch` is returned with the entry as an unexpected key.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut checkpoint = Self {
+            epoch: 0,
+            model: HashMap::new(),
+            optimizer: HashMap::new(),
+            rng: HashMap::new(),
+        };
+        let mut has_epoch = false;
+        for (name, bytes) in read_npz_entries(path)? {
+            if name == "epoch" {
+                checkpoint.epoch = *Tensor::<u64>::from_npy_bytes(&bytes)?.at([])? as usize;
+                has_epoch = true;
+            } else if let Some(name) = name.strip_prefix("model/") {
+                checkpoint.model.insert(name.to_string(), VariableData::from_npy_bytes(&bytes)?);
+            } else if let Some(name) = name.strip_prefix("optimizer/") {
+                checkpoint.optimizer.insert(name.to_string(), VariableData::from_npy_bytes(&bytes)?);
+            } else if let Some(name) = name.strip_prefix("rng/") {
+                let state = TensorRngState::from_tensor(&Tensor::from_npy_bytes(&bytes)?)?;
+                checkpoint.rng.insert(name.to_string(), state);
+            } else {
+                return Err(KDeZeroError::StateKeyMismatch(vec![], vec![name]).into())
+            }
+        }
+        if !has_epoch {
+            return Err(KDeZeroError::StateKeyMismatch(vec!["epoch".to_string()], vec![]).into())
+        }
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::tensor::TensorRng;
+    use crate::{Variable, Model, VariableType};
+    use crate::function::{sigmoid, mean_squared_error};
+    use crate::model::MLP;
+    use crate::optimizer::MomentumSGD;
+    use crate::optimizer::scheduler::ReduceLROnPlateau;
+
+    fn train_step(optimizer: &mut Optimizer, rng: &mut TensorRng) -> Result<()> {
+        let x = Variable::new(rng.gen::<f64, _>([4, 2]).into());
+        let t = Variable::new(rng.gen::<f64, _>([4, 1]).into());
+        let model = optimizer.get_model_mut_result()?;
+        let y = model.forward(&[x])?.remove(0);
+        let mut loss = mean_squared_error(&y, &t)?;
+        model.clear_grads();
+        loss.backward()?;
+        optimizer.update()?;
+        let metric = *loss.data().to_f64_tensor()?.at([])?;
+        optimizer.scheduler_step_with_metric(metric)?;
+        Ok(())
+    }
+
+    fn new_optimizer() -> Result<Optimizer> {
+        let mut optimizer = Optimizer::new(MomentumSGD::new(0.1, 0.9));
+        optimizer.set_model(Model::new(MLP::new(&[2, 4, 1], sigmoid, VariableType::F64)?));
+        optimizer.set_scheduler(ReduceLROnPlateau::new(0.5, 0))?;
+        Ok(optimizer)
+    }
+
+    #[test]
+    fn save_and_resume() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("kdezero_checkpoint_{}.npz", std::process::id()));
+        let mut rng = TensorRng::new();
+        let mut optimizer = new_optimizer()?;
+        train_step(&mut optimizer, &mut rng)?;
+        let mut checkpoint = Checkpoint::from_optimizer(1, &optimizer)?;
+        checkpoint.add_rng_state("data", rng.state());
+        checkpoint.save(&path)?;
+        train_step(&mut optimizer, &mut rng)?;
+
+        let loaded = Checkpoint::load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(loaded, checkpoint);
+        let mut resumed = new_optimizer()?;
+        loaded.restore_optimizer(&mut resumed)?;
+        let mut resumed_rng = TensorRng::from_state(&loaded.rng["data"]);
+        train_step(&mut resumed, &mut resumed_rng)?;
+        assert_eq!(resumed.lr(), optimizer.lr());
+        assert_eq!(
+            resumed.get_model_result()?.state_dict(),
+            optimizer.get_model_result()?.state_dict());
+        assert_eq!(resumed.state_dict(), optimizer.state_dict());
+        Ok(())
+    }
+}
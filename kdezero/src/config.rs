@@ -2,6 +2,7 @@ use std::cell::RefCell;
 
 thread_local! {
     pub static NO_GRAD: RefCell<bool> = RefCell::new(false);
+    pub static TRAIN: RefCell<bool> = RefCell::new(true);
 }
 
 pub struct NoGradGuard;
@@ -39,6 +40,44 @@ pub fn is_no_grad_enabled() -> bool {
     NO_GRAD.with(|no_grad| *no_grad.borrow())
 }
 
+/// Switch between the training and the inference mode while the guard lives
+///
+/// Layers such as `BatchNorm2d` use the statistics of the batch and update their running statistics
+/// in the training mode, and use the running statistics in the inference mode.
+/// The previous mode is restored when the guard is dropped, so the guards can be nested.
+pub struct TrainModeGuard {
+    previous: bool,
+}
+
+impl TrainModeGuard {
+    pub fn new(train: bool) -> Self {
+        let previous = TRAIN.with(|mode| mode.replace(train));
+        Self { previous }
+    }
+}
+
+impl Drop for TrainModeGuard {
+    fn drop(&mut self) {
+        TRAIN.with(|mode| {
+            *mode.borrow_mut() = self.previous;
+        });
+    }
+}
+
+/// Enter the inference mode, which is often used together with `no_grad`
+pub fn eval_mode() -> TrainModeGuard {
+    TrainModeGuard::new(false)
+}
+
+/// Enter the training mode, which is the default
+pub fn train_mode() -> TrainModeGuard {
+    TrainModeGuard::new(true)
+}
+
+pub fn is_train_enabled() -> bool {
+    TRAIN.with(|mode| *mode.borrow())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,4 +91,19 @@ mod tests {
         }
         assert!(!is_no_grad_enabled());
     }
+
+    #[test]
+    fn eval_mode_normal() {
+        assert!(is_train_enabled());
+        {
+            let _guard = eval_mode();
+            assert!(!is_train_enabled());
+            {
+                let _guard = train_mode();
+                assert!(is_train_enabled());
+            }
+            assert!(!is_train_enabled());
+        }
+        assert!(is_train_enabled());
+    }
 }
@@ -42,9 +42,9 @@ where
         let x = self.get_all_data()?;
         let t = self.get_all_label()?;
         if let Some(x) = x {
-            let x = x.slice_with_one_index(index)?;
+            let x = x.slice_with_one_index(index)?.contiguous();
             let t = if let Some(t) = t {
-                Some(t.slice_with_one_index(index)?)
+                Some(t.slice_with_one_index(index)?.contiguous())
             } else {
                 None
             };
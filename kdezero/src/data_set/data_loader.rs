@@ -1,7 +1,7 @@
 use anyhow::Result;
 use super::DataSet;
 
-use ktensor::{Tensor, tensor::TensorRng};
+use ktensor::{Tensor, tensor::{TensorRng, TensorRngState}};
 
 pub struct DataLoader<T, U>
 where
@@ -12,6 +12,7 @@ where
     batch_size: usize,
     data_size: usize,
     shuffle: Option<TensorRng>,
+    shuffle_state: Option<TensorRngState>,
     max_iter: usize,
     iter: usize,
     index: Vec<usize>,
@@ -42,12 +43,14 @@ where
         } else {
             None
         };
+        let shuffle_state = shuffle.as_ref().map(|rng| rng.state());
         let index = Self::reset_index(&mut shuffle, data_size);
         Ok(DataLoader {
             data_set,
             data_size,
             batch_size,
             shuffle,
+            shuffle_state,
             max_iter,
             iter: 0,
             index,
@@ -64,9 +67,30 @@ where
 
     pub fn reset(&mut self) {
         self.iter = 0;
+        self.shuffle_state = self.shuffle.as_ref().map(|rng| rng.state());
         self.index = Self::reset_index(&mut self.shuffle, self.data_size);
     }
 
+    /// Get the state of the shuffling generator before the order of the current epoch was drawn
+    ///
+    /// Passing it to `set_rng_state` replays the current epoch from the first batch.
+    /// `None` is returned if the data is not shuffled.
+    pub fn rng_state(&self) -> Option<TensorRngState> {
+        self.shuffle_state
+    }
+
+    /// Restore the shuffling generator and start the epoch from the first batch
+    ///
+    /// # Note
+    ///
+    /// This does nothing on the order if the data is not shuffled.
+    pub fn set_rng_state(&mut self, state: &TensorRngState) {
+        if self.shuffle.is_some() {
+            self.shuffle = Some(TensorRng::from_state(state));
+        }
+        self.reset();
+    }
+
     pub fn iter(&mut self) -> BatchIterator<T, U> {
         BatchIterator { data_loader: self }
     }
@@ -22,6 +22,14 @@ pub enum KDeZeroError {
     NoOutputVariable(String),
     #[error("NoCreator: {0} has no creator")]
     NoCreator(String),
+    #[error("StateKeyMismatch: missing keys {0:?}, unexpected keys {1:?}")]
+    StateKeyMismatch(Vec<String>, Vec<String>),
+    #[error("StateShapeMismatch: {0} expected {1:?}, actual {2:?}")]
+    StateShapeMismatch(String, Vec<usize>, Vec<usize>),
+    #[error("StateTypeMismatch: {0} expected {1}, actual {2}")]
+    StateTypeMismatch(String, String, String),
+    #[error("DuplicateParameter: {0} is already registered")]
+    DuplicateParameter(String),
 }
 
 #[cfg(test)]
@@ -156,4 +164,36 @@ mod tests {
             }
         }
     }
+
+    fn error_state_key_mismatch() -> Result<()> {
+        Err(KDeZeroError::StateKeyMismatch(vec!["l1.bias".to_string()], vec![]).into())
+    }
+
+    #[test]
+    fn kdezero_error_state_key_mismatch() -> Result<()> {
+        match error_state_key_mismatch() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>().context("downcast error")?;
+                assert_eq!(e.to_string(), "StateKeyMismatch: missing keys [\"l1.bias\"], unexpected keys []");
+                Ok(())
+            }
+        }
+    }
+
+    fn error_duplicate_parameter() -> Result<()> {
+        Err(KDeZeroError::DuplicateParameter("x0".to_string()).into())
+    }
+
+    #[test]
+    fn kdezero_error_duplicate_parameter() -> Result<()> {
+        match error_duplicate_parameter() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>().context("downcast error")?;
+                assert_eq!(e.to_string(), "DuplicateParameter: x0 is already registered");
+                Ok(())
+            }
+        }
+    }
 }
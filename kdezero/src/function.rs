@@ -10,16 +10,22 @@ use crate::is_no_grad_enabled;
 
 pub use operator::{
     Square, Exp, Add, Mul, Neg, Sub, Div, Pow, Sin, Cos,
-    Tanh, Reshape, Transpose, BroadcastTo, SumTo,
-    Sum, MatMul, MeanSquaredError,
+    Tanh, Reshape, Transpose, TransposeAxes, BroadcastTo, SumTo,
+    Sum, MatMul, MatMulTransposed, MeanSquaredError,
     Linear, Sigmoid, GetItem, GetItemGrad, SlicePattern,
-    Softmax, Log, SoftmaxCrossEntropy, Relu,
+    Softmax, Log, SoftmaxCrossEntropy, Relu, Concat, Split,
+    Mean, Var, Max, Min, Prod, LogSumExp, TopK, Gather, ScatterAdd, Einsum,
+    Inv, Solve, Det, Cholesky, Im2col, Col2im,
     exp, square, add, mul, neg, sub, div, pow, sin, cos,
-    tanh, reshape, transpose, broadcast_to, sum_to,
-    sum, sum_keepdims, sum_axis, sum_all, matmul, mean_squared_error,
+    tanh, reshape, transpose, transpose_axes, broadcast_to, sum_to,
+    sum, sum_keepdims, sum_axis, sum_all, matmul, matmul_transposed, mean_squared_error,
     linear, sigmoid,
     get_item, get_item_with_one_index, get_item_with_one_indexes, get_item_with_indexes,
-    softmax, log, softmax_cross_entropy, relu,
+    get_item_with_slice,
+    softmax, log, softmax_cross_entropy, relu, concat, split,
+    mean, var, max, min, prod, logsumexp, topk, gather, scatter_add, einsum,
+    inv, solve, det, cholesky, im2col, col2im, conv2d, conv2d_transpose,
+    max_pool2d, avg_pool2d,
 };
 
 pub trait FunctionContent: std::fmt::Debug {
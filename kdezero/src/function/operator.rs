@@ -11,6 +11,7 @@ mod cos;
 mod tanh;
 mod reshape;
 mod transpose;
+mod transpose_axes;
 mod broadcast_to;
 mod sum_to;
 mod sum;
@@ -23,6 +24,27 @@ mod softmax;
 mod log;
 mod softmax_cross_entropy;
 mod relu;
+mod concat;
+mod split;
+mod mean;
+mod var;
+mod max;
+mod min;
+mod prod;
+mod logsumexp;
+mod topk;
+mod gather;
+mod scatter_add;
+mod einsum;
+mod inv;
+mod solve;
+mod det;
+mod cholesky;
+mod im2col;
+mod col2im;
+mod conv2d;
+mod conv2d_transpose;
+mod pool2d;
 
 pub use square::{Square, square};
 pub use exp::{Exp, exp};
@@ -37,18 +59,41 @@ pub use cos::{Cos, cos};
 pub use tanh::{Tanh, tanh};
 pub use reshape::{Reshape, reshape};
 pub use transpose::{Transpose, transpose};
+pub use transpose_axes::{TransposeAxes, transpose_axes};
 pub use broadcast_to::{BroadcastTo, broadcast_to};
 pub use sum_to::{SumTo, sum_to};
 pub use sum::{Sum, sum, sum_keepdims, sum_axis, sum_all};
-pub use matmul::{MatMul, matmul};
+pub use matmul::{MatMul, MatMulTransposed, matmul, matmul_transposed};
 pub use mean_squared_error::{MeanSquaredError, mean_squared_error};
 pub use linear::{Linear, linear};
 pub use sigmoid::{Sigmoid, sigmoid};
 pub use get_item::{
     GetItem, GetItemGrad, SlicePattern,
     get_item, get_item_with_one_index, get_item_with_one_indexes, get_item_with_indexes,
+    get_item_with_slice,
 };
 pub use softmax::{Softmax, softmax};
 pub use log::{Log, log};
 pub use softmax_cross_entropy::{SoftmaxCrossEntropy, softmax_cross_entropy};
 pub use relu::{Relu, relu};
+pub use concat::{Concat, concat};
+pub use split::{Split, split};
+pub use mean::{Mean, mean};
+pub use var::{Var, var};
+pub use max::{Max, max};
+pub use min::{Min, min};
+pub use prod::{Prod, prod};
+pub use logsumexp::{LogSumExp, logsumexp};
+pub use topk::{TopK, topk};
+pub use gather::{Gather, gather};
+pub use scatter_add::{ScatterAdd, scatter_add};
+pub use einsum::{Einsum, einsum};
+pub use inv::{Inv, inv};
+pub use solve::{Solve, solve};
+pub use det::{Det, det};
+pub use cholesky::{Cholesky, cholesky};
+pub use im2col::{Im2col, im2col};
+pub use col2im::{Col2im, col2im};
+pub use conv2d::conv2d;
+pub use conv2d_transpose::conv2d_transpose;
+pub use pool2d::{max_pool2d, avg_pool2d};
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use crate::Variable;
 use super::super::{FunctionContent, Function};
-use crate::utility::check_variable_count;
+use crate::utility::{check_variable_count, sum_to_input_shape};
 
 #[derive(Debug)]
 pub struct Add {}
@@ -25,7 +25,9 @@ impl FunctionContent for Add {
         check_variable_count(&xs, 2)?;
         check_variable_count(&gys, 1)?;
         let gy = gys[0].clone();
-        Ok(vec![gy.clone(), gy])
+        let gx0 = sum_to_input_shape(gy.clone(), xs[0])?;
+        let gx1 = sum_to_input_shape(gy, xs[1])?;
+        Ok(vec![gx0, gx1])
     }
 
     fn name(&self) -> String {
@@ -43,6 +45,8 @@ pub fn add(x0: &Variable, x1: &Variable) -> Result<Variable> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ktensor::Tensor;
+    use ktensor::error::TensorError;
     use crate::error::KDeZeroError;
 
     #[test]
@@ -81,6 +85,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn add_backward_broadcast() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([3])?.into());
+        let mut y = add(&x0, &x1)?;
+        assert_eq!(*y.data(), Tensor::new([0.0, 2.0, 4.0, 3.0, 5.0, 7.0], [2, 3])?.into());
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::<f64>::ones([2, 3]).into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::full(2.0, [3]).into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_add_forward_mismatch_shape() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([2])?.into());
+        match add(&x0, &x1) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2, 3], vec![2]));
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn add_normal() -> Result<()> {
         let x0 = Variable::from(2.0);
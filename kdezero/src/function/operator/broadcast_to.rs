@@ -56,7 +56,7 @@ mod tests {
     fn broadcast_to_forward() -> Result<()> {
         let x = Variable::new(Tensor::<f64>::arrange([3,])?.into());
         let y = BroadcastTo::new(&[2, 3]).forward(vec![&x])?;
-        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([3,])?.broadcast_to([2, 3])?.into());
+        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([3,])?.broadcast_to([2, 3])?.contiguous().into());
         Ok(())
     }
 
@@ -141,7 +141,7 @@ mod tests {
     fn broadcast_to_normal() -> Result<()> {
         let x = Variable::new(Tensor::<f64>::arrange([3])?.into());
         let y = broadcast_to(&x, &[2, 3])?;
-        assert_eq!(*y.data(), Tensor::<f64>::arrange([3])?.broadcast_to([2, 3])?.into());
+        assert_eq!(*y.data(), Tensor::<f64>::arrange([3])?.broadcast_to([2, 3])?.contiguous().into());
         Ok(())
     }
 }
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use ktensor::Tensor;
+use crate::{Variable, VariableData};
+use super::{matmul, mul, add, transpose, inv};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Cholesky {}
+
+impl Cholesky {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FunctionContent for Cholesky {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.cholesky()?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let l = cholesky(x)?;
+        let l_inv = inv(&l)?;
+        let phi = mul(&matmul(&transpose(&l)?, gy)?, &lower_half_mask(&x.data())?.into())?;
+        let s = matmul(&matmul(&transpose(&l_inv)?, &phi)?, &l_inv)?;
+        let half = x.data().full_like(0.5)?;
+        let gx = mul(&add(&s, &transpose(&s)?)?, &half.into())?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Cholesky".to_string()
+    }
+}
+
+/// Create the mask which keeps the lower triangle and halves the diagonal
+fn lower_half_mask(x: &VariableData) -> Result<VariableData> {
+    let n = x.shape()[0];
+    let condition = |f: fn(usize, usize) -> bool| -> Result<VariableData> {
+        let data = (0..n * n).map(|index| f(index / n, index % n)).collect::<Vec<_>>();
+        Ok(Tensor::new(data, [n, n])?.into())
+    };
+    let diagonal = VariableData::where_(&condition(|i, j| i == j)?, &x.full_like(0.5)?, &x.zeros_like()?)?;
+    VariableData::where_(&condition(|i, j| i > j)?, &x.ones_like()?, &diagonal)
+}
+
+/// Calculate the Cholesky decomposition `x = l l^T` of the symmetric positive definite matrix
+///
+/// Only the lower triangle of `x` is read, and the gradient is symmetrized.
+pub fn cholesky(x: &Variable) -> Result<Variable> {
+    let mut func = Function::new(Cholesky::new());
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::error::TensorError;
+    use crate::error::KDeZeroError;
+    use crate::function::sum_all;
+    use crate::test_utility::assert_approx_eq_tensor;
+
+    #[test]
+    fn cholesky_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([4.0, 2.0, 2.0, 5.0], [2, 2])?.into());
+        let y = Cholesky::new().forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([2.0, 0.0, 1.0, 2.0], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_cholesky_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Cholesky::new().forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_cholesky_not_positive_definite() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 2.0, 2.0, 1.0], [2, 2])?.into());
+        match cholesky(&x) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::NotPositiveDefiniteError());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cholesky_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([4.0, 2.0, 2.0, 5.0], [2, 2])?.into());
+        let mut y = sum_all(&cholesky(&x)?)?;
+        y.backward()?;
+        assert_approx_eq_tensor(
+            x.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([0.1875, 0.125, 0.125, 0.25], [2, 2])?,
+            1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use crate::Variable;
+use super::im2col;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Col2im {
+    pub image_shape: Vec<usize>,
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+    pub dilation: (usize, usize),
+}
+
+impl Col2im {
+    pub fn new(
+        image_shape: &[usize],
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Self {
+        Self { image_shape: image_shape.to_vec(), kernel_size, stride, pad, dilation }
+    }
+}
+
+impl FunctionContent for Col2im {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.col2im(&self.image_shape, self.kernel_size, self.stride, self.pad, self.dilation)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let gx = im2col(gys[0], self.kernel_size, self.stride, self.pad, self.dilation)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Col2im".to_string()
+    }
+}
+
+/// Sum the columns of shape `[N, C * KH * KW, OH * OW]` back into an image of `image_shape`
+///
+/// This is the adjoint of `im2col`, and the overlapping elements are added.
+pub fn col2im(
+    x: &Variable,
+    image_shape: &[usize],
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+) -> Result<Variable> {
+    let mut func = Function::new(Col2im::new(image_shape, kernel_size, stride, pad, dilation));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::mul;
+    use crate::test_utility::{assert_numerical_grad, sin_data};
+
+    #[test]
+    fn col2im_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::ones([1, 4, 4]).into());
+        let y = col2im(&x, &[1, 1, 3, 3], (2, 2), (1, 1), (0, 0), (1, 1))?;
+        assert_eq!(*y.data(), Tensor::new(vec![
+            1.0, 2.0, 1.0,
+            2.0, 4.0, 2.0,
+            1.0, 2.0, 1.0,
+        ], vec![1, 1, 3, 3])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn col2im_backward() -> Result<()> {
+        let x = Variable::new(sin_data(&[1, 8, 6], 0.3)?.into());
+        let weight = Variable::new((&Tensor::<f64>::arrange([1, 2, 5, 5])? * 0.7).cos().into());
+        let mut f = |x: &Variable| mul(&col2im(x, &[1, 2, 5, 5], (2, 2), (2, 2), (1, 0), (1, 2))?, &weight);
+        let mut y = f(&x)?;
+        y.backward()?;
+        assert_numerical_grad(&mut f, &x, 1e-5, 1e-7)?;
+        Ok(())
+    }
+}
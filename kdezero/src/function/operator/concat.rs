@@ -0,0 +1,96 @@
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::split;
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, check_variable_count_between};
+
+#[derive(Debug)]
+pub struct Concat {
+    pub axis: usize,
+}
+
+impl Concat {
+    pub fn new(axis: usize) -> Self {
+        Self { axis }
+    }
+}
+
+impl FunctionContent for Concat {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count_between(&xs, 1, usize::MAX)?;
+        let xs = xs.iter().map(|x| x.data()).collect::<Vec<_>>();
+        let y = VariableData::concat(&xs.iter().map(|x| &**x).collect::<Vec<_>>(), self.axis)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count_between(&xs, 1, usize::MAX)?;
+        check_variable_count(&gys, 1)?;
+        let gy = gys[0];
+        let sizes = xs.iter().map(|x| x.shape()[self.axis]).collect::<Vec<_>>();
+        split(gy, &sizes, self.axis)
+    }
+
+    fn name(&self) -> String {
+        "Concat".to_string()
+    }
+}
+
+/// Concatenate the variables along the existing axis
+pub fn concat(xs: &[Variable], axis: usize) -> Result<Variable> {
+    let mut func = Function::new(Concat::new(axis));
+    let mut ys = func.forward(xs)?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn concat_forward() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([1, 2])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([2, 2])?.into());
+        let y = Concat::new(0).forward(vec![&x0, &x1])?;
+        assert_eq!(*y[0].data(), Tensor::new([0.0, 1.0, 0.0, 1.0, 2.0, 3.0], [3, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_concat_forward_no_variable() -> Result<()> {
+        match Concat::new(0).forward(vec![]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::OutOfRangeVariableCount(0, 1, usize::MAX));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn concat_backward() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 1])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([2, 2])?.into());
+        let dy = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let dx = Concat::new(1).backward(vec![&x0, &x1], vec![], vec![&dy])?;
+        assert_eq!(*dx[0].data(), Tensor::new([0.0, 3.0], [2, 1])?.into());
+        assert_eq!(*dx[1].data(), Tensor::new([1.0, 2.0, 4.0, 5.0], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn concat_normal() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 1])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([2, 2])?.into());
+        let mut y = concat(&[x0.clone(), x1.clone(), x0.clone()], 1)?;
+        assert_eq!(y.shape().to_vec(), vec![2, 4]);
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::full(2.0, [2, 1]).into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::<f64>::ones([2, 2]).into());
+        Ok(())
+    }
+}
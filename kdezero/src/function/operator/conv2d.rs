@@ -0,0 +1,134 @@
+use anyhow::Result;
+use ktensor::tensor::conv_output_size;
+use crate::Variable;
+use crate::error::KDeZeroError;
+use super::{im2col, reshape, matmul};
+use crate::utility::{check_dimensions, check_groups};
+
+/// Add the bias of shape `[C]` to each channel of `y` of shape `[N, C, H, W]`
+pub(super) fn add_channel_bias(y: Variable, b: Option<&Variable>) -> Result<Variable> {
+    match b {
+        Some(b) => {
+            check_dimensions(b, 1)?;
+            Ok(&y + &reshape(b, &[1, b.shape()[0], 1, 1])?)
+        },
+        None => Ok(y),
+    }
+}
+
+/// 2-D convolution (cross-correlation) of the image
+///
+/// # Arguments
+///
+/// * `x` - The image of shape `[N, C, H, W]`
+/// * `w` - The kernel of shape `[OC, C / groups, KH, KW]`
+/// * `b` - The bias of shape `[OC]`
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The zero padding of the height and the width
+/// * `dilation` - The dilation along the height and the width
+/// * `groups` - The number of groups the channels are split into
+///
+/// # Returns
+///
+/// * `Result<Variable>` - The output of shape `[N, OC, OH, OW]`
+///
+/// # Note
+///
+/// The convolution is calculated by `im2col` and `matmul`, so the gradients follow from them.
+/// If the channels are not consistent with `groups`, `KDeZeroError::Error` is returned.
+pub fn conv2d(
+    x: &Variable,
+    w: &Variable,
+    b: Option<&Variable>,
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+) -> Result<Variable> {
+    check_dimensions(x, 4)?;
+    check_dimensions(w, 4)?;
+    let x_shape = x.shape().to_vec();
+    let w_shape = w.shape().to_vec();
+    let (n, c, h, width) = (x_shape[0], x_shape[1], x_shape[2], x_shape[3]);
+    let (oc, kh, kw) = (w_shape[0], w_shape[2], w_shape[3]);
+    check_groups("conv2d", c, oc, groups)?;
+    if w_shape[1] * groups != c {
+        return Err(KDeZeroError::Error(format!(
+            "conv2d: the kernel has {} input channels, expected {}", w_shape[1], c / groups
+        )).into())
+    }
+    let oh = conv_output_size(h, kh, stride.0, pad.0, dilation.0)?;
+    let ow = conv_output_size(width, kw, stride.1, pad.1, dilation.1)?;
+    let col = im2col(x, (kh, kw), stride, pad, dilation)?;
+    let col = reshape(&col, &[n, groups, c / groups * kh * kw, oh * ow])?;
+    let w = reshape(w, &[groups, oc / groups, c / groups * kh * kw])?;
+    let y = reshape(&matmul(&w, &col)?, &[n, oc, oh, ow])?;
+    add_channel_bias(y, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::mul;
+    use crate::test_utility::{assert_numerical_grad, sin_data};
+
+    #[test]
+    fn conv2d_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([1, 1, 3, 3])?.into());
+        let w = Variable::new(Tensor::new(vec![1.0, 0.0, 0.0, -1.0], vec![1, 1, 2, 2])?.into());
+        let b = Variable::new(Tensor::new(vec![0.5], vec![1])?.into());
+        let y = conv2d(&x, &w, Some(&b), (1, 1), (0, 0), (1, 1), 1)?;
+        assert_eq!(*y.data(), Tensor::full(-3.5, [1, 1, 2, 2]).into());
+        let y = conv2d(&x, &w, None, (1, 1), (1, 1), (1, 1), 1)?;
+        assert_eq!(*y.data(), Tensor::new(vec![
+            0.0, -1.0, -2.0, 0.0,
+            -3.0, -4.0, -4.0, 2.0,
+            -6.0, -4.0, -4.0, 5.0,
+            0.0, 6.0, 7.0, 8.0,
+        ], vec![1, 1, 4, 4])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn conv2d_forward_groups() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([1, 2, 2, 2])?.into());
+        let w = Variable::new(Tensor::new(vec![1.0, 2.0], vec![2, 1, 1, 1])?.into());
+        let y = conv2d(&x, &w, None, (1, 1), (0, 0), (1, 1), 2)?;
+        assert_eq!(*y.data(), Tensor::new(vec![0.0, 1.0, 2.0, 3.0, 8.0, 10.0, 12.0, 14.0], vec![1, 2, 2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn conv2d_backward() -> Result<()> {
+        let weight = Variable::new(sin_data(&[2, 6, 2, 3], 0.9)?.into());
+        let x = Variable::new(sin_data(&[2, 4, 5, 6], 0.3)?.into());
+        let w = Variable::new(sin_data(&[6, 2, 3, 2], 0.7)?.into());
+        let b = Variable::new(sin_data(&[6], 1.1)?.into());
+        let conv = |x: &Variable, w: &Variable, b: &Variable| -> Result<Variable> {
+            mul(&conv2d(x, w, Some(b), (2, 3), (1, 1), (2, 1), 2)?, &weight)
+        };
+        let mut y = conv(&x, &w, &b)?;
+        y.backward()?;
+        assert_numerical_grad(&mut |x| conv(x, &w, &b), &x, 1e-5, 1e-7)?;
+        assert_numerical_grad(&mut |w| conv(&x, w, &b), &w, 1e-5, 1e-7)?;
+        assert_numerical_grad(&mut |b| conv(&x, &w, b), &b, 1e-5, 1e-7)?;
+        Ok(())
+    }
+
+    #[test]
+    fn error_conv2d_groups() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::zeros([1, 3, 4, 4]).into());
+        let w = Variable::new(Tensor::<f64>::zeros([4, 1, 3, 3]).into());
+        match conv2d(&x, &w, None, (1, 1), (0, 0), (1, 1), 2) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::Error(
+                    "conv2d: channels 3 and 4 are not divisible by groups 2".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use crate::Variable;
+use crate::error::KDeZeroError;
+use super::{col2im, reshape, matmul, transpose_axes};
+use super::conv2d::add_channel_bias;
+use crate::utility::{check_dimensions, check_groups};
+
+/// The output size of the transposed convolution along one axis
+fn transposed_output_size(
+    input_size: usize,
+    kernel_size: usize,
+    stride: usize,
+    pad: usize,
+    output_padding: usize,
+    dilation: usize,
+) -> Result<usize> {
+    if output_padding >= stride {
+        return Err(KDeZeroError::Error(format!(
+            "conv2d_transpose: output_padding {} must be smaller than stride {}",
+            output_padding, stride
+        )).into())
+    }
+    let size = (input_size.max(1) - 1) * stride + dilation * (kernel_size.max(1) - 1) + output_padding + 1;
+    if size <= 2 * pad {
+        return Err(KDeZeroError::Error(format!(
+            "conv2d_transpose: padding {} is too large for the output size {}", pad, size
+        )).into())
+    }
+    Ok(size - 2 * pad)
+}
+
+/// 2-D transposed convolution, the gradient of `conv2d` with respect to its input
+///
+/// # Arguments
+///
+/// * `x` - The image of shape `[N, C, H, W]`
+/// * `w` - The kernel of shape `[C, OC / groups, KH, KW]`
+/// * `b` - The bias of shape `[OC]`
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The padding removed from both sides of the output
+/// * `output_padding` - The size added to one side of the output
+/// * `dilation` - The dilation along the height and the width
+/// * `groups` - The number of groups the channels are split into
+///
+/// # Returns
+///
+/// * `Result<Variable>` - The output of shape `[N, OC, OH, OW]`
+///   where `OH = (H - 1) * stride - 2 * pad + dilation * (KH - 1) + output_padding + 1`
+///
+/// # Note
+///
+/// The convolution is calculated by `matmul` and `col2im`, so the gradients follow from them.
+/// `output_padding` must be smaller than `stride`, otherwise `KDeZeroError::Error` is returned,
+/// since the added rows and columns are not covered by any stride of the kernel.
+/// If the channels are not consistent with `groups`, `KDeZeroError::Error` is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn conv2d_transpose(
+    x: &Variable,
+    w: &Variable,
+    b: Option<&Variable>,
+    stride: (usize, usize),
+    pad: (usize, usize),
+    output_padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+) -> Result<Variable> {
+    check_dimensions(x, 4)?;
+    check_dimensions(w, 4)?;
+    let x_shape = x.shape().to_vec();
+    let w_shape = w.shape().to_vec();
+    let (n, c, h, width) = (x_shape[0], x_shape[1], x_shape[2], x_shape[3]);
+    let (oc, kh, kw) = (w_shape[1] * groups, w_shape[2], w_shape[3]);
+    check_groups("conv2d_transpose", c, oc, groups)?;
+    if w_shape[0] != c {
+        return Err(KDeZeroError::Error(format!(
+            "conv2d_transpose: the kernel has {} input channels, expected {}", w_shape[0], c
+        )).into())
+    }
+    let oh = transposed_output_size(h, kh, stride.0, pad.0, output_padding.0, dilation.0)?;
+    let ow = transposed_output_size(width, kw, stride.1, pad.1, output_padding.1, dilation.1)?;
+    let x = reshape(x, &[n, groups, c / groups, h * width])?;
+    let w = reshape(w, &[groups, c / groups, oc / groups * kh * kw])?;
+    let col = matmul(&transpose_axes(&w, &[0, 2, 1])?, &x)?;
+    let col = reshape(&col, &[n, oc * kh * kw, h * width])?;
+    let y = col2im(&col, &[n, oc, oh, ow], (kh, kw), stride, pad, dilation)?;
+    add_channel_bias(y, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::{conv2d, mul, sum_all};
+    use crate::test_utility::{assert_approx_eq_tensor, assert_numerical_grad, sin_data};
+
+    #[test]
+    fn conv2d_transpose_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![1, 1, 2, 2])?.into());
+        let w = Variable::new(Tensor::<f64>::ones([1, 1, 2, 2]).into());
+        let y = conv2d_transpose(&x, &w, None, (1, 1), (0, 0), (0, 0), (1, 1), 1)?;
+        assert_eq!(*y.data(), Tensor::new(vec![
+            1.0, 3.0, 2.0,
+            4.0, 10.0, 6.0,
+            3.0, 7.0, 4.0,
+        ], vec![1, 1, 3, 3])?.into());
+        let y = conv2d_transpose(&x, &w, None, (2, 2), (0, 0), (1, 0), (1, 1), 1)?;
+        assert_eq!(y.shape().to_vec(), vec![1, 1, 5, 4]);
+        Ok(())
+    }
+
+    /// The transposed convolution is the adjoint of the convolution with the same kernel
+    #[test]
+    fn conv2d_transpose_adjoint() -> Result<()> {
+        let x = Variable::new(sin_data(&[2, 4, 7, 6], 0.3)?.into());
+        let w = Variable::new(sin_data(&[6, 2, 3, 2], 0.7)?.into());
+        let y = Variable::new(sin_data(&[2, 6, 4, 2], 1.3)?.into());
+        let conv = conv2d(&x, &w, None, (2, 2), (1, 0), (1, 2), 2)?;
+        let transposed = conv2d_transpose(&y, &w, None, (2, 2), (1, 0), (0, 1), (1, 2), 2)?;
+        assert_eq!(transposed.shape().to_vec(), vec![2, 4, 7, 6]);
+        let lhs = sum_all(&mul(&conv, &y)?)?;
+        let rhs = sum_all(&mul(&x, &transposed)?)?;
+        assert_approx_eq_tensor(lhs.data().to_f64_tensor()?, rhs.data().to_f64_tensor()?, 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn conv2d_transpose_backward() -> Result<()> {
+        let weight = Variable::new(sin_data(&[2, 6, 5, 4], 0.9)?.into());
+        let x = Variable::new(sin_data(&[2, 4, 3, 2], 0.3)?.into());
+        let w = Variable::new(sin_data(&[4, 3, 2, 3], 0.7)?.into());
+        let b = Variable::new(sin_data(&[6], 1.1)?.into());
+        let conv = |x: &Variable, w: &Variable, b: &Variable| -> Result<Variable> {
+            mul(&conv2d_transpose(x, w, Some(b), (2, 1), (1, 0), (1, 0), (1, 1), 2)?, &weight)
+        };
+        let mut y = conv(&x, &w, &b)?;
+        y.backward()?;
+        assert_numerical_grad(&mut |x| conv(x, &w, &b), &x, 1e-5, 1e-7)?;
+        assert_numerical_grad(&mut |w| conv(&x, w, &b), &w, 1e-5, 1e-7)?;
+        assert_numerical_grad(&mut |b| conv(&x, &w, b), &b, 1e-5, 1e-7)?;
+        Ok(())
+    }
+
+    #[test]
+    fn error_conv2d_transpose_output_padding() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::zeros([1, 1, 2, 2]).into());
+        let w = Variable::new(Tensor::<f64>::zeros([1, 1, 2, 2]).into());
+        match conv2d_transpose(&x, &w, None, (1, 1), (0, 0), (1, 0), (1, 1), 1) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::Error(
+                    "conv2d_transpose: output_padding 1 must be smaller than stride 1".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_conv2d_transpose_output_padding_with_dilation() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::zeros([1, 1, 3, 3]).into());
+        let w = Variable::new(Tensor::<f64>::zeros([1, 1, 2, 2]).into());
+        match conv2d_transpose(&x, &w, None, (1, 1), (0, 0), (1, 1), (2, 2), 1) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::Error(
+                    "conv2d_transpose: output_padding 1 must be smaller than stride 1".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use crate::Variable;
+use super::{mul, broadcast_to, transpose, inv};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Det {}
+
+impl Det {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FunctionContent for Det {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.det()?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let scale = broadcast_to(&mul(gy, &det(x)?)?, &x.shape())?;
+        let gx = mul(&scale, &transpose(&inv(x)?)?)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Det".to_string()
+    }
+}
+
+/// Calculate the determinant of the square matrix
+///
+/// The result is a scalar and the gradient is `gy det(x) x^-T`,
+/// so the backward fails for a singular matrix.
+pub fn det(x: &Variable) -> Result<Variable> {
+    let mut func = Function::new(Det::new());
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+    use crate::test_utility::{assert_approx_eq, assert_approx_eq_tensor};
+
+    #[test]
+    fn det_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([2.0, 1.0, 1.0, 4.0], [2, 2])?.into());
+        let y = Det::new().forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::scalar(7.0).into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_det_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Det::new().forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn det_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([4.0, 7.0, 2.0, 6.0], [2, 2])?.into());
+        let mut y = det(&x)?;
+        assert_approx_eq(*y.data().to_f64_tensor()?.at([])?, 10.0, 1e-12);
+        y.backward()?;
+        assert_approx_eq_tensor(
+            x.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([6.0, -2.0, -7.0, 4.0], [2, 2])?,
+            1e-12);
+        Ok(())
+    }
+}
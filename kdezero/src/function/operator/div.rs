@@ -2,7 +2,7 @@ use anyhow::Result;
 use crate::Variable;
 use super::{mul, neg, square};
 use super::super::{FunctionContent, Function};
-use crate::utility::check_variable_count;
+use crate::utility::{check_variable_count, sum_to_input_shape};
 
 #[derive(Debug)]
 pub struct Div {}
@@ -28,8 +28,8 @@ impl FunctionContent for Div {
         let x0 = xs[0];
         let x1 = xs[1];
         let gy = gys[0];
-        let gx0 = div(gy, x1)?;
-        let gx1 = mul(gy, &div(&neg(x0)?, &square(x1)?)?)?;
+        let gx0 = sum_to_input_shape(div(gy, x1)?, x0)?;
+        let gx1 = sum_to_input_shape(mul(gy, &div(&neg(x0)?, &square(x1)?)?)?, x1)?;
         Ok(vec![gx0, gx1])
     }
 
@@ -48,6 +48,7 @@ pub fn div(x0: &Variable, x1: &Variable) -> Result<Variable> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ktensor::Tensor;
     use crate::error::KDeZeroError;
 
     #[test]
@@ -86,6 +87,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn div_backward_broadcast() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 2])?.into());
+        let x1 = Variable::new(Tensor::new([1.0, 2.0], [2])?.into());
+        let mut y = div(&x0, &x1)?;
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::new([1.0, 0.5, 1.0, 0.5], [2, 2])?.into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::new([-2.0, -1.0], [2])?.into());
+        Ok(())
+    }
+
     #[test]
     fn div_normal() -> Result<()> {
         let x0 = Variable::from(2.0);
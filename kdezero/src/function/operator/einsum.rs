@@ -0,0 +1,153 @@
+use anyhow::Result;
+use ktensor::EinsumSubscripts;
+use crate::{Variable, VariableData};
+use crate::error::KDeZeroError;
+use super::{reshape, broadcast_to};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Einsum {
+    pub subscripts: String,
+}
+
+impl Einsum {
+    pub fn new(subscripts: &str) -> Self {
+        Self { subscripts: subscripts.to_string() }
+    }
+}
+
+impl FunctionContent for Einsum {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        let data = xs.iter().map(|x| x.data()).collect::<Vec<_>>();
+        let y = VariableData::einsum(
+            &self.subscripts,
+            &data.iter().map(|x| &**x).collect::<Vec<_>>())?;
+        Ok(vec![y.into()])
+    }
+
+    /// The gradient of the i-th operand is the einsum of the output gradient and the other operands
+    /// whose output is the subscripts of the i-th operand, e.g. `"ik,jk->ij"` for `"ij,jk->ik"`.
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&gys, 1)?;
+        let parsed = EinsumSubscripts::parse(&self.subscripts)?;
+        check_variable_count(&xs, parsed.inputs.len())?;
+        let gy = gys[0];
+        let mut gxs = Vec::with_capacity(xs.len());
+        for (i, input) in parsed.inputs.iter().enumerate() {
+            if input.iter().enumerate().any(|(j, c)| input[..j].contains(c)) {
+                return Err(KDeZeroError::Error(
+                    format!("einsum backward does not support repeated subscripts in an operand: {}", self.subscripts)
+                ).into())
+            }
+            let mut inputs = vec![parsed.output.clone()];
+            let mut operands = vec![gy.clone()];
+            for (j, (other, x)) in parsed.inputs.iter().zip(xs.iter()).enumerate() {
+                if i != j {
+                    inputs.push(other.clone());
+                    operands.push((*x).clone());
+                }
+            }
+            // The subscripts which appear only in this operand were summed out,
+            // so they are added back by broadcasting
+            let output = input.iter()
+                .copied()
+                .filter(|c| inputs.iter().flatten().any(|x| x == c))
+                .collect::<Vec<_>>();
+            let rewritten = EinsumSubscripts { inputs, output: output.clone() };
+            let gx = einsum(&rewritten.to_string(), &operands)?;
+            let x_shape = xs[i].shape().to_vec();
+            let gx = if output.len() == input.len() {
+                gx
+            } else {
+                let shape = input.iter()
+                    .zip(x_shape.iter())
+                    .map(|(c, &size)| if output.contains(c) { size } else { 1 })
+                    .collect::<Vec<_>>();
+                broadcast_to(&reshape(&gx, &shape)?, &x_shape)?
+            };
+            gxs.push(gx);
+        }
+        Ok(gxs)
+    }
+
+    fn name(&self) -> String {
+        "Einsum".to_string()
+    }
+}
+
+/// Evaluate the Einstein summation of the variables like `Tensor::einsum`
+///
+/// # Note
+///
+/// The backward does not support the subscripts repeated in an operand like `"ii->i"`.
+pub fn einsum(subscripts: &str, xs: &[Variable]) -> Result<Variable> {
+    let mut func = Function::new(Einsum::new(subscripts));
+    let mut ys = func.forward(xs)?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::{matmul, sum};
+
+    #[test]
+    fn einsum_forward() -> Result<()> {
+        let a = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let b = Variable::new(Tensor::<f64>::arrange([3, 2])?.into());
+        let y = Einsum::new("ij,jk->ik").forward(vec![&a, &b])?;
+        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([2, 3])?.matmul(&Tensor::<f64>::arrange([3, 2])?)?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn einsum_backward_matches_matmul() -> Result<()> {
+        let a = Variable::new(Tensor::<f64>::arrange([2, 2, 3])?.into());
+        let b = Variable::new(Tensor::<f64>::arrange([2, 3, 4])?.into());
+        let weight = Variable::new(Tensor::<f64>::arrange([2, 2, 4])?.into());
+        let mut y = sum(&(&einsum("bij,bjk->bik", &[a.clone(), b.clone()])? * &weight), None, false)?;
+        y.backward()?;
+        let ga = a.grad_result()?.data().clone();
+        let gb = b.grad_result()?.data().clone();
+
+        let a2 = Variable::new(Tensor::<f64>::arrange([2, 2, 3])?.into());
+        let b2 = Variable::new(Tensor::<f64>::arrange([2, 3, 4])?.into());
+        let mut y2 = sum(&(&matmul(&a2, &b2)? * &weight), None, false)?;
+        y2.backward()?;
+        assert_eq!(*y.data(), *y2.data());
+        assert_eq!(ga, *a2.grad_result()?.data());
+        assert_eq!(gb, *b2.grad_result()?.data());
+        Ok(())
+    }
+
+    #[test]
+    fn einsum_backward_summed_out() -> Result<()> {
+        let a = Variable::new(Tensor::new([1.0, 2.0, 3.0, 4.0], [2, 2])?.into());
+        let v = Variable::new(Tensor::new([1.0, 2.0], [2])?.into());
+        let mut y = einsum("ij,k->", &[a.clone(), v.clone()])?;
+        assert_eq!(*y.data(), Tensor::scalar(30.0).into());
+        y.backward()?;
+        assert_eq!(*a.grad_result()?.data(), Tensor::full(3.0, [2, 2]).into());
+        assert_eq!(*v.grad_result()?.data(), Tensor::full(10.0, [2]).into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_einsum_backward_repeated() -> Result<()> {
+        let a = Variable::new(Tensor::new([1.0, 2.0, 3.0, 4.0], [2, 2])?.into());
+        let mut y = einsum("ii->i", &[a.clone()])?;
+        match y.backward() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::Error(
+                    "einsum backward does not support repeated subscripts in an operand: ii->i".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use ktensor::Tensor;
+use crate::Variable;
+use super::scatter_add;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Gather {
+    pub axis: usize,
+    pub index: Tensor<usize>,
+}
+
+impl Gather {
+    pub fn new(axis: usize, index: Tensor<usize>) -> Self {
+        Self { axis, index }
+    }
+}
+
+impl FunctionContent for Gather {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.gather(self.axis, &self.index)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let gy = gys[0];
+        let zeros = Variable::new(xs[0].data().zeros_like()?);
+        let gx = scatter_add(&zeros, self.axis, &self.index, gy)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Gather".to_string()
+    }
+}
+
+/// Gather the values of the variable along the axis like `Tensor::gather`
+pub fn gather(x: &Variable, axis: usize, index: &Tensor<usize>) -> Result<Variable> {
+    let mut func = Function::new(Gather::new(axis, index.clone()));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn gather_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [2, 3])?.into());
+        let y = Gather::new(1, Tensor::new([2, 0], [2, 1])?).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([3.0, 4.0], [2, 1])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_gather_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Gather::new(0, Tensor::new([0], [1])?).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn gather_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [2, 3])?.into());
+        let mut y = gather(&x, 1, &Tensor::new([2, 2, 0, 1], [2, 2])?)?;
+        assert_eq!(*y.data(), Tensor::new([3.0, 3.0, 4.0, 5.0], [2, 2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([0.0, 0.0, 2.0, 1.0, 1.0, 0.0], [2, 3])?.into());
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use ktensor::SliceElem;
 use crate::{Variable, VariableData};
 use super::super::{FunctionContent, Function};
 use crate::utility::check_variable_count;
@@ -8,6 +9,7 @@ pub enum SlicePattern {
     Int(usize),
     Vec(Vec<usize>),
     VecVec(Vec<Vec<usize>>),
+    Slice(Vec<SliceElem>),
 }
 
 #[derive(Debug)]
@@ -32,6 +34,8 @@ impl FunctionContent for GetItem {
                 x.slice_with_one_indexes(v)?,
             SlicePattern::VecVec(ref v) =>
                 x.slice_with_indexes(v.clone())?,
+            SlicePattern::Slice(ref v) =>
+                x.slice(v)?,
         };
         Ok(vec![y.into()])
     }
@@ -80,6 +84,8 @@ impl FunctionContent for GetItemGrad {
                 y.add_at_one_indexes(&x, &v)?,
             SlicePattern::VecVec(ref v) =>
                 y.add_at_with_indexes(&x, v.clone())?,
+            SlicePattern::Slice(ref v) =>
+                y.add_at_slice(&x, v)?,
         };
         Ok(vec![y.into()])
     }
@@ -115,6 +121,11 @@ pub fn get_item_with_indexes(x: &Variable, indexes: &[Vec<usize>]) -> Result<Var
     get_item(x, SlicePattern::VecVec(indexes.to_vec()))
 }
 
+/// Slice the variable with the slice elements created by `ktensor::s!`
+pub fn get_item_with_slice<U: AsRef<[SliceElem]>>(x: &Variable, elems: U) -> Result<Variable> {
+    get_item(x, SlicePattern::Slice(elems.as_ref().to_vec()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,4 +311,48 @@ mod tests {
         )?.into());
         Ok(())
     }
+
+    #[test]
+    fn get_item_forward_slice() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let y = GetItem::new(SlicePattern::Slice(ktensor::s![ktensor::NewAxis, -1, ..;-2].to_vec()))
+            .forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new(
+            vec![5.0, 3.0],
+            vec![1, 2],
+        )?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn get_item_backward_slice() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let dy = Variable::new(Tensor::new(
+            vec![6.0, 7.0, 8.0, 9.0],
+            vec![2, 2],
+        )?.into());
+        let f = GetItem::new(SlicePattern::Slice(ktensor::s![.., [2, 2]].to_vec()));
+        let dx = f.backward(vec![&x], vec![], vec![&dy])?;
+        assert_eq!(*dx[0].data(), Tensor::new(
+            vec![0.0, 0.0, 13.0, 0.0, 0.0, 17.0],
+            vec![2, 3],
+        )?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn get_item_with_slice_normal() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([3, 4])?.into());
+        let mut y = get_item_with_slice(&x, ktensor::s![1.., 1..3])?;
+        assert_eq!(*y.data(), Tensor::new(
+            vec![5.0, 6.0, 9.0, 10.0],
+            vec![2, 2],
+        )?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new(
+            vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0],
+            vec![3, 4],
+        )?.into());
+        Ok(())
+    }
 }
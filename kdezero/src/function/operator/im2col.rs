@@ -0,0 +1,100 @@
+use anyhow::Result;
+use crate::Variable;
+use super::col2im;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Im2col {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+    pub dilation: (usize, usize),
+    pub pad_value: f64,
+}
+
+impl Im2col {
+    pub fn new(kernel_size: (usize, usize), stride: (usize, usize), pad: (usize, usize), dilation: (usize, usize)) -> Self {
+        Self { kernel_size, stride, pad, dilation, pad_value: 0.0 }
+    }
+}
+
+impl FunctionContent for Im2col {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.im2col(self.kernel_size, self.stride, self.pad, self.dilation, self.pad_value)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x_shape = xs[0].shape().to_vec();
+        let gx = col2im(gys[0], &x_shape, self.kernel_size, self.stride, self.pad, self.dilation)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Im2col".to_string()
+    }
+}
+
+/// Extract the patches of the image of shape `[N, C, H, W]` as columns
+///
+/// # Arguments
+///
+/// * `x` - The image
+/// * `kernel_size` - The height and the width of the kernel
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The zero padding of the height and the width
+/// * `dilation` - The dilation along the height and the width
+///
+/// # Returns
+///
+/// * `Result<Variable>` - The columns of shape `[N, C * KH * KW, OH * OW]`
+pub fn im2col(
+    x: &Variable,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+) -> Result<Variable> {
+    let mut func = Function::new(Im2col::new(kernel_size, stride, pad, dilation));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::mul;
+    use crate::test_utility::{assert_numerical_grad, sin_data};
+
+    #[test]
+    fn im2col_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([1, 1, 3, 3])?.into());
+        let y = im2col(&x, (2, 2), (1, 1), (0, 0), (1, 1))?;
+        assert_eq!(y.shape().to_vec(), vec![1, 4, 4]);
+        assert_eq!(*y.data(), Tensor::new(vec![
+            0.0, 1.0, 3.0, 4.0,
+            1.0, 2.0, 4.0, 5.0,
+            3.0, 4.0, 6.0, 7.0,
+            4.0, 5.0, 7.0, 8.0,
+        ], vec![1, 4, 4])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn im2col_backward() -> Result<()> {
+        let x = Variable::new(sin_data(&[2, 2, 4, 3], 0.3)?.into());
+        let weight = Variable::new((&Tensor::<f64>::arrange([2, 18, 2])? * 0.7).cos().into());
+        let mut f = |x: &Variable| mul(&im2col(x, (3, 3), (2, 1), (1, 0), (1, 1))?, &weight);
+        let mut y = f(&x)?;
+        y.backward()?;
+        assert_numerical_grad(&mut f, &x, 1e-5, 1e-7)?;
+        Ok(())
+    }
+}
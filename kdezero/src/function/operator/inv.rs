@@ -0,0 +1,108 @@
+use anyhow::Result;
+use crate::Variable;
+use super::{matmul, neg, transpose};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Inv {}
+
+impl Inv {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FunctionContent for Inv {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.inv()?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let yt = transpose(&inv(x)?)?;
+        let gx = neg(&matmul(&matmul(&yt, gy)?, &yt)?)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Inv".to_string()
+    }
+}
+
+/// Calculate the inverse of the square matrix
+///
+/// The gradient is `-y^T gy y^T` where `y` is the inverse.
+pub fn inv(x: &Variable) -> Result<Variable> {
+    let mut func = Function::new(Inv::new());
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use ktensor::error::TensorError;
+    use crate::error::KDeZeroError;
+    use crate::function::sum_all;
+    use crate::test_utility::assert_approx_eq_tensor;
+
+    #[test]
+    fn inv_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([2.0, 0.0, 0.0, 4.0], [2, 2])?.into());
+        let y = Inv::new().forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([0.5, 0.0, 0.0, 0.25], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_inv_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Inv::new().forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_inv_singular() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 2.0, 2.0, 4.0], [2, 2])?.into());
+        match inv(&x) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::SingularMatrixError());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn inv_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([4.0, 7.0, 2.0, 6.0], [2, 2])?.into());
+        let y = inv(&x)?;
+        assert_approx_eq_tensor(
+            y.data().to_f64_tensor()?,
+            &Tensor::new([0.6, -0.7, -0.2, 0.4], [2, 2])?,
+            1e-12);
+        let mut z = sum_all(&y)?;
+        z.backward()?;
+        assert_approx_eq_tensor(
+            x.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([0.04, -0.08, -0.03, 0.06], [2, 2])?,
+            1e-12);
+        Ok(())
+    }
+}
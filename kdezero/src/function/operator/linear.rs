@@ -1,8 +1,8 @@
 use anyhow::Result;
 use crate::Variable;
-use super::{transpose, matmul};
+use super::matmul_transposed;
 use super::super::{FunctionContent, Function};
-use crate::utility::{check_variable_count, check_variable_count_between};
+use crate::utility::{check_variable_count, check_variable_count_between, sum_to_input_shape};
 
 #[derive(Debug)]
 pub struct Linear {}
@@ -34,10 +34,11 @@ impl FunctionContent for Linear {
         let x0 = xs[0];
         let x1 = xs[1];
         let gy = gys[0];
-        let gx0 = matmul(&gy, &transpose(&x1)?)?;
-        let gx1 = matmul(&transpose(&x0)?, &gy)?;
+        let gx0 = matmul_transposed(gy, x1, false, true)?;
+        let gx1 = matmul_transposed(x0, gy, true, false)?;
         if len == 3 {
-            Ok(vec![gx0, gx1, gy.clone()])
+            let gx2 = sum_to_input_shape(gy.clone(), xs[2])?;
+            Ok(vec![gx0, gx1, gx2])
         } else {
             Ok(vec![gx0, gx1])
         }
@@ -125,6 +126,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn linear_backward_with_broadcast_b() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([3, 1])?.into());
+        let x2 = Variable::new(Tensor::<f64>::arrange([1])?.into());
+        let dy = Variable::new(Tensor::<f64>::arrange([2, 1])?.into());
+        let f = Linear::new();
+        let y = f.forward(vec![&x0, &x1, &x2])?;
+        assert_eq!(*y[0].data(), Tensor::<f64>::new(vec![5.0, 14.0], [2, 1])?.into());
+        let dx = f.backward(vec![&x0, &x1, &x2], vec![], vec![&dy])?;
+        assert_eq!(*dx[2].data(), Tensor::new([1.0], [1])?.into());
+        Ok(())
+    }
+
     #[test]
     fn error_linear_backward_invalid_variable_count_dy() -> Result<()> {
         let x0 = Variable::from(2.0);
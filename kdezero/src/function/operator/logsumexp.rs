@@ -0,0 +1,95 @@
+use anyhow::Result;
+use crate::Variable;
+use super::{exp, sub, mul};
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, reshape_sum_backward};
+
+#[derive(Debug)]
+pub struct LogSumExp {
+    pub axis: usize,
+    pub keepdims: bool,
+}
+
+impl LogSumExp {
+    pub fn new(axis: usize, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+}
+
+impl FunctionContent for LogSumExp {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.logsumexp_with_axis(self.axis, self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&ys, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let x_shape = x.shape().to_vec();
+        let y = reshape_sum_backward(ys[0], &x_shape, &[self.axis], self.keepdims)?;
+        let gy = reshape_sum_backward(gys[0], &x_shape, &[self.axis], self.keepdims)?;
+        let gx = mul(&gy, &exp(&sub(x, &y)?)?)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "LogSumExp".to_string()
+    }
+}
+
+/// Calculate `log(sum(exp(x)))` of the variable along the axis
+pub fn logsumexp(x: &Variable, axis: usize, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(LogSumExp::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn logsumexp_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([0.0, 0.0, 1.0, 1.0], [2, 2])?.into());
+        let y = LogSumExp::new(1, false).forward(vec![&x])?;
+        let y = y[0].data().to_f64_tensor()?.clone();
+        assert_eq!(y.get_shape(), &vec![2]);
+        assert!((y.get_data()[0] - 2.0f64.ln()).abs() < 1e-12);
+        assert!((y.get_data()[1] - 1.0 - 2.0f64.ln()).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn error_logsumexp_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match LogSumExp::new(0, false).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn logsumexp_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([0.0, 2.0f64.ln(), 1.0, 1.0], [2, 2])?.into());
+        let mut y = logsumexp(&x, 1, true)?;
+        assert_eq!(*y.shape(), [2, 1]);
+        y.backward()?;
+        let gx = x.grad_result()?.data().to_f64_tensor()?.clone();
+        let expected = [1.0 / 3.0, 2.0 / 3.0, 0.5, 0.5];
+        for (g, e) in gx.get_data().iter().zip(expected.iter()) {
+            assert!((g - e).abs() < 1e-12);
+        }
+        Ok(())
+    }
+}
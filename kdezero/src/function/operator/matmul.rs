@@ -1,8 +1,9 @@
 use anyhow::Result;
 use crate::Variable;
-use super::transpose;
+use super::reshape;
 use super::super::{FunctionContent, Function};
-use crate::utility::check_variable_count;
+use crate::utility::{check_variable_count, sum_to_input_shape};
+use crate::error::KDeZeroError;
 
 #[derive(Debug)]
 pub struct MatMul {}
@@ -28,9 +29,25 @@ impl FunctionContent for MatMul {
         let x0 = xs[0];
         let x1 = xs[1];
         let gy = gys[0];
-        let gx = matmul(&gy, &transpose(&x1)?)?;
-        let gw = matmul(&transpose(&x0)?, &gy)?;
-        Ok(vec![gx, gw])
+        let x0_shape = x0.shape().to_vec();
+        let x1_shape = x1.shape().to_vec();
+        let mut gy_shape = gy.shape().to_vec();
+        let a = if x0.ndim() == 1 {
+            gy_shape.insert(gy_shape.len() - (x1.ndim() > 1) as usize, 1);
+            reshape(&x0, &[1, x0_shape[0]])?
+        } else {
+            x0.clone()
+        };
+        let b = if x1.ndim() == 1 {
+            gy_shape.push(1);
+            reshape(&x1, &[x1_shape[0], 1])?
+        } else {
+            x1.clone()
+        };
+        let gy = reshape(&gy, &gy_shape)?;
+        let gx = sum_to_input_shape(matmul_transposed(&gy, &b, false, true)?, &a)?;
+        let gw = sum_to_input_shape(matmul_transposed(&a, &gy, true, false)?, &b)?;
+        Ok(vec![reshape(&gx, &x0_shape)?, reshape(&gw, &x1_shape)?])
     }
 
     fn name(&self) -> String {
@@ -38,6 +55,70 @@ impl FunctionContent for MatMul {
     }
 }
 
+/// Matrix multiplication of operands whose last two axes are optionally swapped
+///
+/// The swapped operands are not copied, so this is used for the gradients of `MatMul` and `Linear`.
+#[derive(Debug)]
+pub struct MatMulTransposed {
+    pub transpose_lhs: bool,
+    pub transpose_rhs: bool,
+}
+
+impl MatMulTransposed {
+    pub fn new(transpose_lhs: bool, transpose_rhs: bool) -> Self {
+        Self { transpose_lhs, transpose_rhs }
+    }
+}
+
+impl FunctionContent for MatMulTransposed {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        for x in xs.iter() {
+            if x.ndim() < 2 {
+                return Err(KDeZeroError::InvalidDimension(2, x.ndim()).into())
+            }
+        }
+        let x0 = xs[0].data();
+        let x1 = xs[1].data();
+        let y = x0.matmul_transposed(&x1, self.transpose_lhs, self.transpose_rhs)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        check_variable_count(&gys, 1)?;
+        let a = xs[0];
+        let b = xs[1];
+        let gy = gys[0];
+        let (ga, gb) = match (self.transpose_lhs, self.transpose_rhs) {
+            (false, false) => (matmul_transposed(gy, b, false, true)?, matmul_transposed(a, gy, true, false)?),
+            (true, false) => (matmul_transposed(b, gy, false, true)?, matmul_transposed(a, gy, false, false)?),
+            (false, true) => (matmul_transposed(gy, b, false, false)?, matmul_transposed(gy, a, true, false)?),
+            (true, true) => (matmul_transposed(b, gy, true, true)?, matmul_transposed(gy, a, true, true)?),
+        };
+        Ok(vec![sum_to_input_shape(ga, a)?, sum_to_input_shape(gb, b)?])
+    }
+
+    fn name(&self) -> String {
+        "MatMulTransposed".to_string()
+    }
+}
+
+/// Matrix multiplication with the last two axes of `x0` and `x1` optionally swapped
+///
+/// This is `matmul(&transpose(x0), x1)` and so on without copying the transposed operand.
+/// Both inputs must have at least 2 dimensions.
+pub fn matmul_transposed(x0: &Variable, x1: &Variable, transpose_lhs: bool, transpose_rhs: bool) -> Result<Variable> {
+    let mut func = Function::new(MatMulTransposed::new(transpose_lhs, transpose_rhs));
+    let mut ys = func.forward(&[x0.clone(), x1.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+/// Matrix multiplication like NumPy's `matmul`
+///
+/// The leading dimensions are broadcast as batch dimensions,
+/// and 1-D inputs are treated as vectors.
 pub fn matmul(x0: &Variable, x1: &Variable) -> Result<Variable> {
     let mut func = Function::new(MatMul::new());
     let mut ys = func.forward(&[x0.clone(), x1.clone()])?;
@@ -49,7 +130,8 @@ pub fn matmul(x0: &Variable, x1: &Variable) -> Result<Variable> {
 mod tests {
     use super::*;
     use ktensor::Tensor;
-    use crate::error::KDeZeroError;
+    use super::super::{transpose, transpose_axes};
+    use crate::test_utility::assert_approx_eq_tensor;
 
     #[test]
     fn matmul_forward() -> Result<()> {
@@ -87,6 +169,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn matmul_backward_batched() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 2, 3])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([3, 4])?.into());
+        let mut y = matmul(&x0, &x1)?;
+        assert_eq!(y.shape().to_vec(), vec![2, 2, 4]);
+        y.backward()?;
+        let gy = Tensor::<f64>::ones([2, 2, 4]);
+        let gx = gy.matmul(&x1.data().to_f64_tensor()?.transpose())?;
+        let gw = x0.data().to_f64_tensor()?.permute([0, 2, 1])?.matmul(&gy)?.sum(Some([0]), false);
+        assert_eq!(*x0.grad_result()?.data(), gx.into());
+        assert_eq!(*x1.grad_result()?.data(), gw.into());
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_backward_broadcast_batch() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 1, 2, 3])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([3, 3, 2])?.into());
+        let mut y = matmul(&x0, &x1)?;
+        assert_eq!(y.shape().to_vec(), vec![2, 3, 2, 2]);
+        y.backward()?;
+        assert_eq!(x0.grad_result()?.shape().to_vec(), vec![2, 1, 2, 3]);
+        assert_eq!(x1.grad_result()?.shape().to_vec(), vec![3, 3, 2]);
+        let gw = Tensor::<f64>::ones([2, 2]);
+        let x1_data = x1.data().to_f64_tensor()?.clone();
+        let mut expected = Tensor::<f64>::zeros([2, 3]);
+        for i in 0..3 {
+            let b = Tensor::new(x1_data.get_data()[i * 6..(i + 1) * 6].to_vec(), [3, 2])?;
+            expected = expected + gw.matmul(&b.transpose())?;
+        }
+        let gx = x0.grad_result()?.data().to_f64_tensor()?.clone();
+        assert_eq!(Tensor::new(gx.get_data()[..6].to_vec(), [2, 3])?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_backward_vector() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::new([1.0, 2.0], [2])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let mut y = matmul(&x0, &x1)?;
+        assert_eq!(y.shape().to_vec(), vec![3]);
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::<f64>::new([3.0, 12.0], [2])?.into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::<f64>::new([1.0, 1.0, 1.0, 2.0, 2.0, 2.0], [2, 3])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_backward_vector_vector() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::new([1.0, 2.0], [2])?.into());
+        let x1 = Variable::new(Tensor::<f64>::new([3.0, 4.0], [2])?.into());
+        let mut y = matmul(&x0, &x1)?;
+        assert_eq!(*y.data(), Tensor::<f64>::scalar(11.0).into());
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::<f64>::new([3.0, 4.0], [2])?.into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::<f64>::new([1.0, 2.0], [2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_transposed_forward() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3, 2])?.into());
+        let x1 = Variable::new(Tensor::<f64>::arrange([4, 3])?.into());
+        let y = matmul_transposed(&x0, &x1, true, true)?;
+        let expected = matmul(&transpose_axes(&x0, &[0, 2, 1])?, &transpose(&x1)?)?;
+        assert_eq!(*y.data(), *expected.data());
+        match matmul_transposed(&x0, &Variable::new(Tensor::<f64>::arrange([3])?.into()), false, false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidDimension(2, 1));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_transposed_backward() -> Result<()> {
+        let x0_data = Tensor::<f64>::arrange([3, 2])?.sin();
+        let x1_data = Tensor::<f64>::arrange([2, 4, 3])?.cos();
+        for (transpose_lhs, transpose_rhs) in [(false, true), (true, true)] {
+            let x1 = Variable::new(x1_data.clone().into());
+            let x0 = Variable::new(if transpose_lhs { x0_data.clone() } else { x0_data.transpose().contiguous() }.into());
+            let mut y = matmul_transposed(&x0, &x1, transpose_lhs, transpose_rhs)?;
+            y.backward()?;
+            let a = Variable::new(x0.data().clone());
+            let b = Variable::new(x1.data().clone());
+            let lhs = if transpose_lhs { transpose(&a)? } else { a.clone() };
+            let rhs = if transpose_rhs { transpose_axes(&b, &[0, 2, 1])? } else { b.clone() };
+            let mut expected = matmul(&lhs, &rhs)?;
+            expected.backward()?;
+            assert_eq!(*y.data(), *expected.data());
+            assert_approx_eq_tensor(x0.grad_result()?.data().to_f64_tensor()?, a.grad_result()?.data().to_f64_tensor()?, 1e-10);
+            assert_approx_eq_tensor(x1.grad_result()?.data().to_f64_tensor()?, b.grad_result()?.data().to_f64_tensor()?, 1e-10);
+        }
+        Ok(())
+    }
+
     #[test]
     fn matmul_normal() -> Result<()> {
         let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
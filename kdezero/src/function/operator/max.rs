@@ -0,0 +1,100 @@
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::mul;
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, reshape_sum_backward};
+
+#[derive(Debug)]
+pub struct Max {
+    pub axis: usize,
+    pub keepdims: bool,
+}
+
+impl Max {
+    pub fn new(axis: usize, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+}
+
+impl FunctionContent for Max {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.max_with_axis(self.axis, self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&ys, 1)?;
+        check_variable_count(&gys, 1)?;
+        let gx = extremum_backward(xs[0], ys[0], gys[0], self.axis, self.keepdims)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Max".to_string()
+    }
+}
+
+/// Backward of `Max` and `Min`
+///
+/// The gradient is divided equally among the elements which are equal to the output.
+pub(super) fn extremum_backward(x: &Variable, y: &Variable, gy: &Variable, axis: usize, keepdims: bool) -> Result<Variable> {
+    let x_shape = x.shape().to_vec();
+    let mut y_shape = x_shape.clone();
+    y_shape[axis] = 1;
+    let x_data = x.data();
+    let mask = x_data.eq(&y.data().reshape(&y_shape)?)?;
+    let mask = VariableData::where_(&mask, &x_data.ones_like()?, &x_data.zeros_like()?)?;
+    let count = mask.sum(Some(&vec![axis]), true)?;
+    let weight = mask.div(&count)?;
+    let gy = reshape_sum_backward(gy, &x_shape, &[axis], keepdims)?;
+    mul(&gy, &weight.into())
+}
+
+/// Get the maximum values of the variable along the axis
+pub fn max(x: &Variable, axis: usize, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(Max::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn max_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 0.0], [2, 2])?.into());
+        let y = Max::new(0, false).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([2.0, 3.0], [2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_max_backward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Max::new(0, false).backward(vec![&x], vec![&x], vec![&x, &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn max_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 2.0], [2, 2])?.into());
+        let mut y = max(&x, 1, false)?;
+        assert_eq!(*y.data(), Tensor::new([3.0, 2.0], [2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([0.0, 1.0, 0.5, 0.5], [2, 2])?.into());
+        Ok(())
+    }
+}
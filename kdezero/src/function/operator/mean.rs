@@ -0,0 +1,101 @@
+use anyhow::Result;
+use crate::Variable;
+use super::mul;
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, reshape_sum_backward};
+
+#[derive(Debug)]
+pub struct Mean {
+    pub axis: Option<Vec<usize>>,
+    pub keepdims: bool,
+}
+
+impl Mean {
+    pub fn new(axis: Option<Vec<usize>>, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+
+    fn axis(&self, ndim: usize) -> Vec<usize> {
+        match self.axis.as_ref() {
+            Some(axis) => axis.clone(),
+            None => (0..ndim).collect(),
+        }
+    }
+}
+
+impl FunctionContent for Mean {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.mean_with_axis(&self.axis(x.ndim()), self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let x_shape = x.shape().to_vec();
+        let axis = self.axis(x_shape.len());
+        let n = axis.iter().map(|&i| x_shape[i]).product::<usize>();
+        let gx = reshape_sum_backward(gy, &x_shape, &axis, self.keepdims)?;
+        let scale = gx.data().full_like(1. / (n as f64))?;
+        let gx = mul(&gx, &scale.into())?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Mean".to_string()
+    }
+}
+
+/// Calculate the mean of the variable along the axes
+///
+/// If `axis` is `None`, the mean of all the elements is calculated.
+pub fn mean(x: &Variable, axis: Option<Vec<usize>>, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(Mean::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn mean_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let y = Mean::new(Some(vec![1]), false).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([1.0, 4.0], [2])?.into());
+        let y = Mean::new(None, false).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::scalar(2.5).into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_mean_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Mean::new(None, false).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn mean_normal() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let mut y = mean(&x, Some(vec![0]), true)?;
+        assert_eq!(*y.data(), Tensor::new([1.5, 2.5, 3.5], [1, 3])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::full(0.5, [2, 3]).into());
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use crate::Variable;
+use super::max::extremum_backward;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Min {
+    pub axis: usize,
+    pub keepdims: bool,
+}
+
+impl Min {
+    pub fn new(axis: usize, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+}
+
+impl FunctionContent for Min {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.min_with_axis(self.axis, self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&ys, 1)?;
+        check_variable_count(&gys, 1)?;
+        let gx = extremum_backward(xs[0], ys[0], gys[0], self.axis, self.keepdims)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Min".to_string()
+    }
+}
+
+/// Get the minimum values of the variable along the axis
+pub fn min(x: &Variable, axis: usize, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(Min::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn min_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 0.0], [2, 2])?.into());
+        let y = Min::new(1, true).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([1.0, 0.0], [2, 1])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_min_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Min::new(0, false).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn min_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 0.0], [2, 2])?.into());
+        let mut y = min(&x, 0, true)?;
+        assert_eq!(*y.data(), Tensor::new([1.0, 0.0], [1, 2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([1.0, 0.0, 0.0, 1.0], [2, 2])?.into());
+        Ok(())
+    }
+}
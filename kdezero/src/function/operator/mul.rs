@@ -1,7 +1,7 @@
 use anyhow::Result;
 use crate::Variable;
 use super::super::{FunctionContent, Function};
-use crate::utility::check_variable_count;
+use crate::utility::{check_variable_count, sum_to_input_shape};
 
 #[derive(Debug)]
 pub struct Mul {}
@@ -27,8 +27,8 @@ impl FunctionContent for Mul {
         let x0 = xs[0];
         let x1 = xs[1];
         let gy = gys[0];
-        let gx0 = mul(gy, x1)?;
-        let gx1 = mul(gy, x0)?;
+        let gx0 = sum_to_input_shape(mul(gy, x1)?, x0)?;
+        let gx1 = sum_to_input_shape(mul(gy, x0)?, x1)?;
         Ok(vec![gx0, gx1])
     }
 
@@ -47,6 +47,7 @@ pub fn mul(x0: &Variable, x1: &Variable) -> Result<Variable> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ktensor::Tensor;
     use crate::error::KDeZeroError;
 
     #[test]
@@ -85,6 +86,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mul_backward_broadcast() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let x1 = Variable::new(Tensor::new([2.0, 3.0], [2, 1])?.into());
+        let mut y = mul(&x0, &x1)?;
+        assert_eq!(*y.data(), Tensor::new([0.0, 2.0, 4.0, 9.0, 12.0, 15.0], [2, 3])?.into());
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::new([2.0, 2.0, 2.0, 3.0, 3.0, 3.0], [2, 3])?.into());
+        assert_eq!(*x1.grad_result()?.data(), Tensor::new([3.0, 12.0], [2, 1])?.into());
+        Ok(())
+    }
+
     #[test]
     fn mul_normal() -> Result<()> {
         let x0 = Variable::from(2.0);
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use ktensor::tensor::conv_output_size;
+use crate::Variable;
+use super::{Im2col, reshape, max, mean};
+use super::super::Function;
+use crate::utility::check_dimensions;
+
+/// Extract the windows of `x` of shape `[N, C, H, W]` as `[N, C, KH * KW, OH * OW]`
+fn pool_windows(
+    x: &Variable,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+    pad_value: f64,
+) -> Result<(Variable, Vec<usize>)> {
+    check_dimensions(x, 4)?;
+    let x_shape = x.shape().to_vec();
+    let oh = conv_output_size(x_shape[2], kernel_size.0, stride.0, pad.0, dilation.0)?;
+    let ow = conv_output_size(x_shape[3], kernel_size.1, stride.1, pad.1, dilation.1)?;
+    let mut func = Function::new(Im2col { pad_value, ..Im2col::new(kernel_size, stride, pad, dilation) });
+    let col = func.forward(&[x.clone()])?.remove(0);
+    let windows = reshape(&col, &[x_shape[0], x_shape[1], kernel_size.0 * kernel_size.1, oh * ow])?;
+    Ok((windows, vec![x_shape[0], x_shape[1], oh, ow]))
+}
+
+/// 2-D max pooling
+///
+/// # Arguments
+///
+/// * `x` - The image of shape `[N, C, H, W]`
+/// * `kernel_size` - The height and the width of the window
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The padding of the height and the width, which never becomes the maximum
+/// * `dilation` - The dilation along the height and the width
+///
+/// # Returns
+///
+/// * `Result<Variable>` - The output of shape `[N, C, OH, OW]`
+///
+/// # Note
+///
+/// Like `max`, the gradient is divided equally among the elements of a window equal to the maximum.
+pub fn max_pool2d(
+    x: &Variable,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+) -> Result<Variable> {
+    let (windows, shape) = pool_windows(x, kernel_size, stride, pad, dilation, f64::NEG_INFINITY)?;
+    reshape(&max(&windows, 2, false)?, &shape)
+}
+
+/// 2-D average pooling
+///
+/// # Arguments
+///
+/// * `x` - The image of shape `[N, C, H, W]`
+/// * `kernel_size` - The height and the width of the window
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The zero padding of the height and the width
+///
+/// # Returns
+///
+/// * `Result<Variable>` - The output of shape `[N, C, OH, OW]`
+///
+/// # Note
+///
+/// The padded zeros are included in the average, so every window is divided by `KH * KW`.
+pub fn avg_pool2d(
+    x: &Variable,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+) -> Result<Variable> {
+    let (windows, shape) = pool_windows(x, kernel_size, stride, pad, (1, 1), 0.0)?;
+    reshape(&mean(&windows, Some(vec![2]), false)?, &shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::mul;
+    use crate::test_utility::{assert_numerical_grad, sin_data};
+
+    #[test]
+    fn max_pool2d_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new(vec![
+            1.0, -2.0, 3.0,
+            -4.0, 5.0, -6.0,
+            7.0, -8.0, -9.0,
+        ], vec![1, 1, 3, 3])?.into());
+        let y = max_pool2d(&x, (2, 2), (1, 1), (0, 0), (1, 1))?;
+        assert_eq!(*y.data(), Tensor::new(vec![5.0, 5.0, 7.0, 5.0], vec![1, 1, 2, 2])?.into());
+        let y = max_pool2d(&(-&x), (2, 2), (2, 2), (1, 1), (1, 1))?;
+        assert_eq!(*y.data(), Tensor::new(vec![-1.0, 2.0, 4.0, 9.0], vec![1, 1, 2, 2])?.into());
+        let y = max_pool2d(&x, (2, 2), (1, 1), (0, 0), (2, 2))?;
+        assert_eq!(*y.data(), Tensor::new(vec![7.0], vec![1, 1, 1, 1])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn avg_pool2d_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([1, 1, 4, 4])?.into());
+        let y = avg_pool2d(&x, (2, 2), (2, 2), (0, 0))?;
+        assert_eq!(*y.data(), Tensor::new(vec![2.5, 4.5, 10.5, 12.5], vec![1, 1, 2, 2])?.into());
+        let y = avg_pool2d(&x, (2, 2), (2, 2), (1, 1))?;
+        assert_eq!(y.data().to_f64_tensor()?.get_data()[0], 0.0);
+        assert_eq!(y.data().to_f64_tensor()?.get_data()[1], 0.75);
+        Ok(())
+    }
+
+    fn check_backward(pool: impl Fn(&Variable) -> Result<Variable>) -> Result<()> {
+        let x = Variable::new(sin_data(&[2, 3, 5, 4], 0.37)?.into());
+        let weight = Variable::new((&Tensor::<f64>::arrange([1, 1, 3, 1])? * 0.9).cos().into());
+        let mut f = |x: &Variable| mul(&pool(x)?, &weight);
+        let mut y = f(&x)?;
+        y.backward()?;
+        assert_numerical_grad(&mut f, &x, 1e-5, 1e-7)?;
+        Ok(())
+    }
+
+    #[test]
+    fn max_pool2d_backward() -> Result<()> {
+        check_backward(|x| max_pool2d(x, (3, 2), (2, 2), (1, 0), (1, 2)))
+    }
+
+    #[test]
+    fn avg_pool2d_backward() -> Result<()> {
+        check_backward(|x| avg_pool2d(x, (3, 2), (2, 2), (1, 1)))
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use num_traits::Float;
+use ktensor::Tensor;
+use crate::{Variable, VariableData};
+use crate::error::KDeZeroError;
+use super::mul;
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, reshape_sum_backward};
+
+#[derive(Debug)]
+pub struct Prod {
+    pub axis: usize,
+    pub keepdims: bool,
+}
+
+impl Prod {
+    pub fn new(axis: usize, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+}
+
+impl FunctionContent for Prod {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.prod_with_axis(self.axis, self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let others: VariableData = match &*x.data() {
+            VariableData::F32(x) => product_of_others(x, self.axis)?.into(),
+            VariableData::F64(x) => product_of_others(x, self.axis)?.into(),
+            x => return Err(KDeZeroError::NotImplementedType(
+                "Prod backward".to_string(),
+                x.data_type().to_string(),
+            ).into()),
+        };
+        let x_shape = x.shape().to_vec();
+        let gy = reshape_sum_backward(gy, &x_shape, &[self.axis], self.keepdims)?;
+        let gx = mul(&gy, &others.into())?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Prod".to_string()
+    }
+}
+
+/// Calculate the product of the other elements along the axis for each element
+///
+/// Prefix and suffix products are used instead of `prod / x` so that zeros are handled.
+fn product_of_others<T: Float>(x: &Tensor<T>, axis: usize) -> Result<Tensor<T>> {
+    let shape = x.get_shape().clone();
+    let data = x.get_data();
+    let size = shape[axis];
+    let before = shape[..axis].iter().product::<usize>();
+    let after = shape[axis + 1..].iter().product::<usize>();
+    let mut others = vec![T::one(); data.len()];
+    for i in 0..before {
+        for j in 0..after {
+            let index = |k: usize| (i * size + k) * after + j;
+            let mut acc = T::one();
+            for k in 0..size {
+                others[index(k)] = acc;
+                acc = acc * data[index(k)];
+            }
+            acc = T::one();
+            for k in (0..size).rev() {
+                others[index(k)] = others[index(k)] * acc;
+                acc = acc * data[index(k)];
+            }
+        }
+    }
+    Tensor::new(others, shape)
+}
+
+/// Multiply the elements of the variable along the axis
+///
+/// The gradient is the product of the other elements,
+/// which is treated as a constant in higher order differentiation.
+pub fn prod(x: &Variable, axis: usize, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(Prod::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prod_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 2.0, 3.0, 4.0], [2, 2])?.into());
+        let y = Prod::new(0, false).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([3.0, 8.0], [2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_prod_backward_i32() -> Result<()> {
+        let x = Variable::new(Tensor::new([1, 2], [2])?.into());
+        let dy = Variable::from(1);
+        match Prod::new(0, false).backward(vec![&x], vec![], vec![&dy]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "Prod backward".to_string(),
+                    "i32".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn prod_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([2.0, 3.0, 4.0, 0.0, 5.0, 6.0], [2, 3])?.into());
+        let mut y = prod(&x, 1, false)?;
+        assert_eq!(*y.data(), Tensor::new([24.0, 0.0], [2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([12.0, 8.0, 6.0, 30.0, 0.0, 0.0], [2, 3])?.into());
+        Ok(())
+    }
+}
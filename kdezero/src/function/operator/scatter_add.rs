@@ -0,0 +1,91 @@
+use anyhow::Result;
+use ktensor::Tensor;
+use crate::Variable;
+use super::gather;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct ScatterAdd {
+    pub axis: usize,
+    pub index: Tensor<usize>,
+}
+
+impl ScatterAdd {
+    pub fn new(axis: usize, index: Tensor<usize>) -> Self {
+        Self { axis, index }
+    }
+}
+
+impl FunctionContent for ScatterAdd {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        let x = xs[0].data();
+        let src = xs[1].data();
+        let y = x.scatter_add(self.axis, &self.index, &src)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        check_variable_count(&gys, 1)?;
+        let gy = gys[0];
+        let gsrc = gather(gy, self.axis, &self.index)?;
+        Ok(vec![gy.clone(), gsrc])
+    }
+
+    fn name(&self) -> String {
+        "ScatterAdd".to_string()
+    }
+}
+
+/// Add the values of `src` to the variable at the indices along the axis like `Tensor::scatter_add`
+pub fn scatter_add(x: &Variable, axis: usize, index: &Tensor<usize>, src: &Variable) -> Result<Variable> {
+    let mut func = Function::new(ScatterAdd::new(axis, index.clone()));
+    let mut ys = func.forward(&[x.clone(), src.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn scatter_add_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::zeros([2, 2]).into());
+        let src = Variable::new(Tensor::new([1.0, 2.0], [1, 2])?.into());
+        let y = ScatterAdd::new(0, Tensor::new([1, 0], [1, 2])?).forward(vec![&x, &src])?;
+        assert_eq!(*y[0].data(), Tensor::new([0.0, 2.0, 1.0, 0.0], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_scatter_add_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match ScatterAdd::new(0, Tensor::new([0], [1])?).forward(vec![&x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(2, 1));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn scatter_add_normal() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::zeros([2, 2]).into());
+        let src = Variable::new(Tensor::new([1.0, 2.0, 3.0], [3, 1])?.into());
+        let index = Tensor::new([1, 0, 1], [3, 1])?;
+        let y = scatter_add(&x, 0, &index, &src)?;
+        assert_eq!(*y.data(), Tensor::new([2.0, 0.0, 4.0, 0.0], [2, 2])?.into());
+        let weight = Variable::new(Tensor::new([1.0, 0.0, 10.0, 0.0], [2, 2])?.into());
+        let mut z = crate::function::sum(&(&y * &weight), None, false)?;
+        z.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([1.0, 0.0, 10.0, 0.0], [2, 2])?.into());
+        assert_eq!(*src.grad_result()?.data(), Tensor::new([10.0, 1.0, 10.0], [3, 1])?.into());
+        Ok(())
+    }
+}
@@ -17,17 +17,13 @@ impl FunctionContent for SoftmaxCrossEntropy {
     fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
         check_variable_count(&xs, 2)?;
         let x = xs[0].data();
+        let n = x.shape()[0];
         let t = xs[1].data()
             .to_usize_tensor()?
-            .to_vector()?;
-        let n = x.shape()[0];
-        let log_z = x.log_sum_exp(1)?
-            .broadcast_to(&x.shape())?;
-        let log_p = x.sub(&log_z)?;
-        let log_p = log_p.slice_with_indexes(vec![
-            (0..n).collect(),
-            t,
-        ])?;
+            .reshape([n, 1])?;
+        let log_z = x.logsumexp_with_axis(1, true)?;
+        let log_p = x.sub(&log_z)?
+            .gather(1, &t)?;
         let y = log_p.sum(None, false)?
             .neg()?
             .scalar_mul(1. / (n as f64))?;
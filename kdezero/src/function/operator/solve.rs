@@ -0,0 +1,123 @@
+use anyhow::Result;
+use crate::Variable;
+use super::{matmul, neg, transpose, reshape};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct Solve {}
+
+impl Solve {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FunctionContent for Solve {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        let a = xs[0].data();
+        let b = xs[1].data();
+        let y = a.solve(&b)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 2)?;
+        check_variable_count(&gys, 1)?;
+        let a = xs[0];
+        let b = xs[1];
+        let gy = gys[0];
+        let y = solve(a, b)?;
+        let gb = solve(&transpose(a)?, gy)?;
+        let ga = if b.ndim() == 1 {
+            let n = b.shape()[0];
+            neg(&matmul(&reshape(&gb, &[n, 1])?, &reshape(&y, &[1, n])?)?)?
+        } else {
+            neg(&matmul(&gb, &transpose(&y)?)?)?
+        };
+        Ok(vec![ga, gb])
+    }
+
+    fn name(&self) -> String {
+        "Solve".to_string()
+    }
+}
+
+/// Solve the linear equation `a y = b`
+///
+/// `b` is a vector of shape `[n]` or a matrix of shape `[n, k]`.
+/// The gradients are `gb = solve(a^T, gy)` and `ga = -gb y^T`.
+pub fn solve(a: &Variable, b: &Variable) -> Result<Variable> {
+    let mut func = Function::new(Solve::new());
+    let mut ys = func.forward(&[a.clone(), b.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+    use crate::function::sum_all;
+    use crate::test_utility::assert_approx_eq_tensor;
+
+    #[test]
+    fn solve_forward() -> Result<()> {
+        let a = Variable::new(Tensor::new([2.0, 0.0, 0.0, 4.0], [2, 2])?.into());
+        let b = Variable::new(Tensor::new([2.0, 2.0], [2])?.into());
+        let y = Solve::new().forward(vec![&a, &b])?;
+        assert_eq!(*y[0].data(), Tensor::new([1.0, 0.5], [2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_solve_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Solve::new().forward(vec![&x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(2, 1));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn solve_vector() -> Result<()> {
+        let a = Variable::new(Tensor::new([3.0, 1.0, 1.0, 2.0], [2, 2])?.into());
+        let b = Variable::new(Tensor::new([9.0, 8.0], [2])?.into());
+        let y = solve(&a, &b)?;
+        assert_approx_eq_tensor(y.data().to_f64_tensor()?, &Tensor::new([2.0, 3.0], [2])?, 1e-12);
+        let mut z = sum_all(&y)?;
+        z.backward()?;
+        assert_approx_eq_tensor(
+            b.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([0.2, 0.4], [2])?,
+            1e-12);
+        assert_approx_eq_tensor(
+            a.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([-0.4, -0.6, -0.8, -1.2], [2, 2])?,
+            1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn solve_matrix() -> Result<()> {
+        let a = Variable::new(Tensor::new([3.0, 1.0, 1.0, 2.0], [2, 2])?.into());
+        let b = Variable::new(Tensor::new([9.0, 1.0, 8.0, 2.0], [2, 2])?.into());
+        let mut z = sum_all(&solve(&a, &b)?)?;
+        z.backward()?;
+        assert_approx_eq_tensor(
+            b.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([0.2, 0.2, 0.4, 0.4], [2, 2])?,
+            1e-12);
+        assert_approx_eq_tensor(
+            a.grad_result()?.data().to_f64_tensor()?,
+            &Tensor::new([-0.4, -0.8, -0.8, -1.6], [2, 2])?,
+            1e-12);
+        Ok(())
+    }
+}
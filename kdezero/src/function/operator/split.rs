@@ -0,0 +1,97 @@
+use anyhow::Result;
+use crate::Variable;
+use super::concat;
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, check_variable_count_between};
+
+#[derive(Debug)]
+pub struct Split {
+    pub sizes: Vec<usize>,
+    pub axis: usize,
+}
+
+impl Split {
+    pub fn new(sizes: &[usize], axis: usize) -> Self {
+        Self { sizes: sizes.to_vec(), axis }
+    }
+}
+
+impl FunctionContent for Split {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let ys = x.split(&self.sizes, self.axis)?;
+        Ok(ys.into_iter().map(|y| y.into()).collect())
+    }
+
+    fn backward(&self, _xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count_between(&gys, 1, usize::MAX)?;
+        let gys = gys.into_iter().cloned().collect::<Vec<_>>();
+        let gx = concat(&gys, self.axis)?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Split".to_string()
+    }
+}
+
+/// Split the variable into the variables of the given sizes along the axis
+///
+/// The outputs which are not used in the loss get zero gradients.
+pub fn split(x: &Variable, sizes: &[usize], axis: usize) -> Result<Vec<Variable>> {
+    let mut func = Function::new(Split::new(sizes, axis));
+    func.forward(&[x.clone()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use ktensor::error::TensorError;
+
+    #[test]
+    fn split_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([3, 2])?.into());
+        let ys = Split::new(&[2, 1], 0).forward(vec![&x])?;
+        assert_eq!(ys.len(), 2);
+        assert_eq!(*ys[0].data(), Tensor::<f64>::arrange([2, 2])?.into());
+        assert_eq!(*ys[1].data(), Tensor::new([4.0, 5.0], [1, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_split_forward_sizes() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([3, 2])?.into());
+        match Split::new(&[1, 2], 1).forward(vec![&x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "sizes [1, 2] do not sum to the size of axis 1: 2".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn split_backward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let dy0 = Variable::new(Tensor::<f64>::ones([2, 1]).into());
+        let dy1 = Variable::new(Tensor::full(2.0, [2, 2]).into());
+        let dx = Split::new(&[1, 2], 1).backward(vec![&x], vec![], vec![&dy0, &dy1])?;
+        assert_eq!(*dx[0].data(), Tensor::new([1.0, 2.0, 2.0, 1.0, 2.0, 2.0], [2, 3])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn split_normal_unused_output() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([4])?.into());
+        let ys = split(&x, &[1, 3], 0)?;
+        let mut y = ys[1].clone();
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([0.0, 1.0, 1.0, 1.0], [4])?.into());
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use crate::Variable;
 use super::neg;
 use super::super::{FunctionContent, Function};
-use crate::utility::check_variable_count;
+use crate::utility::{check_variable_count, sum_to_input_shape};
 
 #[derive(Debug)]
 pub struct Sub {}
@@ -26,8 +26,8 @@ impl FunctionContent for Sub {
         check_variable_count(&xs, 2)?;
         check_variable_count(&gys, 1)?;
         let gy = gys[0];
-        let gx0 = gy.clone();
-        let gx1 = neg(gy)?;
+        let gx0 = sum_to_input_shape(gy.clone(), xs[0])?;
+        let gx1 = sum_to_input_shape(neg(gy)?, xs[1])?;
         Ok(vec![gx0, gx1])
     }
 
@@ -46,6 +46,7 @@ pub fn sub(x0: &Variable, x1: &Variable) -> Result<Variable> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ktensor::Tensor;
     use crate::error::KDeZeroError;
 
     #[test]
@@ -84,6 +85,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sub_backward_broadcast() -> Result<()> {
+        let x0 = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        let x1 = Variable::from(1.0);
+        let mut y = sub(&x0, &x1)?;
+        y.backward()?;
+        assert_eq!(*x0.grad_result()?.data(), Tensor::<f64>::ones([2, 3]).into());
+        assert_eq!(*x1.grad_result()?.data(), (-6.0).into());
+        Ok(())
+    }
+
     #[test]
     fn sub_normal() -> Result<()> {
         let x0 = Variable::from(2.0);
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use ktensor::Tensor;
+use crate::Variable;
+use super::{reshape, GetItemGrad, SlicePattern};
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct TopK {
+    pub indexes: Vec<Vec<usize>>,
+    pub shape: Vec<usize>,
+}
+
+impl TopK {
+    /// Create the function which selects the elements at `indices` along the axis
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The indices along the axis returned by `Tensor::topk`
+    /// * `axis` - The axis
+    pub fn new(indices: &Tensor<usize>, axis: usize) -> Self {
+        let shape = indices.get_shape().clone();
+        let mut indexes = vec![Vec::with_capacity(indices.size()); shape.len()];
+        // Push the positions from the last dimension so that `rest` can be divided in order
+        for (i, &index) in indices.get_data().iter().enumerate() {
+            let mut rest = i;
+            for dim in (0..shape.len()).rev() {
+                let p = if dim == axis { index } else { rest % shape[dim] };
+                indexes[dim].push(p);
+                rest /= shape[dim];
+            }
+        }
+        Self { indexes, shape }
+    }
+}
+
+impl FunctionContent for TopK {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        // The trailing axis of size 1 makes each index select a single element
+        let mut shape = x.shape().to_vec();
+        shape.push(1);
+        let y = x.reshape(&shape)?
+            .slice_with_indexes(self.indexes.clone())?
+            .reshape(&self.shape)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x_shape = xs[0].shape().to_vec();
+        let gy = reshape(gys[0], &[gys[0].size(), 1])?;
+        let mut shape = x_shape.clone();
+        shape.push(1);
+        let mut func = Function::new(
+            GetItemGrad::new(
+                SlicePattern::VecVec(self.indexes.clone()),
+                shape));
+        let gx = func.forward(&[gy])?.remove(0);
+        Ok(vec![reshape(&gx, &x_shape)?])
+    }
+
+    fn name(&self) -> String {
+        "TopK".to_string()
+    }
+}
+
+/// Get the `k` largest elements of the variable along the axis
+///
+/// # Returns
+///
+/// * `Result<(Variable, Variable)>` - The values in descending order and their indices along the axis
+///
+/// # Note
+///
+/// The gradient of the values is scattered back to the selected positions.
+/// The indices are not differentiable.
+pub fn topk(x: &Variable, k: usize, axis: usize) -> Result<(Variable, Variable)> {
+    let (_, indices) = x.data().topk(k, axis)?;
+    let mut func = Function::new(TopK::new(indices.to_usize_tensor()?, axis));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok((y, Variable::new(indices)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn topk_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 4.0, 2.0, 3.0, 0.0, 5.0], [3, 2])?.into());
+        let indices = Tensor::new([1, 2, 0, 0], [2, 2])?;
+        let y = TopK::new(&indices, 0).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([2.0, 5.0, 1.0, 4.0], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_topk_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        let indices = Tensor::new([0], [1])?;
+        match TopK::new(&indices, 0).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn topk_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 4.0, 2.0, 3.0, 0.0, 5.0], [2, 3])?.into());
+        let (mut y, indices) = topk(&x, 2, 1)?;
+        assert_eq!(*y.data(), Tensor::new([4.0, 2.0, 5.0, 3.0], [2, 2])?.into());
+        assert_eq!(*indices.data(), Tensor::<usize>::new([1, 2, 2, 0], [2, 2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([0.0, 1.0, 1.0, 1.0, 0.0, 1.0], [2, 3])?.into());
+        Ok(())
+    }
+}
@@ -49,7 +49,7 @@ mod tests {
     fn transpose_forward() -> Result<()> {
         let x = Variable::new(Tensor::<f64>::arrange([3, 2])?.into());
         let y = Transpose::new().forward(vec![&x])?;
-        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([3, 2])?.transpose().into());
+        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([3, 2])?.transpose().contiguous().into());
         Ok(())
     }
 
@@ -100,7 +100,7 @@ mod tests {
     fn transpose_normal() -> Result<()> {
         let x = Variable::new(Tensor::<f64>::arrange([3, 2])?.into());
         let y = transpose(&x)?;
-        assert_eq!(*y.data(), Tensor::<f64>::arrange([3, 2])?.transpose().into());
+        assert_eq!(*y.data(), Tensor::<f64>::arrange([3, 2])?.transpose().contiguous().into());
         Ok(())
     }
 }
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use crate::Variable;
+use super::super::{FunctionContent, Function};
+use crate::utility::check_variable_count;
+
+#[derive(Debug)]
+pub struct TransposeAxes {
+    pub axes: Vec<usize>,
+}
+
+impl TransposeAxes {
+    pub fn new(axes: &[usize]) -> Self {
+        Self { axes: axes.to_vec() }
+    }
+
+    fn inverse_axes(&self) -> Vec<usize> {
+        let mut inverse = vec![0; self.axes.len()];
+        for (i, &axis) in self.axes.iter().enumerate() {
+            inverse[axis] = i;
+        }
+        inverse
+    }
+}
+
+impl FunctionContent for TransposeAxes {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.permute(&self.axes)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, _xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&gys, 1)?;
+        let gy = gys[0];
+        let gx = transpose_axes(gy, &self.inverse_axes())?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "TransposeAxes".to_string()
+    }
+}
+
+/// Permute the axes of the variable
+///
+/// The i-th axis of the output is the `axes[i]`-th axis of `x`.
+pub fn transpose_axes(x: &Variable, axes: &[usize]) -> Result<Variable> {
+    let mut func = Function::new(TransposeAxes::new(axes));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use ktensor::error::TensorError;
+
+    #[test]
+    fn transpose_axes_forward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3, 4])?.into());
+        let y = TransposeAxes::new(&[2, 0, 1]).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::<f64>::arrange([2, 3, 4])?.permute([2, 0, 1])?.contiguous().into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_transpose_axes_forward_not_permutation() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3])?.into());
+        match TransposeAxes::new(&[0, 0]).forward(vec![&x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "axes is not a permutation: [0, 0]".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn transpose_axes_backward() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3, 4])?.into());
+        let dy = Variable::new(Tensor::<f64>::arrange([4, 2, 3])?.into());
+        let f = TransposeAxes::new(&[2, 0, 1]);
+        let dx = f.backward(vec![&x], vec![], vec![&dy])?;
+        assert_eq!(*dx[0].data(), Tensor::<f64>::arrange([4, 2, 3])?.permute([1, 2, 0])?.contiguous().into());
+        Ok(())
+    }
+
+    #[test]
+    fn transpose_axes_normal() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3, 4])?.into());
+        let mut y = transpose_axes(&x, &[1, 2, 0])?;
+        y.backward()?;
+        assert_eq!(y.shape().to_vec(), vec![3, 4, 2]);
+        assert_eq!(*x.grad_result()?.data(), Tensor::<f64>::ones([2, 3, 4]).into());
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+use crate::Variable;
+use super::{mean, sub, mul, broadcast_to};
+use super::super::{FunctionContent, Function};
+use crate::utility::{check_variable_count, reshape_sum_backward};
+
+#[derive(Debug)]
+pub struct Var {
+    pub axis: Option<Vec<usize>>,
+    pub keepdims: bool,
+}
+
+impl Var {
+    pub fn new(axis: Option<Vec<usize>>, keepdims: bool) -> Self {
+        Self { axis, keepdims }
+    }
+
+    fn axis(&self, ndim: usize) -> Vec<usize> {
+        match self.axis.as_ref() {
+            Some(axis) => axis.clone(),
+            None => (0..ndim).collect(),
+        }
+    }
+}
+
+impl FunctionContent for Var {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0].data();
+        let y = x.var_with_axis(&self.axis(x.ndim()), self.keepdims)?;
+        Ok(vec![y.into()])
+    }
+
+    fn backward(&self, xs: Vec<&Variable>, _ys: Vec<&Variable>, gys: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        check_variable_count(&gys, 1)?;
+        let x = xs[0];
+        let gy = gys[0];
+        let x_shape = x.shape().to_vec();
+        let axis = self.axis(x_shape.len());
+        let n = axis.iter().map(|&i| x_shape[i]).product::<usize>();
+        let x_mean = broadcast_to(&mean(x, Some(axis.clone()), true)?, &x_shape)?;
+        let diff = sub(x, &x_mean)?;
+        let scale = diff.data().full_like(2. / (n as f64))?;
+        let gy = reshape_sum_backward(gy, &x_shape, &axis, self.keepdims)?;
+        let gx = mul(&mul(&gy, &diff)?, &scale.into())?;
+        Ok(vec![gx])
+    }
+
+    fn name(&self) -> String {
+        "Var".to_string()
+    }
+}
+
+/// Calculate the variance of the variable along the axes
+///
+/// If `axis` is `None`, the variance of all the elements is calculated.
+/// The variance is divided by the number of the elements (not by `n - 1`).
+pub fn var(x: &Variable, axis: Option<Vec<usize>>, keepdims: bool) -> Result<Variable> {
+    let mut func = Function::new(Var::new(axis, keepdims));
+    let mut ys = func.forward(&[x.clone()])?;
+    let y = ys.remove(0);
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+
+    #[test]
+    fn var_forward() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 2.0], [2, 2])?.into());
+        let y = Var::new(Some(vec![1]), false).forward(vec![&x])?;
+        assert_eq!(*y[0].data(), Tensor::new([1.0, 0.0], [2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_var_forward_invalid_variable_count() -> Result<()> {
+        let x = Variable::from(2.0);
+        match Var::new(None, false).forward(vec![&x.clone(), &x]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::InvalidVariableCount(1, 2));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn var_normal() -> Result<()> {
+        let x = Variable::new(Tensor::new([1.0, 3.0, 2.0, 6.0], [2, 2])?.into());
+        let mut y = var(&x, Some(vec![1]), false)?;
+        assert_eq!(*y.data(), Tensor::new([1.0, 4.0], [2])?.into());
+        y.backward()?;
+        assert_eq!(*x.grad_result()?.data(), Tensor::new([-1.0, 1.0, -2.0, 2.0], [2, 2])?.into());
+        Ok(())
+    }
+}
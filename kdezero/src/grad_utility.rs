@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Model, Variable, VariableData};
+use crate::error::KDeZeroError;
+
+/// The squared L2 norm of the data accumulated in f64
+fn squared_norm(data: &VariableData) -> Result<f64> {
+    Ok(match data {
+        VariableData::F32(x) => x.get_data().iter().map(|v| (*v as f64).powi(2)).sum(),
+        VariableData::F64(x) => x.get_data().iter().map(|v| v.powi(2)).sum(),
+        _ => return Err(KDeZeroError::NotImplementedType(
+            "squared_norm".to_string(),
+            data.data_type().to_string(),
+        ).into()),
+    })
+}
+
+/// The L2 norm of the gradient of each parameter
+///
+/// Parameters without a gradient are skipped.
+///
+/// # Arguments
+///
+/// * `model` - The model whose parameters are checked
+///
+/// # Returns
+///
+/// * `Result<HashMap<String, f64>>` - The norms keyed by the parameter names
+pub fn grad_norms(model: &Model) -> Result<HashMap<String, f64>> {
+    let mut norms = HashMap::new();
+    for (name, param) in model.get_params() {
+        if let Some(grad) = param.grad_clone() {
+            norms.insert(name, squared_norm(&grad.data())?.sqrt());
+        }
+    }
+    Ok(norms)
+}
+
+/// The L2 norm of all the gradients of the model taken as one vector
+pub fn grad_norm(model: &Model) -> Result<f64> {
+    Ok(grad_norms(model)?
+        .values()
+        .map(|norm| norm * norm)
+        .sum::<f64>()
+        .sqrt())
+}
+
+/// The sorted names of the parameters whose gradient contains infinity or NaN
+pub fn non_finite_grads(model: &Model) -> Result<Vec<String>> {
+    let mut names = vec![];
+    for (name, param) in model.get_params() {
+        if let Some(grad) = param.grad_clone() {
+            if !grad.data().all_finite()? {
+                names.push(name);
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Check if any gradient of the model contains infinity or NaN
+///
+/// This can be used to skip `Optimizer::update` for a batch which exploded.
+pub fn has_non_finite_grad(model: &Model) -> Result<bool> {
+    Ok(!non_finite_grads(model)?.is_empty())
+}
+
+fn map_grads(model: &mut Model, f: impl Fn(&VariableData) -> Result<VariableData>) -> Result<()> {
+    for (_, mut param) in model.get_params() {
+        if let Some(grad) = param.grad_clone() {
+            let data = f(&grad.data())?;
+            param.set_grad(Variable::new(data));
+        }
+    }
+    Ok(())
+}
+
+/// Scale the gradients so that their total L2 norm is at most `max_norm`
+///
+/// # Arguments
+///
+/// * `model` - The model whose gradients are clipped
+/// * `max_norm` - The maximum total norm
+///
+/// # Returns
+///
+/// * `Result<f64>` - The total norm before clipping
+///
+/// # Note
+///
+/// If the total norm is not finite, the gradients are left as they are.
+pub fn clip_grad_norm(model: &mut Model, max_norm: f64) -> Result<f64> {
+    let total_norm = grad_norm(model)?;
+    let coef = max_norm / (total_norm + 1e-6);
+    if total_norm.is_finite() && coef < 1.0 {
+        map_grads(model, |grad| grad.scalar_mul(coef))?;
+    }
+    Ok(total_norm)
+}
+
+/// Clip each element of the gradients to `[-clip_value, clip_value]`
+///
+/// # Arguments
+///
+/// * `model` - The model whose gradients are clipped
+/// * `clip_value` - The maximum absolute value
+///
+/// # Returns
+///
+/// * `Result<f64>` - The total norm before clipping
+pub fn clip_grad_value(model: &mut Model, clip_value: f64) -> Result<f64> {
+    let total_norm = grad_norm(model)?;
+    map_grads(model, |grad| grad.clip(-clip_value, clip_value))?;
+    Ok(total_norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::test_utility::assert_approx_eq;
+    use crate::VariableType;
+    use crate::model::MLP;
+    use crate::function::sigmoid;
+
+    /// An MLP whose bias gradient is filled with `bias` and weight gradient with `weight`
+    fn model_with_grads(bias: f64, weight: f64) -> Result<Model> {
+        let model = Model::new(MLP::new(&[2, 1], sigmoid, VariableType::F64)?);
+        let mut params = model.get_params().into_iter().collect::<Vec<_>>();
+        params.sort_by(|(x, _), (y, _)| x.cmp(y));
+        for ((_, param), value) in params.iter_mut().zip([bias, weight]) {
+            let shape = param.data().shape().to_vec();
+            let size = shape.iter().product::<usize>();
+            let grad = Tensor::new(vec![value; size], shape)?;
+            param.set_grad(Variable::new(grad.into()));
+        }
+        Ok(model)
+    }
+
+    fn grads(model: &Model) -> Result<Vec<f64>> {
+        let mut params = model.get_params().into_iter().collect::<Vec<_>>();
+        params.sort_by(|(x, _), (y, _)| x.cmp(y));
+        let mut values = vec![];
+        for (_, param) in params {
+            values.extend(param.grad_result()?.data().to_f64_tensor()?.get_data().iter());
+        }
+        Ok(values)
+    }
+
+    #[test]
+    fn grad_norm_normal() -> Result<()> {
+        let model = model_with_grads(3.0, 4.0)?;
+        let norms = grad_norms(&model)?;
+        assert_eq!(norms.len(), 2);
+        assert_approx_eq(grad_norm(&model)?, 41.0f64.sqrt(), 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn clip_grad_norm_normal() -> Result<()> {
+        let mut model = model_with_grads(3.0, 4.0)?;
+        let total = clip_grad_norm(&mut model, 1.0)?;
+        assert_approx_eq(total, 41.0f64.sqrt(), 1e-12);
+        assert_approx_eq(grad_norm(&model)?, 1.0, 1e-6);
+        let total = clip_grad_norm(&mut model, 10.0)?;
+        assert_approx_eq(total, 1.0, 1e-6);
+        assert_approx_eq(grad_norm(&model)?, 1.0, 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn clip_grad_value_normal() -> Result<()> {
+        let mut model = model_with_grads(-3.0, 0.5)?;
+        clip_grad_value(&mut model, 1.0)?;
+        assert_eq!(grads(&model)?, vec![-1.0, 0.5, 0.5]);
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_grads_normal() -> Result<()> {
+        let mut model = model_with_grads(f64::INFINITY, 1.0)?;
+        assert!(has_non_finite_grad(&model)?);
+        assert_eq!(non_finite_grads(&model)?.len(), 1);
+        let total = clip_grad_norm(&mut model, 1.0)?;
+        assert!(total.is_infinite());
+        assert_eq!(grads(&model)?[1..], [1.0, 1.0]);
+        let model = model_with_grads(1.0, 2.0)?;
+        assert!(!has_non_finite_grad(&model)?);
+        Ok(())
+    }
+}
@@ -6,7 +6,10 @@ use std::cell::{RefCell, Ref};
 use anyhow::Result;
 use crate::{Variable, VariableWeak};
 
-pub use contents::Linear;
+pub use contents::{
+    Linear, Conv2d, ConvTranspose2d, MaxPool2d, AvgPool2d, Flatten,
+    BatchNorm, BatchNorm1d, BatchNorm2d, LayerNorm, GroupNorm,
+};
 
 pub trait LayerContent {
     fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>>;
@@ -30,6 +33,22 @@ pub trait LayerContent {
         }
         params
     }
+
+    /// The variables which are saved with the parameters but not updated by the optimizer,
+    /// such as the running statistics of `BatchNorm2d`
+    fn get_buffers(&self) -> HashMap<String, Variable> {
+        HashMap::new()
+    }
+
+    fn get_buffers_recursive(&self) -> HashMap<String, Variable> {
+        let mut buffers = self.get_buffers();
+        for (layer_name, layer) in self.get_layers() {
+            for (name, buffer) in layer.get_buffers_recursive() {
+                buffers.insert(format!("{}.{}", layer_name, name), buffer);
+            }
+        }
+        buffers
+    }
 }
 
 pub struct LayerInner {
@@ -120,4 +139,9 @@ impl Layer {
         let inner = self.inner.borrow();
         inner.layer.get_params_recursive()
     }
+
+    pub fn get_buffers_recursive(&self) -> HashMap<String, Variable> {
+        let inner = self.inner.borrow();
+        inner.layer.get_buffers_recursive()
+    }
 }
@@ -1,3 +1,19 @@
 mod linear;
+mod conv2d;
+mod conv_transpose2d;
+mod max_pool2d;
+mod avg_pool2d;
+mod flatten;
+mod batch_norm;
+mod layer_norm;
+mod group_norm;
 
 pub use linear::Linear;
+pub use conv2d::Conv2d;
+pub use conv_transpose2d::ConvTranspose2d;
+pub use max_pool2d::MaxPool2d;
+pub use avg_pool2d::AvgPool2d;
+pub use flatten::Flatten;
+pub use batch_norm::{BatchNorm, BatchNorm1d, BatchNorm2d};
+pub use layer_norm::LayerNorm;
+pub use group_norm::GroupNorm;
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use crate::Variable;
+use super::super::LayerContent;
+use crate::utility::check_variable_count;
+use crate::function;
+
+/// 2-D average pooling layer
+///
+/// # Fields
+///
+/// * `kernel_size` - The height and the width of the window
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The zero padding of the height and the width, which is included in the average
+#[derive(Debug, Clone)]
+pub struct AvgPool2d {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+}
+
+impl AvgPool2d {
+    pub fn new(kernel_size: (usize, usize), stride: (usize, usize), pad: (usize, usize)) -> Self {
+        Self {
+            kernel_size,
+            stride,
+            pad,
+        }
+    }
+}
+
+impl LayerContent for AvgPool2d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let y = function::avg_pool2d(xs[0], self.kernel_size, self.stride, self.pad)?;
+        Ok(vec![y])
+    }
+}
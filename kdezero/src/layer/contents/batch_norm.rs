@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableType, VariableData, is_train_enabled};
+use super::super::LayerContent;
+use super::conv2d::check_param_type;
+use crate::utility::{check_variable_count, check_dimensions};
+use crate::function::{add, sub, mul, pow, mean, var, reshape};
+use crate::error::KDeZeroError;
+
+/// Standardize `x` over `axis`
+///
+/// # Returns
+///
+/// * `Result<(Variable, Variable, Variable)>` - The standardized `x`,
+///   and the mean and the variance over `axis` with the dimensions kept
+pub(super) fn normalize(x: &Variable, axis: &[usize], eps: f64) -> Result<(Variable, Variable, Variable)> {
+    let x_mean = mean(x, Some(axis.to_vec()), true)?;
+    let x_var = var(x, Some(axis.to_vec()), true)?;
+    let y = scale_by_std(&sub(x, &x_mean)?, &x_var, eps)?;
+    Ok((y, x_mean, x_var))
+}
+
+/// Divide `x` by `sqrt(x_var + eps)`
+fn scale_by_std(x: &Variable, x_var: &Variable, eps: f64) -> Result<Variable> {
+    let eps = Variable::new(x_var.data().full_like(eps)?);
+    mul(x, &pow(&add(x_var, &eps)?, -0.5)?)
+}
+
+/// Multiply `x` by `weight` and add `bias`, both reshaped to `shape` to broadcast
+pub(super) fn affine(x: &Variable, weight: &Variable, bias: &Variable, shape: &[usize]) -> Result<Variable> {
+    add(&mul(x, &reshape(weight, shape)?)?, &reshape(bias, shape)?)
+}
+
+/// Create the weight filled with 1 and the bias filled with 0 of an affine transformation
+pub(super) fn affine_params(shape: &[usize], variable_type: VariableType) -> Result<(Variable, Variable)> {
+    let zeros = VariableData::zeros_type(shape, variable_type)?;
+    Ok((Variable::new(zeros.ones_like()?), Variable::new(zeros)))
+}
+
+/// Batch normalization over the channel axis 1, shared by `BatchNorm1d` and `BatchNorm2d`
+///
+/// In the training mode, the input is normalized by the statistics of the batch
+/// and the running statistics are updated as `(1 - momentum) * running + momentum * batch`,
+/// where the unbiased variance is used. In the inference mode, the running statistics are used.
+///
+/// # Fields
+///
+/// * `weight` - The scale of shape `[C]`
+/// * `bias` - The shift of shape `[C]`
+/// * `running_mean` - The running mean of shape `[C]`, a buffer
+/// * `running_var` - The running variance of shape `[C]`, a buffer
+/// * `num_features` - The number of the channels `C`
+/// * `eps` - The value added to the variance
+/// * `momentum` - The weight of the batch statistics in the running statistics
+/// * `variable_type` - The type of the parameters and the buffers
+#[derive(Debug)]
+pub struct BatchNorm {
+    pub weight: Variable,
+    pub bias: Variable,
+    pub running_mean: Variable,
+    pub running_var: Variable,
+    pub num_features: usize,
+    pub eps: f64,
+    pub momentum: f64,
+    pub variable_type: VariableType,
+}
+
+impl BatchNorm {
+    pub fn new(num_features: usize, variable_type: VariableType) -> Result<Self> {
+        check_param_type(variable_type, "BatchNorm")?;
+        let (weight, bias) = affine_params(&[num_features], variable_type)?;
+        let running_mean = VariableData::zeros_type(&[num_features], variable_type)?;
+        let running_var = running_mean.ones_like()?;
+        let (running_mean, running_var) = (Variable::new(running_mean), Variable::new(running_var));
+        Ok(Self {
+            weight,
+            bias,
+            running_mean,
+            running_var,
+            num_features,
+            eps: 1e-5,
+            momentum: 0.1,
+            variable_type,
+        })
+    }
+
+    fn forward(&self, x: &Variable) -> Result<Variable> {
+        let x_shape = x.shape().to_vec();
+        if x_shape[1] != self.num_features {
+            return Err(KDeZeroError::Error(format!(
+                "BatchNorm: expected {} channels, actual {}", self.num_features, x_shape[1]
+            )).into())
+        }
+        let mut shape = vec![1; x_shape.len()];
+        shape[1] = self.num_features;
+        let y = if is_train_enabled() {
+            let axis = (0..x_shape.len()).filter(|&i| i != 1).collect::<Vec<_>>();
+            let (y, x_mean, x_var) = normalize(x, &axis, self.eps)?;
+            let n = axis.iter().map(|&i| x_shape[i]).product::<usize>();
+            let unbiased = if n > 1 { n as f64 / (n - 1) as f64 } else { 1.0 };
+            self.update_running(&self.running_mean, &x_mean.data(), 1.0)?;
+            self.update_running(&self.running_var, &x_var.data(), unbiased)?;
+            y
+        } else {
+            let running_mean = Variable::new(self.running_mean.data().reshape(&shape)?);
+            let running_var = Variable::new(self.running_var.data().reshape(&shape)?);
+            scale_by_std(&sub(x, &running_mean)?, &running_var, self.eps)?
+        };
+        affine(&y, &self.weight, &self.bias, &shape)
+    }
+
+    fn update_running(&self, running: &Variable, batch: &VariableData, scale: f64) -> Result<()> {
+        let batch = batch.reshape(&[self.num_features])?.scalar_mul(scale * self.momentum)?;
+        let data = running.data().scalar_mul(1.0 - self.momentum)?.add(&batch)?;
+        running.clone().set_data(data);
+        Ok(())
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        HashMap::from([
+            ("weight".to_string(), self.weight.clone()),
+            ("bias".to_string(), self.bias.clone()),
+        ])
+    }
+
+    fn get_buffers(&self) -> HashMap<String, Variable> {
+        HashMap::from([
+            ("running_mean".to_string(), self.running_mean.clone()),
+            ("running_var".to_string(), self.running_var.clone()),
+        ])
+    }
+}
+
+/// Batch normalization of an input of shape `[N, C]` or `[N, C, L]`
+#[derive(Debug)]
+pub struct BatchNorm1d(pub BatchNorm);
+
+impl BatchNorm1d {
+    pub fn new(num_features: usize, variable_type: VariableType) -> Result<Self> {
+        Ok(Self(BatchNorm::new(num_features, variable_type)?))
+    }
+}
+
+impl LayerContent for BatchNorm1d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        if x.ndim() != 3 {
+            check_dimensions(x, 2)?;
+        }
+        Ok(vec![self.0.forward(x)?])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        self.0.get_params()
+    }
+
+    fn get_buffers(&self) -> HashMap<String, Variable> {
+        self.0.get_buffers()
+    }
+}
+
+/// Batch normalization of an image of shape `[N, C, H, W]`
+#[derive(Debug)]
+pub struct BatchNorm2d(pub BatchNorm);
+
+impl BatchNorm2d {
+    pub fn new(num_features: usize, variable_type: VariableType) -> Result<Self> {
+        Ok(Self(BatchNorm::new(num_features, variable_type)?))
+    }
+}
+
+impl LayerContent for BatchNorm2d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        check_dimensions(x, 4)?;
+        Ok(vec![self.0.forward(x)?])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        self.0.get_params()
+    }
+
+    fn get_buffers(&self) -> HashMap<String, Variable> {
+        self.0.get_buffers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::{eval_mode, no_grad};
+    use crate::test_utility::{assert_approx_eq_tensor, assert_numerical_grad, sin_data};
+
+    #[test]
+    fn batch_norm1d_forward() -> Result<()> {
+        let layer = BatchNorm1d::new(2, VariableType::F64)?;
+        let x = Variable::new(Tensor::new(vec![1.0, 10.0, 3.0, 20.0], vec![2, 2])?.into());
+        let y = layer.forward(vec![&x])?.remove(0);
+        let expected = Tensor::new(vec![-1.0, -1.0, 1.0, 1.0], vec![2, 2])?;
+        assert_approx_eq_tensor(y.data().to_f64_tensor()?, &expected, 1e-4);
+        // mean: 0.1 * [2, 15], unbiased variance: 0.9 + 0.1 * [2, 50]
+        assert_approx_eq_tensor(
+            layer.0.running_mean.data().to_f64_tensor()?, &Tensor::new(vec![0.2, 1.5], vec![2])?, 1e-12);
+        assert_approx_eq_tensor(
+            layer.0.running_var.data().to_f64_tensor()?, &Tensor::new(vec![1.1, 5.9], vec![2])?, 1e-12);
+
+        let _guard = eval_mode();
+        let y = layer.forward(vec![&x])?.remove(0);
+        let s = [1.0 / (1.1f64 + 1e-5).sqrt(), 1.0 / (5.9f64 + 1e-5).sqrt()];
+        let expected = Tensor::new(vec![
+            0.8 * s[0], 8.5 * s[1], 2.8 * s[0], 18.5 * s[1],
+        ], vec![2, 2])?;
+        assert_approx_eq_tensor(y.data().to_f64_tensor()?, &expected, 1e-12);
+        assert_approx_eq_tensor(
+            layer.0.running_mean.data().to_f64_tensor()?, &Tensor::new(vec![0.2, 1.5], vec![2])?, 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn batch_norm2d_backward() -> Result<()> {
+        let x = Variable::new(sin_data(&[2, 3, 2, 2], 0.7)?.into());
+        let weight = Variable::new(sin_data(&[2, 3, 2, 2], 1.3)?.into());
+        let layer = BatchNorm2d::new(3, VariableType::F64)?;
+        layer.0.weight.clone().set_data(sin_data(&[3], 0.5)?.cos().into());
+        let mut y = mul(&layer.forward(vec![&x])?.remove(0), &weight)?;
+        y.backward()?;
+        let mut f = |x: &Variable| -> Result<Variable> {
+            let _guard = no_grad();
+            mul(&layer.forward(vec![x])?.remove(0), &weight)
+        };
+        assert_numerical_grad(&mut f, &x, 1e-5, 1e-6)?;
+        assert_eq!(layer.get_params().len(), 2);
+        assert_eq!(layer.get_buffers().len(), 2);
+        Ok(())
+    }
+}
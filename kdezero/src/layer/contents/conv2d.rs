@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableType, VariableData};
+use super::super::LayerContent;
+use crate::utility::{check_variable_count, check_dimensions, check_groups};
+use crate::function;
+use crate::error::KDeZeroError;
+
+/// Check that the type can be used for the parameters of a layer
+pub(super) fn check_param_type(variable_type: VariableType, name: &str) -> Result<()> {
+    match variable_type {
+        VariableType::F32 | VariableType::F64 => Ok(()),
+        _ => Err(KDeZeroError::NotImplementedType(
+            variable_type.to_string(),
+            name.to_string(),
+        ).into()),
+    }
+}
+
+/// Set a kernel drawn from `N(0, 1 / fan_in)` to `weight`
+///
+/// The handle is shared with the layer, so this also initializes a weight created as `VariableData::none()`.
+pub(super) fn init_kernel(weight: &Variable, shape: &[usize], variable_type: VariableType) -> Result<()> {
+    let fan_in = shape[1..].iter().product::<usize>().max(1);
+    let data = VariableData::random_normal(shape, variable_type)?
+        .scalar_mul((1.0 / fan_in as f64).sqrt())?;
+    weight.clone().set_data(data);
+    Ok(())
+}
+
+/// 2-D convolution layer
+///
+/// # Fields
+///
+/// * `weight` - The kernel of shape `[OC, C / groups, KH, KW]`,
+///   which is `VariableData::None` until the first forward if `in_channels` is not given
+/// * `bias` - The bias of shape `[OC]`
+/// * `out_channels` - The number of the output channels
+/// * `kernel_size` - The height and the width of the kernel
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The zero padding of the height and the width
+/// * `dilation` - The dilation along the height and the width
+/// * `groups` - The number of groups the channels are split into, set by `set_groups`
+/// * `variable_type` - The type of the parameters
+#[derive(Debug)]
+pub struct Conv2d {
+    pub weight: Variable,
+    pub bias: Option<Variable>,
+    pub out_channels: usize,
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+    pub dilation: (usize, usize),
+    groups: usize,
+    pub variable_type: VariableType,
+}
+
+impl Conv2d {
+    /// Create a convolution layer
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - The number of the input channels, or `None` to infer it from the first input
+    /// * `out_channels` - The number of the output channels
+    /// * `kernel_size` - The height and the width of the kernel
+    /// * `stride` - The strides along the height and the width
+    /// * `pad` - The zero padding of the height and the width
+    /// * `bias` - Whether the layer has a bias
+    /// * `variable_type` - The type of the parameters
+    pub fn new(
+        in_channels: Option<usize>,
+        out_channels: usize,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        bias: bool,
+        variable_type: VariableType,
+    ) -> Result<Self> {
+        check_param_type(variable_type, "Conv2d")?;
+        let bias = if bias {
+            Some(VariableData::zeros_type(&[out_channels], variable_type)?.into())
+        } else {
+            None
+        };
+        let layer = Self {
+            weight: Variable::new(VariableData::none()),
+            bias,
+            out_channels,
+            kernel_size,
+            stride,
+            pad,
+            dilation: (1, 1),
+            groups: 1,
+            variable_type,
+        };
+        if let Some(in_channels) = in_channels {
+            layer.init_weight(in_channels)?;
+        }
+        Ok(layer)
+    }
+
+    /// The number of the input channels, or `None` before it is inferred
+    pub fn in_channels(&self) -> Option<usize> {
+        let weight = self.weight.data();
+        if weight.is_none() {
+            None
+        } else {
+            Some(weight.shape()[1] * self.groups)
+        }
+    }
+
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    /// Split the channels into `groups`, which initializes the weight again if it exists
+    ///
+    /// # Note
+    ///
+    /// If the channels are not divisible by `groups`, `KDeZeroError::Error` is returned.
+    pub fn set_groups(&mut self, groups: usize) -> Result<()> {
+        let in_channels = self.in_channels();
+        check_groups("Conv2d", in_channels.unwrap_or(groups), self.out_channels, groups)?;
+        self.groups = groups;
+        if let Some(in_channels) = in_channels {
+            self.init_weight(in_channels)?;
+        }
+        Ok(())
+    }
+
+    fn init_weight(&self, in_channels: usize) -> Result<()> {
+        check_groups("Conv2d", in_channels, self.out_channels, self.groups)?;
+        let shape = [
+            self.out_channels,
+            in_channels / self.groups,
+            self.kernel_size.0,
+            self.kernel_size.1,
+        ];
+        init_kernel(&self.weight, &shape, self.variable_type)
+    }
+}
+
+impl LayerContent for Conv2d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        check_dimensions(x, 4)?;
+        if self.in_channels().is_none() {
+            self.init_weight(x.shape()[1])?;
+        }
+        let y = function::conv2d(
+            x,
+            &self.weight,
+            self.bias.as_ref(),
+            self.stride,
+            self.pad,
+            self.dilation,
+            self.groups,
+        )?;
+        Ok(vec![y])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        let mut params = HashMap::new();
+        params.insert("weight".to_string(), self.weight.clone());
+        if let Some(bias) = &self.bias {
+            params.insert("bias".to_string(), bias.clone());
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+
+    #[test]
+    fn conv2d_lazy_in_channels() -> Result<()> {
+        let layer = Conv2d::new(None, 4, (3, 3), (1, 1), (1, 1), true, VariableType::F64)?;
+        assert_eq!(layer.in_channels(), None);
+        let weight = layer.get_params()["weight"].clone();
+        assert!(weight.data().is_none());
+        let x = Variable::new(Tensor::<f64>::ones([2, 3, 5, 5]).into());
+        let y = layer.forward(vec![&x])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![2, 4, 5, 5]);
+        assert_eq!(layer.in_channels(), Some(3));
+        assert_eq!(weight.shape().to_vec(), vec![4, 3, 3, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn conv2d_groups() -> Result<()> {
+        let mut layer = Conv2d::new(Some(4), 6, (3, 3), (2, 2), (0, 0), false, VariableType::F32)?;
+        layer.set_groups(2)?;
+        assert_eq!(layer.weight.shape().to_vec(), vec![6, 2, 3, 3]);
+        assert_eq!(layer.in_channels(), Some(4));
+        assert!(layer.set_groups(3).is_err());
+        assert_eq!(layer.groups(), 2);
+        let x = Variable::new(Tensor::<f32>::ones([1, 4, 7, 7]).into());
+        let y = layer.forward(vec![&x])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![1, 6, 3, 3]);
+        assert_eq!(layer.get_params().len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableType, VariableData};
+use super::super::LayerContent;
+use super::conv2d::{check_param_type, init_kernel};
+use crate::utility::{check_variable_count, check_dimensions, check_groups};
+use crate::function;
+
+/// 2-D transposed convolution layer
+///
+/// # Fields
+///
+/// * `weight` - The kernel of shape `[C, OC / groups, KH, KW]`,
+///   which is `VariableData::None` until the first forward if `in_channels` is not given
+/// * `bias` - The bias of shape `[OC]`
+/// * `out_channels` - The number of the output channels
+/// * `kernel_size` - The height and the width of the kernel
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The padding removed from both sides of the output
+/// * `output_padding` - The size added to one side of the output
+/// * `dilation` - The dilation along the height and the width
+/// * `groups` - The number of groups the channels are split into, set by `set_groups`
+/// * `variable_type` - The type of the parameters
+#[derive(Debug)]
+pub struct ConvTranspose2d {
+    pub weight: Variable,
+    pub bias: Option<Variable>,
+    pub out_channels: usize,
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+    pub output_padding: (usize, usize),
+    pub dilation: (usize, usize),
+    groups: usize,
+    pub variable_type: VariableType,
+}
+
+impl ConvTranspose2d {
+    /// Create a transposed convolution layer
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - The number of the input channels, or `None` to infer it from the first input
+    /// * `out_channels` - The number of the output channels
+    /// * `kernel_size` - The height and the width of the kernel
+    /// * `stride` - The strides along the height and the width
+    /// * `pad` - The padding removed from both sides of the output
+    /// * `bias` - Whether the layer has a bias
+    /// * `variable_type` - The type of the parameters
+    pub fn new(
+        in_channels: Option<usize>,
+        out_channels: usize,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        bias: bool,
+        variable_type: VariableType,
+    ) -> Result<Self> {
+        check_param_type(variable_type, "ConvTranspose2d")?;
+        let bias = if bias {
+            Some(VariableData::zeros_type(&[out_channels], variable_type)?.into())
+        } else {
+            None
+        };
+        let layer = Self {
+            weight: Variable::new(VariableData::none()),
+            bias,
+            out_channels,
+            kernel_size,
+            stride,
+            pad,
+            output_padding: (0, 0),
+            dilation: (1, 1),
+            groups: 1,
+            variable_type,
+        };
+        if let Some(in_channels) = in_channels {
+            layer.init_weight(in_channels)?;
+        }
+        Ok(layer)
+    }
+
+    /// The number of the input channels, or `None` before it is inferred
+    pub fn in_channels(&self) -> Option<usize> {
+        let weight = self.weight.data();
+        if weight.is_none() {
+            None
+        } else {
+            Some(weight.shape()[0])
+        }
+    }
+
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    /// Split the channels into `groups`, which initializes the weight again if it exists
+    ///
+    /// # Note
+    ///
+    /// If the channels are not divisible by `groups`, `KDeZeroError::Error` is returned.
+    pub fn set_groups(&mut self, groups: usize) -> Result<()> {
+        let in_channels = self.in_channels();
+        check_groups("ConvTranspose2d", in_channels.unwrap_or(groups), self.out_channels, groups)?;
+        self.groups = groups;
+        if let Some(in_channels) = in_channels {
+            self.init_weight(in_channels)?;
+        }
+        Ok(())
+    }
+
+    fn init_weight(&self, in_channels: usize) -> Result<()> {
+        check_groups("ConvTranspose2d", in_channels, self.out_channels, self.groups)?;
+        let shape = [
+            in_channels,
+            self.out_channels / self.groups,
+            self.kernel_size.0,
+            self.kernel_size.1,
+        ];
+        init_kernel(&self.weight, &shape, self.variable_type)
+    }
+}
+
+impl LayerContent for ConvTranspose2d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        check_dimensions(x, 4)?;
+        if self.in_channels().is_none() {
+            self.init_weight(x.shape()[1])?;
+        }
+        let y = function::conv2d_transpose(
+            x,
+            &self.weight,
+            self.bias.as_ref(),
+            self.stride,
+            self.pad,
+            self.output_padding,
+            self.dilation,
+            self.groups,
+        )?;
+        Ok(vec![y])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        let mut params = HashMap::new();
+        params.insert("weight".to_string(), self.weight.clone());
+        if let Some(bias) = &self.bias {
+            params.insert("bias".to_string(), bias.clone());
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+
+    #[test]
+    fn conv_transpose2d_lazy_in_channels() -> Result<()> {
+        let mut layer = ConvTranspose2d::new(None, 2, (4, 4), (2, 2), (1, 1), true, VariableType::F64)?;
+        layer.output_padding = (1, 1);
+        let x = Variable::new(Tensor::<f64>::ones([1, 3, 4, 4]).into());
+        let y = layer.forward(vec![&x])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![1, 2, 9, 9]);
+        assert_eq!(layer.in_channels(), Some(3));
+        assert_eq!(layer.get_params()["weight"].shape().to_vec(), vec![3, 2, 4, 4]);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use crate::Variable;
+use super::super::LayerContent;
+use crate::utility::check_variable_count;
+use crate::function;
+use crate::error::KDeZeroError;
+
+/// Reshape the input of shape `[N, ...]` to `[N, M]`, keeping the batch axis
+#[derive(Debug, Clone, Default)]
+pub struct Flatten {}
+
+impl Flatten {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl LayerContent for Flatten {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        let shape = x.shape().to_vec();
+        if shape.is_empty() {
+            return Err(KDeZeroError::InvalidDimension(1, 0).into())
+        }
+        let y = function::reshape(x, &[shape[0], shape[1..].iter().product()])?;
+        Ok(vec![y])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::layer::{MaxPool2d, AvgPool2d};
+
+    #[test]
+    fn pool_and_flatten() -> Result<()> {
+        let x = Variable::new(Tensor::<f64>::arrange([2, 3, 4, 4])?.into());
+        let y = MaxPool2d::new((2, 2), (2, 2), (0, 0)).forward(vec![&x])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![2, 3, 2, 2]);
+        let y = AvgPool2d::new((3, 3), (1, 1), (1, 1)).forward(vec![&y])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![2, 3, 2, 2]);
+        let y = Flatten::new().forward(vec![&y])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![2, 12]);
+        assert!(Flatten::new().forward(vec![&Variable::from(1.0)]).is_err());
+        Ok(())
+    }
+}
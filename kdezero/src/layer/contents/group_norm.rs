@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableType};
+use super::super::LayerContent;
+use super::conv2d::check_param_type;
+use super::batch_norm::{normalize, affine, affine_params};
+use crate::utility::{check_variable_count, check_groups};
+use crate::function::reshape;
+use crate::error::KDeZeroError;
+
+/// Group normalization of an input of shape `[N, C, ...]`
+///
+/// The channels are split into groups, and each group of each sample is normalized.
+/// The statistics are calculated from the input both in the training and the inference mode.
+///
+/// # Fields
+///
+/// * `weight` - The scale of shape `[C]`
+/// * `bias` - The shift of shape `[C]`
+/// * `num_groups` - The number of the groups
+/// * `num_channels` - The number of the channels `C`
+/// * `eps` - The value added to the variance
+/// * `variable_type` - The type of the parameters
+#[derive(Debug)]
+pub struct GroupNorm {
+    pub weight: Variable,
+    pub bias: Variable,
+    pub num_groups: usize,
+    pub num_channels: usize,
+    pub eps: f64,
+    pub variable_type: VariableType,
+}
+
+impl GroupNorm {
+    /// Create a group normalization layer
+    ///
+    /// # Note
+    ///
+    /// If `num_channels` is not divisible by `num_groups`, `KDeZeroError::Error` is returned.
+    pub fn new(num_groups: usize, num_channels: usize, variable_type: VariableType) -> Result<Self> {
+        check_param_type(variable_type, "GroupNorm")?;
+        check_groups("GroupNorm", num_channels, num_channels, num_groups)?;
+        let (weight, bias) = affine_params(&[num_channels], variable_type)?;
+        Ok(Self {
+            weight,
+            bias,
+            num_groups,
+            num_channels,
+            eps: 1e-5,
+            variable_type,
+        })
+    }
+}
+
+impl LayerContent for GroupNorm {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        let x_shape = x.shape().to_vec();
+        if x_shape.len() < 2 || x_shape[1] != self.num_channels {
+            return Err(KDeZeroError::Error(format!(
+                "GroupNorm: expected {} channels, the shape is {:?}", self.num_channels, x_shape
+            )).into())
+        }
+        let group_size = x_shape[1..].iter().product::<usize>() / self.num_groups;
+        let groups = reshape(x, &[x_shape[0], self.num_groups, group_size])?;
+        let (y, _, _) = normalize(&groups, &[2], self.eps)?;
+        let y = reshape(&y, &x_shape)?;
+        let mut shape = vec![1; x_shape.len()];
+        shape[1] = self.num_channels;
+        Ok(vec![affine(&y, &self.weight, &self.bias, &shape)?])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        HashMap::from([
+            ("weight".to_string(), self.weight.clone()),
+            ("bias".to_string(), self.bias.clone()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::function::mul;
+    use crate::test_utility::{assert_approx_eq_tensor, assert_numerical_grad, sin_data};
+
+    #[test]
+    fn group_norm_backward() -> Result<()> {
+        let x = Variable::new(sin_data(&[2, 4, 3], 0.7)?.into());
+        let weight = Variable::new(sin_data(&[2, 4, 3], 1.3)?.into());
+        let layer = GroupNorm::new(2, 4, VariableType::F64)?;
+        let y = layer.forward(vec![&x])?.remove(0);
+        let groups = y.data().to_f64_tensor()?.reshape([2, 2, 6])?;
+        let mean = groups.mean_with_axis([2], false)?;
+        assert_approx_eq_tensor(&mean, &Tensor::zeros([2, 2]), 1e-12);
+        layer.weight.clone().set_data(Tensor::new(vec![1.0, 2.0, 0.5, -1.0], vec![4])?.into());
+        let f = |x: &Variable| -> Result<Variable> {
+            mul(&layer.forward(vec![x])?.remove(0), &weight)
+        };
+        let mut y = f(&x)?;
+        y.backward()?;
+        assert_numerical_grad(&mut |x| f(x), &x, 1e-5, 1e-6)?;
+        assert!(GroupNorm::new(3, 4, VariableType::F64).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableType};
+use super::super::LayerContent;
+use super::conv2d::check_param_type;
+use super::batch_norm::{normalize, affine, affine_params};
+use crate::utility::check_variable_count;
+use crate::error::KDeZeroError;
+
+/// Layer normalization over the last axes of each sample
+///
+/// The statistics are calculated from the input both in the training and the inference mode.
+///
+/// # Fields
+///
+/// * `weight` - The scale of shape `normalized_shape`
+/// * `bias` - The shift of shape `normalized_shape`
+/// * `normalized_shape` - The shape of the last axes which are normalized
+/// * `eps` - The value added to the variance
+/// * `variable_type` - The type of the parameters
+#[derive(Debug)]
+pub struct LayerNorm {
+    pub weight: Variable,
+    pub bias: Variable,
+    pub normalized_shape: Vec<usize>,
+    pub eps: f64,
+    pub variable_type: VariableType,
+}
+
+impl LayerNorm {
+    pub fn new(normalized_shape: &[usize], variable_type: VariableType) -> Result<Self> {
+        check_param_type(variable_type, "LayerNorm")?;
+        let (weight, bias) = affine_params(normalized_shape, variable_type)?;
+        Ok(Self {
+            weight,
+            bias,
+            normalized_shape: normalized_shape.to_vec(),
+            eps: 1e-5,
+            variable_type,
+        })
+    }
+}
+
+impl LayerContent for LayerNorm {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let x = xs[0];
+        let x_shape = x.shape().to_vec();
+        let batch_ndim = x_shape.len().saturating_sub(self.normalized_shape.len());
+        if x_shape.len() <= self.normalized_shape.len() || x_shape[batch_ndim..] != self.normalized_shape[..] {
+            return Err(KDeZeroError::Error(format!(
+                "LayerNorm: the shape {:?} does not end with {:?}", x_shape, self.normalized_shape
+            )).into())
+        }
+        let axis = (batch_ndim..x_shape.len()).collect::<Vec<_>>();
+        let (y, _, _) = normalize(x, &axis, self.eps)?;
+        let mut shape = vec![1; batch_ndim];
+        shape.extend(&self.normalized_shape);
+        Ok(vec![affine(&y, &self.weight, &self.bias, &shape)?])
+    }
+
+    fn get_params(&self) -> HashMap<String, Variable> {
+        HashMap::from([
+            ("weight".to_string(), self.weight.clone()),
+            ("bias".to_string(), self.bias.clone()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::test_utility::assert_approx_eq_tensor;
+
+    #[test]
+    fn layer_norm_forward() -> Result<()> {
+        let layer = LayerNorm::new(&[2, 2], VariableType::F64)?;
+        layer.bias.clone().set_data(Tensor::full(0.5, [2, 2]).into());
+        let x = Variable::new(Tensor::new(vec![
+            1.0, 2.0, 3.0, 4.0,
+            0.0, 0.0, 4.0, 4.0,
+        ], vec![2, 2, 2])?.into());
+        let y = layer.forward(vec![&x])?.remove(0);
+        let s = 1.0 / 1.25f64.sqrt();
+        let expected = Tensor::new(vec![
+            0.5 - 1.5 * s, 0.5 - 0.5 * s, 0.5 + 0.5 * s, 0.5 + 1.5 * s,
+            -0.5, -0.5, 1.5, 1.5,
+        ], vec![2, 2, 2])?;
+        assert_approx_eq_tensor(y.data().to_f64_tensor()?, &expected, 1e-5);
+        assert!(layer.forward(vec![&Variable::new(Tensor::<f64>::ones([2, 2, 3]).into())]).is_err());
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use anyhow::Result;
 use crate::{Variable, VariableType, VariableData};
 use super::super::LayerContent;
 use crate::utility::{check_variable_count, check_dimensions};
-use crate::function::{self, broadcast_to};
+use crate::function;
 use crate::error::KDeZeroError;
 
 #[derive(Debug)]
@@ -46,13 +46,7 @@ impl LayerContent for Linear {
         let x = xs[0];
         check_dimensions(x, 2)?;
         let w = &self.weight;
-        let b_shape = [x.shape()[0], self.out_size];
-        let y = function::linear(x, w, self.bias
-            .as_ref()
-            .map(|b| broadcast_to(b, &b_shape))
-            .transpose()?
-            .as_ref()
-        )?;
+        let y = function::linear(x, w, self.bias.as_ref())?;
         Ok(vec![y])
     }
 
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use crate::Variable;
+use super::super::LayerContent;
+use crate::utility::check_variable_count;
+use crate::function;
+
+/// 2-D max pooling layer
+///
+/// # Fields
+///
+/// * `kernel_size` - The height and the width of the window
+/// * `stride` - The strides along the height and the width
+/// * `pad` - The padding of the height and the width
+/// * `dilation` - The dilation along the height and the width
+#[derive(Debug, Clone)]
+pub struct MaxPool2d {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub pad: (usize, usize),
+    pub dilation: (usize, usize),
+}
+
+impl MaxPool2d {
+    pub fn new(kernel_size: (usize, usize), stride: (usize, usize), pad: (usize, usize)) -> Self {
+        Self {
+            kernel_size,
+            stride,
+            pad,
+            dilation: (1, 1),
+        }
+    }
+}
+
+impl LayerContent for MaxPool2d {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        check_variable_count(&xs, 1)?;
+        let y = function::max_pool2d(xs[0], self.kernel_size, self.stride, self.pad, self.dilation)?;
+        Ok(vec![y])
+    }
+}
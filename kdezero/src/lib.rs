@@ -9,11 +9,15 @@ mod utility;
 pub mod model;
 pub mod optimizer;
 pub mod data_set;
+pub mod checkpoint;
+pub mod grad_utility;
 
 pub use variable::{Variable, VariableData, VariableWeak, VariableType};
 pub use function::{Function, FunctionInner, FunctionContent};
 pub use layer::{Layer, LayerInner, LayerContent};
 pub use model::Model;
 pub use optimizer::{Optimizer, OptimizerContent};
-pub use config::{no_grad, no_grad_frag, is_no_grad_enabled};
+pub use checkpoint::Checkpoint;
+pub use grad_utility::{clip_grad_norm, clip_grad_value, grad_norm, grad_norms, non_finite_grads, has_non_finite_grad};
+pub use config::{no_grad, no_grad_frag, is_no_grad_enabled, eval_mode, train_mode, is_train_enabled};
 pub use dot_graph::{get_dot_graph, plot_dot_graph};
@@ -2,15 +2,81 @@ mod contents;
 
 use std::collections::HashMap;
 use std::cell::Ref;
+use std::path::Path;
 use anyhow::Result;
-use crate::{Variable, Layer, LayerContent, plot_dot_graph};
+use crate::{Variable, VariableData, Layer, LayerContent, plot_dot_graph};
+use crate::error::KDeZeroError;
 
-pub use contents::{TwoLayerNet, MLP};
+pub use contents::{TwoLayerNet, MLP, SimpleCNN};
 
 pub struct Model {
     layer: Layer,
 }
 
+/// Keys which did not match when loading a state
+///
+/// # Fields
+///
+/// * `missing_keys` - The parameters of the model which are not in the state
+/// * `unexpected_keys` - The entries of the state which are not parameters of the model
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadStateReport {
+    pub missing_keys: Vec<String>,
+    pub unexpected_keys: Vec<String>,
+}
+
+/// Copy the data of the parameters keyed by their names
+pub(crate) fn params_state_dict(params: &HashMap<String, Variable>) -> HashMap<String, VariableData> {
+    params.iter()
+        .map(|(name, param)| (name.clone(), param.data().clone()))
+        .collect()
+}
+
+/// Set the data of the parameters from the state as `Model::load_state_dict` does
+pub(crate) fn load_params_state_dict(
+    params: HashMap<String, Variable>,
+    state: &HashMap<String, VariableData>,
+    strict: bool,
+) -> Result<LoadStateReport> {
+    let mut report = LoadStateReport {
+        missing_keys: params.keys().filter(|name| !state.contains_key(*name)).cloned().collect(),
+        unexpected_keys: state.keys().filter(|name| !params.contains_key(*name)).cloned().collect(),
+    };
+    report.missing_keys.sort();
+    report.unexpected_keys.sort();
+    if strict && (!report.missing_keys.is_empty() || !report.unexpected_keys.is_empty()) {
+        return Err(KDeZeroError::StateKeyMismatch(report.missing_keys, report.unexpected_keys).into())
+    }
+    for (name, param) in params.iter() {
+        let Some(data) = state.get(name) else { continue };
+        let param_data = param.data();
+        if param_data.is_none() {
+            // a lazily initialized parameter takes the data as it is
+            continue
+        }
+        if param_data.data_type() != data.data_type() {
+            return Err(KDeZeroError::StateTypeMismatch(
+                name.clone(),
+                param_data.data_type().to_string(),
+                data.data_type().to_string(),
+            ).into())
+        }
+        if param_data.shape() != data.shape() {
+            return Err(KDeZeroError::StateShapeMismatch(
+                name.clone(),
+                param_data.shape().to_vec(),
+                data.shape().to_vec(),
+            ).into())
+        }
+    }
+    for (name, mut param) in params {
+        if let Some(data) = state.get(&name) {
+            param.set_data(data.clone());
+        }
+    }
+    Ok(report)
+}
+
 impl Model {
     pub fn new<T>(layer: T) -> Self
     where
@@ -37,8 +103,199 @@ impl Model {
         self.layer.get_params_recursive()
     }
 
+    /// The buffers of the layers keyed by their dotted names, which are not updated by the optimizer
+    pub fn get_buffers(&self) -> HashMap<String, Variable> {
+        self.layer.get_buffers_recursive()
+    }
+
+    /// The parameters and the buffers, which make up the state of the model
+    pub fn get_state_variables(&self) -> HashMap<String, Variable> {
+        let mut variables = self.get_params();
+        variables.extend(self.get_buffers());
+        variables
+    }
+
+    /// Copy the data of all the parameters and the buffers keyed by their dotted names
+    pub fn state_dict(&self) -> HashMap<String, VariableData> {
+        params_state_dict(&self.get_state_variables())
+    }
+
+    /// Set the data of the parameters and the buffers from the state
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The data keyed by the dotted parameter names
+    /// * `strict` - Whether missing and unexpected keys are errors
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LoadStateReport>` - The keys which did not match
+    ///
+    /// # Note
+    ///
+    /// If `strict` is true and some keys do not match, `KDeZeroError::StateKeyMismatch` is returned.
+    /// If the type or the shape of an entry differs from the parameter,
+    /// `KDeZeroError::StateTypeMismatch` or `KDeZeroError::StateShapeMismatch` is returned.
+    /// No parameter is changed when an error is returned.
+    /// A parameter which is not initialized yet, such as the weight of a lazy `Conv2d`, takes the entry as it is.
+    pub fn load_state_dict(&mut self, state: &HashMap<String, VariableData>, strict: bool) -> Result<LoadStateReport> {
+        load_params_state_dict(self.get_state_variables(), state, strict)
+    }
+
+    /// Save the parameters to a `.npz` archive keyed by their dotted names
+    pub fn save_state<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let state = self.state_dict();
+        let mut names = state.keys().collect::<Vec<_>>();
+        names.sort();
+        let entries = names.iter()
+            .map(|&name| (name.as_str(), &state[name]))
+            .collect::<Vec<_>>();
+        VariableData::write_npz(path, &entries)
+    }
+
+    /// Load the parameters from a `.npz` archive written by `save_state`
+    ///
+    /// See `load_state_dict` for `strict` and the errors.
+    pub fn load_state<P: AsRef<Path>>(&mut self, path: P, strict: bool) -> Result<LoadStateReport> {
+        let state = VariableData::read_npz(path)?;
+        self.load_state_dict(&state, strict)
+    }
+
     pub fn plot(&self, inputs: &[Variable], out_path_without_extension: &str) -> Result<()> {
         let ys = self.forward(inputs)?;
         plot_dot_graph(&ys[0], out_path_without_extension, true, true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::VariableType;
+    use crate::function::sigmoid;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("kdezero_model_{}_{}", std::process::id(), name))
+    }
+
+    fn mlp() -> Result<Model> {
+        Ok(Model::new(MLP::new(&[2, 3, 1], sigmoid, VariableType::F64)?))
+    }
+
+    #[test]
+    fn save_and_load_state() -> Result<()> {
+        let path = temp_path("state.npz");
+        let model = mlp()?;
+        model.save_state(&path)?;
+        let mut other = mlp()?;
+        let report = other.load_state(&path, true)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(report, LoadStateReport::default());
+        assert_eq!(other.state_dict(), model.state_dict());
+        let xs = [Variable::new(Tensor::<f64>::arrange([4, 2])?.into())];
+        assert_eq!(*model.forward(&xs)?[0].data(), *other.forward(&xs)?[0].data());
+        Ok(())
+    }
+
+    #[test]
+    fn load_state_dict_lazy_params() -> Result<()> {
+        let model = Model::new(SimpleCNN::new(None, (4, 4), 2, VariableType::F64)?);
+        let xs = [Variable::new(Tensor::<f64>::arrange([1, 3, 4, 4])?.into())];
+        let y = model.forward(&xs)?.remove(0);
+        let mut other = Model::new(SimpleCNN::new(None, (4, 4), 2, VariableType::F64)?);
+        other.load_state_dict(&model.state_dict(), true)?;
+        assert_eq!(*other.forward(&xs)?[0].data(), *y.data());
+        Ok(())
+    }
+
+    #[test]
+    fn state_dict_with_buffers() -> Result<()> {
+        use crate::layer::BatchNorm1d;
+        use crate::{Optimizer, eval_mode};
+        use crate::optimizer::SGD;
+
+        let model = Model::new(BatchNorm1d::new(2, VariableType::F64)?);
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        optimizer.set_model(model);
+        let x = Variable::new(Tensor::new(vec![1.0, 10.0, 3.0, 20.0], vec![2, 2])?.into());
+        let mut y = optimizer.get_model_result()?.forward(std::slice::from_ref(&x))?.remove(0);
+        y.backward()?;
+        optimizer.update()?;
+        assert_eq!(optimizer.params().len(), 2);
+        let model = optimizer.get_model_result()?;
+        let state = model.state_dict();
+        assert_eq!(state.len(), 4);
+        assert_eq!(state["running_mean"], Tensor::new(vec![0.2, 1.5], vec![2])?.into());
+        assert_eq!(optimizer.params_state_dict(), state);
+
+        let mut other = Model::new(BatchNorm1d::new(2, VariableType::F64)?);
+        other.load_state_dict(&state, true)?;
+        let _guard = eval_mode();
+        assert_eq!(*other.forward(std::slice::from_ref(&x))?[0].data(), *model.forward(&[x])?[0].data());
+        assert_eq!(other.state_dict(), state);
+        Ok(())
+    }
+
+    #[test]
+    fn load_state_dict_not_strict() -> Result<()> {
+        let mut model = mlp()?;
+        let mut state = mlp()?.state_dict();
+        let bias = state.remove("l2.bias").unwrap();
+        state.insert("l3.bias".to_string(), bias);
+        let report = model.load_state_dict(&state, false)?;
+        assert_eq!(report, LoadStateReport {
+            missing_keys: vec!["l2.bias".to_string()],
+            unexpected_keys: vec!["l3.bias".to_string()],
+        });
+        assert_eq!(model.state_dict()["l1.weight"], state["l1.weight"]);
+        Ok(())
+    }
+
+    #[test]
+    fn error_load_state_dict_strict() -> Result<()> {
+        let mut model = mlp()?;
+        let mut state = model.state_dict();
+        state.remove("l1.bias");
+        match model.load_state_dict(&state, true) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::StateKeyMismatch(vec!["l1.bias".to_string()], vec![]));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_load_state_dict_shape() -> Result<()> {
+        let mut model = mlp()?;
+        let before = model.state_dict();
+        let mut state = before.clone();
+        state.insert("l1.weight".to_string(), Tensor::<f64>::zeros([3, 2]).into());
+        match model.load_state_dict(&state, true) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::StateShapeMismatch("l1.weight".to_string(), vec![2, 3], vec![3, 2]));
+            }
+        }
+        assert_eq!(model.state_dict(), before);
+        Ok(())
+    }
+
+    #[test]
+    fn error_load_state_dict_type() -> Result<()> {
+        let mut model = mlp()?;
+        let mut state = model.state_dict();
+        state.insert("l2.bias".to_string(), Tensor::<f32>::zeros([1]).into());
+        match model.load_state_dict(&state, false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::StateTypeMismatch(
+                    "l2.bias".to_string(), "f64".to_string(), "f32".to_string()));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 mod two_layer_net;
 mod mlp;
+mod simple_cnn;
 
 pub use two_layer_net::TwoLayerNet;
 pub use mlp::MLP;
+pub use simple_cnn::SimpleCNN;
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::function::relu;
+use crate::{Variable, VariableType, Layer, LayerContent};
+use crate::layer::{Conv2d, MaxPool2d, Flatten, Linear};
+
+/// A small convolutional network for images such as MNIST
+///
+/// `conv1 (8 channels) - relu - max pool - conv2 (16 channels) - relu - max pool - flatten - fc`,
+/// where the convolutions are 3x3 keeping the size and the poolings halve it.
+pub struct SimpleCNN {
+    pub conv1: Layer,
+    pub conv2: Layer,
+    pub pool: Layer,
+    pub flatten: Layer,
+    pub fc: Layer,
+}
+
+impl SimpleCNN {
+    /// Create the network
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - The number of the input channels, or `None` to infer it from the first input
+    /// * `image_size` - The height and the width of the input, which decide the input size of `fc`
+    /// * `num_classes` - The number of the outputs
+    /// * `variable_type` - The type of the parameters
+    pub fn new(
+        in_channels: Option<usize>,
+        image_size: (usize, usize),
+        num_classes: usize,
+        variable_type: VariableType,
+    ) -> Result<Self> {
+        let conv1 = Conv2d::new(in_channels, 8, (3, 3), (1, 1), (1, 1), true, variable_type)?;
+        let conv2 = Conv2d::new(Some(8), 16, (3, 3), (1, 1), (1, 1), true, variable_type)?;
+        let fc_size = 16 * (image_size.0 / 4) * (image_size.1 / 4);
+        let mut fc = Linear::new(fc_size, num_classes, true, variable_type)?;
+        let weight = fc.weight.data().scalar_mul((1.0 / fc_size as f64).sqrt())?;
+        fc.weight.set_data(weight);
+        Ok(Self {
+            conv1: Layer::new(conv1),
+            conv2: Layer::new(conv2),
+            pool: Layer::new(MaxPool2d::new((2, 2), (2, 2), (0, 0))),
+            flatten: Layer::new(Flatten::new()),
+            fc: Layer::new(fc),
+        })
+    }
+}
+
+impl LayerContent for SimpleCNN {
+    fn forward(&self, xs: Vec<&Variable>) -> Result<Vec<Variable>> {
+        let x_ref = xs
+            .iter()
+            .map(|&x| x.clone()).collect::<Vec<_>>();
+        let y = relu(&self.conv1.forward(&x_ref)?.remove(0))?;
+        let y = self.pool.forward(&[y])?.remove(0);
+        let y = relu(&self.conv2.forward(&[y])?.remove(0))?;
+        let y = self.pool.forward(&[y])?.remove(0);
+        let y = self.flatten.forward(&[y])?.remove(0);
+        self.fc.forward(&[y])
+    }
+
+    fn get_layers(&self) -> HashMap<String, Layer> {
+        let mut layers = HashMap::new();
+        layers.insert("conv1".to_string(), self.conv1.clone());
+        layers.insert("conv2".to_string(), self.conv2.clone());
+        layers.insert("fc".to_string(), self.fc.clone());
+        layers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::Model;
+
+    #[test]
+    fn simple_cnn_forward() -> Result<()> {
+        let model = Model::new(SimpleCNN::new(None, (8, 8), 10, VariableType::F64)?);
+        let mut names = model.get_params().into_keys().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec![
+            "conv1.bias", "conv1.weight", "conv2.bias", "conv2.weight", "fc.bias", "fc.weight",
+        ]);
+        assert!(model.get_params()["conv1.weight"].data().is_none());
+        let x = Variable::new(Tensor::<f64>::ones([2, 1, 8, 8]).into());
+        let y = model.forward(&[x])?.remove(0);
+        assert_eq!(y.shape().to_vec(), vec![2, 10]);
+        assert_eq!(model.get_params()["conv1.weight"].shape().to_vec(), vec![8, 1, 3, 3]);
+        Ok(())
+    }
+}
@@ -1,26 +1,62 @@
 mod contents;
+mod param_group;
+pub mod scheduler;
 
+use std::collections::HashMap;
 use anyhow::Result;
-use crate::{Variable, Model};
+use crate::{Variable, VariableData, Model};
+use crate::error::KDeZeroError;
+use crate::model::{LoadStateReport, params_state_dict, load_params_state_dict};
+use scheduler::{LrScheduler, state_f64, state_usize};
 
-pub use contents::{SGD, MomentumSGD};
+pub use contents::{SGD, MomentumSGD, Adam, AdamW, AdaGrad, AdaDelta, RMSprop};
+pub use param_group::{ParamGroup, WeightDecay};
 
+/// The update rule of an optimizer
+///
+/// `Optimizer::update` calls `update_one` for each parameter
+/// after setting the learning rate of the parameter group.
 pub trait OptimizerContent {
-    fn update_one(&mut self, _param: &mut Variable) -> Result<()> {
+    fn lr(&self) -> f64;
+
+    fn set_lr(&mut self, lr: f64);
+
+    /// Update one parameter with the gradient
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter, which keys the internal buffers
+    /// * `param` - The parameter to be updated
+    /// * `grad` - The gradient of the parameter, to which the L2 weight decay is already added
+    fn update_one(&mut self, _name: &str, _param: &mut Variable, _grad: &VariableData) -> Result<()> {
         Ok(())
     }
 
-    fn update(&mut self, model: &mut Model) -> Result<()> {
-        for (_, param) in model.get_params().iter_mut() {
-            self.update_one(param)?;
-        }
+    /// The internal buffers of the optimizer keyed by `<buffer>.<parameter name>`
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        HashMap::new()
+    }
+
+    /// Restore the internal buffers from `state_dict`
+    fn load_state_dict(&mut self, _state: &HashMap<String, VariableData>) -> Result<()> {
         Ok(())
     }
 }
 
+/// Update the parameters of a model and of the variables added to it
+///
+/// The parameters are identified by their names, which key the state of the optimizer
+/// and select the parameter groups.
 pub struct Optimizer {
     optimizer: Box<dyn OptimizerContent>,
     model: Option<Model>,
+    params: HashMap<String, Variable>,
+    buffers: HashMap<String, Variable>,
+    param_groups: Vec<ParamGroup>,
+    weight_decay: WeightDecay,
+    scheduler: Option<Box<dyn LrScheduler>>,
+    base_lr: f64,
+    scheduler_steps: usize,
 }
 
 impl Optimizer {
@@ -28,9 +64,25 @@ impl Optimizer {
     where
         T: OptimizerContent + 'static
     {
+        let base_lr = optimizer.lr();
         Self {
             optimizer: Box::new(optimizer),
             model: None,
+            params: HashMap::new(),
+            buffers: HashMap::new(),
+            param_groups: Vec::new(),
+            weight_decay: WeightDecay::None,
+            scheduler: None,
+            base_lr,
+            scheduler_steps: 0,
+        }
+    }
+
+    pub fn get_model_result(&self) -> Result<&Model> {
+        if let Some(model) = &self.model {
+            Ok(model)
+        } else {
+            Err(anyhow::anyhow!("Model is not set"))
         }
     }
 
@@ -46,11 +98,358 @@ impl Optimizer {
         self.model = Some(model);
     }
 
+    /// Create an optimizer over the given variables instead of a model
+    ///
+    /// # Arguments
+    ///
+    /// * `optimizer` - The content of the optimizer
+    /// * `params` - The variables keyed by their names
+    pub fn from_params<T>(optimizer: T, params: HashMap<String, Variable>) -> Self
+    where
+        T: OptimizerContent + 'static
+    {
+        let mut result = Self::new(optimizer);
+        result.params = params;
+        result
+    }
+
+    /// Add a variable to be updated
+    ///
+    /// # Note
+    ///
+    /// If the name is already used by a parameter of the optimizer,
+    /// `KDeZeroError::DuplicateParameter` is returned.
+    /// The names of the model set later are not checked against the variables.
+    pub fn add_param(&mut self, name: &str, param: &Variable) -> Result<()> {
+        if self.params().contains_key(name) {
+            return Err(KDeZeroError::DuplicateParameter(name.to_string()).into())
+        }
+        self.params.insert(name.to_string(), param.clone());
+        Ok(())
+    }
+
+    /// Add the parameters of a model without handing the model over,
+    /// named `<prefix>.<parameter name>`
+    ///
+    /// This allows several models, or some layers of a model, to share one optimizer.
+    /// The model keeps working on the same variables.
+    ///
+    /// # Note
+    ///
+    /// The buffers of the model, like the running statistics of `BatchNorm`, are not updated
+    /// but kept as `<prefix>.<buffer name>`, so that `params_state_dict` includes them.
+    /// If a name is already used, `KDeZeroError::DuplicateParameter` is returned.
+    pub fn add_model_params(&mut self, prefix: &str, model: &Model) -> Result<()> {
+        let mut params = model.get_params().into_iter().collect::<Vec<_>>();
+        params.sort_by(|(x, _), (y, _)| x.cmp(y));
+        for (name, param) in params {
+            self.add_param(&format!("{}.{}", prefix, name), &param)?;
+        }
+        let mut buffers = model.get_buffers().into_iter().collect::<Vec<_>>();
+        buffers.sort_by(|(x, _), (y, _)| x.cmp(y));
+        for (name, buffer) in buffers {
+            let name = format!("{}.{}", prefix, name);
+            if self.state_variables().contains_key(&name) {
+                return Err(KDeZeroError::DuplicateParameter(name).into())
+            }
+            self.buffers.insert(name, buffer);
+        }
+        Ok(())
+    }
+
+    /// All the parameters updated by the optimizer: the ones of the model and the added variables
+    pub fn params(&self) -> HashMap<String, Variable> {
+        let mut params = match &self.model {
+            Some(model) => model.get_params(),
+            None => HashMap::new(),
+        };
+        params.extend(self.params.iter().map(|(name, param)| (name.clone(), param.clone())));
+        params
+    }
+
+    pub fn clear_grads(&mut self) {
+        for (_, mut param) in self.params() {
+            param.clear_grad();
+        }
+    }
+
+    /// The parameters and the buffers of the model with the added variables and buffers
+    fn state_variables(&self) -> HashMap<String, Variable> {
+        let mut variables = self.params();
+        if let Some(model) = &self.model {
+            variables.extend(model.get_buffers());
+        }
+        variables.extend(self.buffers.iter().map(|(name, buffer)| (name.clone(), buffer.clone())));
+        variables
+    }
+
+    /// Copy the data of all the parameters of the optimizer and the buffers of the model
+    pub fn params_state_dict(&self) -> HashMap<String, VariableData> {
+        params_state_dict(&self.state_variables())
+    }
+
+    /// Set the data of all the parameters of the optimizer and the buffers of the model
+    /// as `Model::load_state_dict` does
+    pub fn load_params_state_dict(&mut self, state: &HashMap<String, VariableData>, strict: bool) -> Result<LoadStateReport> {
+        load_params_state_dict(self.state_variables(), state, strict)
+    }
+
+    /// Add a parameter group
+    ///
+    /// A parameter belongs to the first group added whose pattern matches its name,
+    /// and the parameters in no group use the learning rate and the weight decay of the optimizer.
+    pub fn add_param_group(&mut self, group: ParamGroup) {
+        self.param_groups.push(group);
+    }
+
+    pub fn param_groups(&self) -> &[ParamGroup] {
+        &self.param_groups
+    }
+
+    /// Set the weight decay of the parameters in no group
+    pub fn set_weight_decay(&mut self, weight_decay: WeightDecay) {
+        self.weight_decay = weight_decay;
+    }
+
+    fn param_hyperparameters(&self, name: &str) -> (f64, WeightDecay) {
+        match self.param_groups.iter().find(|group| group.matches(name)) {
+            Some(group) => (group.lr_scale, group.weight_decay),
+            None => (1.0, self.weight_decay),
+        }
+    }
+
+    /// The learning rate used for the parameter `name`
+    pub fn param_lr(&self, name: &str) -> f64 {
+        self.optimizer.lr() * self.param_hyperparameters(name).0
+    }
+
+    /// Update the parameters of the model and the added variables
+    ///
+    /// Each parameter is updated with the learning rate and the weight decay of its group.
     pub fn update(&mut self) -> Result<()> {
-        if let Some(model) = &mut self.model {
-            self.optimizer.update(model)
-        } else {
-            Ok(())
+        let base_lr = self.optimizer.lr();
+        for (name, mut param) in self.params() {
+            let (lr_scale, weight_decay) = self.param_hyperparameters(&name);
+            let lr = base_lr * lr_scale;
+            let grad = param.grad_result()?.data().clone();
+            let grad = weight_decay.apply(&mut param, &grad, lr)?;
+            self.optimizer.set_lr(lr);
+            let result = self.optimizer.update_one(&name, &mut param, &grad);
+            self.optimizer.set_lr(base_lr);
+            result?;
+        }
+        Ok(())
+    }
+
+    /// The internal buffers of the optimizer with the learning rate and the state of the scheduler
+    ///
+    /// # Note
+    ///
+    /// The learning rate is saved as `lr`, `base_lr` and `scheduler_steps`,
+    /// and the state of the scheduler is keyed by `scheduler.<key>`.
+    pub fn state_dict(&self) -> HashMap<String, VariableData> {
+        let mut state = self.optimizer.state_dict();
+        state.insert("lr".to_string(), VariableData::from(self.optimizer.lr()));
+        state.insert("base_lr".to_string(), VariableData::from(self.base_lr));
+        state.insert("scheduler_steps".to_string(), VariableData::from(self.scheduler_steps));
+        if let Some(scheduler) = &self.scheduler {
+            for (key, value) in scheduler.state_dict() {
+                state.insert(format!("scheduler.{}", key), value);
+            }
+        }
+        state
+    }
+
+    /// Restore the state saved by `state_dict`
+    ///
+    /// # Note
+    ///
+    /// The scheduler has to be attached before, so that the schedule resumes from the saved step.
+    /// If the state has a scheduler state but no scheduler is attached,
+    /// `KDeZeroError::StateKeyMismatch` is returned with its keys as the unexpected keys.
+    pub fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        let mut content_state = HashMap::new();
+        let mut scheduler_state = HashMap::new();
+        for (key, value) in state {
+            match key.strip_prefix("scheduler.") {
+                Some(key) => { scheduler_state.insert(key.to_string(), value.clone()); },
+                None if ["lr", "base_lr", "scheduler_steps"].contains(&key.as_str()) => (),
+                None => { content_state.insert(key.clone(), value.clone()); },
+            }
+        }
+        match &mut self.scheduler {
+            Some(scheduler) => scheduler.load_state_dict(&scheduler_state)?,
+            None if !scheduler_state.is_empty() => {
+                let mut unexpected = scheduler_state.keys()
+                    .map(|key| format!("scheduler.{}", key))
+                    .collect::<Vec<_>>();
+                unexpected.sort();
+                return Err(KDeZeroError::StateKeyMismatch(vec![], unexpected).into())
+            },
+            None => (),
+        }
+        self.optimizer.load_state_dict(&content_state)?;
+        if let Some(base_lr) = state.get("base_lr") {
+            self.base_lr = state_f64(base_lr)?;
+        }
+        if let Some(steps) = state.get("scheduler_steps") {
+            self.scheduler_steps = state_usize(steps)?;
         }
+        if let Some(lr) = state.get("lr") {
+            self.optimizer.set_lr(state_f64(lr)?);
+        }
+        Ok(())
+    }
+
+    /// The current learning rate
+    pub fn lr(&self) -> f64 {
+        self.optimizer.lr()
+    }
+
+    pub fn set_lr(&mut self, lr: f64) {
+        self.optimizer.set_lr(lr);
+    }
+
+    /// Attach a learning rate scheduler
+    ///
+    /// The current learning rate becomes the base learning rate of the scheduler,
+    /// and the learning rate of the step 0 is applied immediately.
+    pub fn set_scheduler<T>(&mut self, scheduler: T) -> Result<()>
+    where
+        T: LrScheduler + 'static
+    {
+        let mut scheduler = Box::new(scheduler);
+        self.base_lr = self.optimizer.lr();
+        self.scheduler_steps = 0;
+        let lr = scheduler.get_lr(self.base_lr, 0, None)?;
+        self.optimizer.set_lr(lr);
+        self.scheduler = Some(scheduler);
+        Ok(())
+    }
+
+    /// Advance the scheduler by one step and apply the new learning rate
+    ///
+    /// # Returns
+    ///
+    /// * `Result<f64>` - The new learning rate
+    pub fn scheduler_step(&mut self) -> Result<f64> {
+        self.step_scheduler(None)
+    }
+
+    /// Advance the scheduler by one step with the monitored value such as the validation loss
+    ///
+    /// # Returns
+    ///
+    /// * `Result<f64>` - The new learning rate
+    pub fn scheduler_step_with_metric(&mut self, metric: f64) -> Result<f64> {
+        self.step_scheduler(Some(metric))
+    }
+
+    fn step_scheduler(&mut self, metric: Option<f64>) -> Result<f64> {
+        let scheduler = self.scheduler.as_mut()
+            .ok_or_else(|| KDeZeroError::Error("Scheduler is not set".to_string()))?;
+        self.scheduler_steps += 1;
+        let lr = scheduler.get_lr(self.base_lr, self.scheduler_steps, metric)?;
+        self.optimizer.set_lr(lr);
+        Ok(lr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::VariableType;
+    use crate::model::MLP;
+    use crate::layer::BatchNorm1d;
+    use crate::function::{pow, sigmoid};
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn update_params() -> Result<()> {
+        let x0 = Variable::from(0.0);
+        let x1 = Variable::from(2.0);
+        let params = HashMap::from([("x0".to_string(), x0.clone()), ("x1".to_string(), x1.clone())]);
+        let mut optimizer = Optimizer::from_params(MomentumSGD::new_nesterov(0.001, 0.9), params);
+        for _ in 0..1000 {
+            let mut y = pow(&(&x1 - &pow(&x0, 2.0)?), 2.0)? * 100.0.into()
+                + pow(&(&x0 - &1.0.into()), 2.0)?;
+            optimizer.clear_grads();
+            y.backward()?;
+            optimizer.update()?;
+        }
+        assert_approx_eq(*x0.data().to_f64_tensor()?.at([])?, 0.9951242033914439, 1e-9);
+        assert_approx_eq(*x1.data().to_f64_tensor()?.at([])?, 0.9902526111741995, 1e-9);
+        let state = optimizer.state_dict();
+        assert_eq!(state.keys().filter(|key| key.starts_with("velocity.")).count(), 2);
+        assert_eq!(state["lr"], VariableData::from(0.001));
+        Ok(())
+    }
+
+    #[test]
+    fn update_several_models() -> Result<()> {
+        let generator = Model::new(MLP::new(&[2, 2, 1], sigmoid, VariableType::F64)?);
+        let discriminator = Model::new(MLP::new(&[1, 2, 1], sigmoid, VariableType::F64)?);
+        let mut optimizer_g = Optimizer::new(SGD::new(0.1));
+        optimizer_g.add_model_params("generator", &generator)?;
+        let mut optimizer_d = Optimizer::new(SGD::new(0.1));
+        optimizer_d.add_model_params("discriminator", &discriminator)?;
+        let mut names = optimizer_g.params().into_keys().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["generator.l1.bias", "generator.l1.weight", "generator.l2.bias", "generator.l2.weight"]);
+
+        let x = Variable::new(Tensor::new(vec![1.0, 2.0], vec![1, 2])?.into());
+        let before_d = discriminator.state_dict();
+        let before_g = generator.state_dict();
+        let y = generator.forward(&[x])?.remove(0);
+        let mut z = discriminator.forward(&[y])?.remove(0);
+        optimizer_g.clear_grads();
+        optimizer_d.clear_grads();
+        z.backward()?;
+        optimizer_g.update()?;
+        assert_eq!(discriminator.state_dict(), before_d);
+        assert_ne!(generator.state_dict(), before_g);
+        Ok(())
+    }
+
+    #[test]
+    fn add_model_params_with_buffers() -> Result<()> {
+        let model = Model::new(BatchNorm1d::new(2, VariableType::F64)?);
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        optimizer.add_model_params("bn", &model)?;
+        let x = Variable::new(Tensor::new(vec![1.0, 10.0, 3.0, 20.0], vec![2, 2])?.into());
+        let mut y = model.forward(&[x])?.remove(0);
+        y.backward()?;
+        optimizer.update()?;
+        assert_eq!(optimizer.params().len(), 2);
+        let state = optimizer.params_state_dict();
+        let mut names = state.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["bn.bias", "bn.running_mean", "bn.running_var", "bn.weight"]);
+        assert_eq!(state["bn.running_mean"], Tensor::new(vec![0.2, 1.5], vec![2])?.into());
+
+        let other = Model::new(BatchNorm1d::new(2, VariableType::F64)?);
+        let mut resumed = Optimizer::new(SGD::new(0.1));
+        resumed.add_model_params("bn", &other)?;
+        resumed.load_params_state_dict(&state, true)?;
+        assert_eq!(other.state_dict(), model.state_dict());
+        Ok(())
+    }
+
+    #[test]
+    fn error_duplicate_param() -> Result<()> {
+        let model = Model::new(MLP::new(&[2, 1], sigmoid, VariableType::F64)?);
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        optimizer.set_model(model);
+        match optimizer.add_param("l1.bias", &Variable::from(0.0)) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::DuplicateParameter("l1.bias".to_string()));
+            }
+        }
+        optimizer.add_param("scale", &Variable::from(0.0))?;
+        assert_eq!(optimizer.params_state_dict().len(), 3);
+        Ok(())
     }
 }
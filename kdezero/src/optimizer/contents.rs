@@ -1,5 +1,73 @@
 mod sgd;
 mod momentum_sgd;
+mod adam;
+mod adam_w;
+mod ada_grad;
+mod ada_delta;
+mod rms_prop;
+
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::VariableData;
+use crate::error::KDeZeroError;
 
 pub use sgd::SGD;
 pub use momentum_sgd::MomentumSGD;
+pub use adam::Adam;
+pub use adam_w::AdamW;
+pub use ada_grad::AdaGrad;
+pub use ada_delta::AdaDelta;
+pub use rms_prop::RMSprop;
+
+/// Collect the per-parameter buffers into a state dict keyed by `<buffer>.<parameter name>`
+fn buffers_to_state(buffers: &[(&str, &HashMap<String, VariableData>)]) -> HashMap<String, VariableData> {
+    buffers.iter()
+        .flat_map(|(buffer, values)| values.iter()
+            .map(move |(name, value)| (format!("{}.{}", buffer, name), value.clone())))
+        .collect()
+}
+
+/// Split a state dict into the per-parameter buffers in the order of `buffers`
+///
+/// If a key does not start with one of the buffers, `KDeZeroError::StateKeyMismatch` is returned.
+fn state_to_buffers(state: &HashMap<String, VariableData>, buffers: &[&str]) -> Result<Vec<HashMap<String, VariableData>>> {
+    let mut result = vec![HashMap::new(); buffers.len()];
+    let mut unexpected = vec![];
+    for (key, value) in state {
+        let found = buffers.iter()
+            .position(|buffer| key.len() > buffer.len() + 1 && key.starts_with(&format!("{}.", buffer)));
+        match found {
+            Some(i) => { result[i].insert(key[buffers[i].len() + 1..].to_string(), value.clone()); },
+            None => unexpected.push(key.clone()),
+        }
+    }
+    if !unexpected.is_empty() {
+        unexpected.sort();
+        return Err(KDeZeroError::StateKeyMismatch(vec![], unexpected).into())
+    }
+    Ok(result)
+}
+
+/// Run the optimizer on the Rosenbrock function from `(0, 2)` and return the final point
+#[cfg(test)]
+fn rosenbrock_trajectory(optimizer: &mut dyn super::OptimizerContent, iters: usize) -> Result<(f64, f64)> {
+    use crate::Variable;
+    use crate::function::pow;
+
+    let mut x0 = Variable::from(0.0);
+    let mut x1 = Variable::from(2.0);
+    for _ in 0..iters {
+        let mut y = pow(&(&x1 - &pow(&x0, 2.0)?), 2.0)? * 100.0.into()
+            + pow(&(&x0 - &1.0.into()), 2.0)?;
+        x0.clear_grad();
+        x1.clear_grad();
+        y.backward()?;
+        let grad = x0.grad_result()?.data().clone();
+        optimizer.update_one("x0", &mut x0, &grad)?;
+        let grad = x1.grad_result()?.data().clone();
+        optimizer.update_one("x1", &mut x1, &grad)?;
+    }
+    let x0 = *x0.data().to_f64_tensor()?.at([])?;
+    let x1 = *x1.data().to_f64_tensor()?.at([])?;
+    Ok((x0, x1))
+}
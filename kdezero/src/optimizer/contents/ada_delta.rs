@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::super::OptimizerContent;
+use super::{buffers_to_state, state_to_buffers};
+
+pub struct AdaDelta {
+    pub lr: f64,
+    pub rho: f64,
+    pub eps: f64,
+    pub hs: HashMap<String, VariableData>,
+    pub deltas: HashMap<String, VariableData>,
+}
+
+impl AdaDelta {
+    pub fn new(lr: f64, rho: f64, eps: f64) -> Self {
+        Self {
+            lr,
+            rho,
+            eps,
+            hs: HashMap::new(),
+            deltas: HashMap::new(),
+        }
+    }
+}
+
+impl OptimizerContent for AdaDelta {
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn update_one(&mut self, name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
+        if !self.hs.contains_key(name) {
+            self.hs.insert(name.to_string(), grad.zeros_like()?);
+            self.deltas.insert(name.to_string(), grad.zeros_like()?);
+        }
+        let h = self.hs.get_mut(name).unwrap();
+        *h = h.scalar_mul(self.rho)?.add(&grad.square()?.scalar_mul(1.0 - self.rho)?)?;
+        let delta = self.deltas.get_mut(name).unwrap();
+        let dx = delta.scalar_add(self.eps)?.pow(0.5)?
+            .div(&h.scalar_add(self.eps)?.pow(0.5)?)?
+            .mul(grad)?;
+        *delta = delta.scalar_mul(self.rho)?.add(&dx.square()?.scalar_mul(1.0 - self.rho)?)?;
+        let new_data = param.data().sub(&dx.scalar_mul(self.lr)?)?;
+        param.set_data(new_data);
+        Ok(())
+    }
+
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        buffers_to_state(&[("h", &self.hs), ("delta", &self.deltas)])
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        let mut buffers = state_to_buffers(state, &["h", "delta"])?;
+        self.deltas = buffers.pop().unwrap();
+        self.hs = buffers.pop().unwrap();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use super::super::rosenbrock_trajectory;
+
+    #[test]
+    fn ada_delta_rosenbrock() -> Result<()> {
+        let (x0, x1) = rosenbrock_trajectory(&mut AdaDelta::new(1.0, 0.95, 1e-6), 1000)?;
+        assert_approx_eq(x0, 1.2427861672739042, 1e-9);
+        assert_approx_eq(x1, 1.5658829410028192, 1e-9);
+        Ok(())
+    }
+}
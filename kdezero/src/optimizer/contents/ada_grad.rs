@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::super::OptimizerContent;
+use super::{buffers_to_state, state_to_buffers};
+
+pub struct AdaGrad {
+    pub lr: f64,
+    pub eps: f64,
+    pub hs: HashMap<String, VariableData>,
+}
+
+impl AdaGrad {
+    pub fn new(lr: f64, eps: f64) -> Self {
+        Self {
+            lr,
+            eps,
+            hs: HashMap::new(),
+        }
+    }
+}
+
+impl OptimizerContent for AdaGrad {
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn update_one(&mut self, name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
+        if !self.hs.contains_key(name) {
+            self.hs.insert(name.to_string(), grad.zeros_like()?);
+        }
+        let h = self.hs.get_mut(name).unwrap();
+        *h = h.add(&grad.square()?)?;
+        let delta = grad.scalar_mul(self.lr)?.div(&h.pow(0.5)?.scalar_add(self.eps)?)?;
+        let new_data = param.data().sub(&delta)?;
+        param.set_data(new_data);
+        Ok(())
+    }
+
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        buffers_to_state(&[("h", &self.hs)])
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        self.hs = state_to_buffers(state, &["h"])?.remove(0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use super::super::rosenbrock_trajectory;
+
+    #[test]
+    fn ada_grad_rosenbrock() -> Result<()> {
+        let (x0, x1) = rosenbrock_trajectory(&mut AdaGrad::new(0.1, 1e-8), 1000)?;
+        assert_approx_eq(x0, 1.1397339943137952, 1e-9);
+        assert_approx_eq(x1, 1.2995048348565827, 1e-9);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::super::OptimizerContent;
+use super::{buffers_to_state, state_to_buffers};
+
+pub struct Adam {
+    pub lr: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub eps: f64,
+    pub ms: HashMap<String, VariableData>,
+    pub vs: HashMap<String, VariableData>,
+    pub steps: HashMap<String, usize>,
+}
+
+impl Adam {
+    pub fn new(lr: f64, beta1: f64, beta2: f64, eps: f64) -> Self {
+        Self {
+            lr,
+            beta1,
+            beta2,
+            eps,
+            ms: HashMap::new(),
+            vs: HashMap::new(),
+            steps: HashMap::new(),
+        }
+    }
+
+    /// Update the parameter after shrinking it by `lr * weight_decay` as AdamW does
+    pub(super) fn step(&mut self, name: &str, param: &mut Variable, grad: &VariableData, weight_decay: f64) -> Result<()> {
+        let step = self.steps.entry(name.to_string()).or_insert(0);
+        *step += 1;
+        let t = *step as i32;
+        if !self.ms.contains_key(name) {
+            self.ms.insert(name.to_string(), grad.zeros_like()?);
+            self.vs.insert(name.to_string(), grad.zeros_like()?);
+        }
+        let m = self.ms.get_mut(name).unwrap();
+        *m = m.scalar_mul(self.beta1)?.add(&grad.scalar_mul(1.0 - self.beta1)?)?;
+        let v = self.vs.get_mut(name).unwrap();
+        *v = v.scalar_mul(self.beta2)?.add(&grad.square()?.scalar_mul(1.0 - self.beta2)?)?;
+        let m_hat = m.scalar_mul(1.0 / (1.0 - self.beta1.powi(t)))?;
+        let v_hat = v.scalar_mul(1.0 / (1.0 - self.beta2.powi(t)))?;
+        let delta = m_hat.scalar_mul(self.lr)?.div(&v_hat.pow(0.5)?.scalar_add(self.eps)?)?;
+        let mut data = param.data().clone();
+        if weight_decay != 0.0 {
+            data = data.scalar_mul(1.0 - self.lr * weight_decay)?;
+        }
+        param.set_data(data.sub(&delta)?);
+        Ok(())
+    }
+}
+
+impl OptimizerContent for Adam {
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn update_one(&mut self, name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
+        self.step(name, param, grad, 0.0)
+    }
+
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        let steps = self.steps.iter()
+            .map(|(name, &step)| (name.clone(), VariableData::from(step)))
+            .collect();
+        buffers_to_state(&[("m", &self.ms), ("v", &self.vs), ("step", &steps)])
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        let mut buffers = state_to_buffers(state, &["m", "v", "step"])?;
+        self.steps = buffers.pop().unwrap().into_iter()
+            .map(|(name, step)| Ok((name, *step.to_usize_tensor()?.at([])?)))
+            .collect::<Result<_>>()?;
+        self.vs = buffers.pop().unwrap();
+        self.ms = buffers.pop().unwrap();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use super::super::rosenbrock_trajectory;
+
+    #[test]
+    fn adam_rosenbrock() -> Result<()> {
+        let (x0, x1) = rosenbrock_trajectory(&mut Adam::new(0.01, 0.9, 0.999, 1e-8), 1000)?;
+        assert_approx_eq(x0, 1.1166870188109839, 1e-9);
+        assert_approx_eq(x1, 1.24744319187539, 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn adam_state_dict_resume() -> Result<()> {
+        let mut optimizer = Adam::new(0.01, 0.9, 0.999, 1e-8);
+        rosenbrock_trajectory(&mut optimizer, 1)?;
+        let state = optimizer.state_dict();
+        assert_eq!(state["step.x0"], VariableData::from(1usize));
+        let mut resumed = Adam::new(0.01, 0.9, 0.999, 1e-8);
+        resumed.load_state_dict(&state)?;
+        assert_eq!(resumed.state_dict(), state);
+        Ok(())
+    }
+}
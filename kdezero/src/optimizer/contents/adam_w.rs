@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::{Variable, VariableData};
+use super::super::OptimizerContent;
+use super::Adam;
+
+/// Adam with the weight decay decoupled from the gradient
+///
+/// Each parameter is multiplied by `1 - lr * weight_decay` before the Adam update.
+pub struct AdamW {
+    pub adam: Adam,
+    pub weight_decay: f64,
+}
+
+impl AdamW {
+    pub fn new(lr: f64, beta1: f64, beta2: f64, eps: f64, weight_decay: f64) -> Self {
+        Self {
+            adam: Adam::new(lr, beta1, beta2, eps),
+            weight_decay,
+        }
+    }
+}
+
+impl OptimizerContent for AdamW {
+    fn lr(&self) -> f64 {
+        self.adam.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.adam.lr = lr;
+    }
+
+    fn update_one(&mut self, name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
+        self.adam.step(name, param, grad, self.weight_decay)
+    }
+
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        self.adam.state_dict()
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        self.adam.load_state_dict(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use super::super::rosenbrock_trajectory;
+
+    #[test]
+    fn adam_w_rosenbrock() -> Result<()> {
+        let (x0, x1) = rosenbrock_trajectory(&mut AdamW::new(0.01, 0.9, 0.999, 1e-8, 0.1), 1000)?;
+        assert_approx_eq(x0, 0.6227720113507307, 1e-9);
+        assert_approx_eq(x1, 0.3895150918358152, 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn adam_w_without_weight_decay() -> Result<()> {
+        let adam_w = rosenbrock_trajectory(&mut AdamW::new(0.01, 0.9, 0.999, 1e-8, 0.0), 10)?;
+        let adam = rosenbrock_trajectory(&mut Adam::new(0.01, 0.9, 0.999, 1e-8), 10)?;
+        assert_eq!(adam_w, adam);
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use anyhow::Result;
-use crate::Variable;
+use crate::{Variable, VariableData};
 use super::super::OptimizerContent;
+use super::{buffers_to_state, state_to_buffers};
 
 pub struct MomentumSGD {
     pub lr: f64,
     pub momentum: f64,
-    pub params: HashMap<usize, Variable>,
+    pub nesterov: bool,
+    pub velocities: HashMap<String, VariableData>,
 }
 
 impl MomentumSGD {
@@ -14,27 +16,104 @@ impl MomentumSGD {
         Self {
             lr,
             momentum,
-            params: HashMap::new(),
+            nesterov: false,
+            velocities: HashMap::new(),
+        }
+    }
+
+    /// Create the optimizer with Nesterov momentum
+    ///
+    /// The parameter moves by `momentum * v - lr * grad` with the updated velocity `v`,
+    /// which looks ahead along the velocity.
+    pub fn new_nesterov(lr: f64, momentum: f64) -> Self {
+        Self {
+            nesterov: true,
+            ..Self::new(lr, momentum)
         }
     }
 }
 
 impl OptimizerContent for MomentumSGD {
-    fn update_one(&mut self, param: &mut Variable) -> Result<()> {
-        let id = param.id();
-        if !self.params.contains_key(&id) {
-            self.params.insert(
-                id,
-                param.data().zeros_like()?.into()
-            );
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn update_one(&mut self, name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
+        if !self.velocities.contains_key(name) {
+            self.velocities.insert(name.to_string(), param.data().zeros_like()?);
         }
-        let v = self.params.get_mut(&id).unwrap();
-        let new_v = v.data()
-            .scalar_mul(self.momentum)?
-            .sub(&param.grad_result()?.data().scalar_mul(self.lr)?)?;
-        v.set_data(new_v);
-        let new_data = param.data().add(&v.data())?;
+        let v = self.velocities.get_mut(name).unwrap();
+        let grad = grad.scalar_mul(self.lr)?;
+        *v = v.scalar_mul(self.momentum)?.sub(&grad)?;
+        let delta = if self.nesterov {
+            v.scalar_mul(self.momentum)?.sub(&grad)?
+        } else {
+            v.clone()
+        };
+        let new_data = param.data().add(&delta)?;
         param.set_data(new_data);
         Ok(())
     }
+
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        buffers_to_state(&[("velocity", &self.velocities)])
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        self.velocities = state_to_buffers(state, &["velocity"])?.remove(0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::error::KDeZeroError;
+    use crate::test_utility::assert_approx_eq;
+    use super::super::rosenbrock_trajectory;
+
+    #[test]
+    fn nesterov_rosenbrock() -> Result<()> {
+        let (x0, x1) = rosenbrock_trajectory(&mut MomentumSGD::new_nesterov(0.001, 0.9), 1000)?;
+        assert_approx_eq(x0, 0.9951242033914439, 1e-9);
+        assert_approx_eq(x1, 0.9902526111741995, 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn state_dict_resume() -> Result<()> {
+        let grad = VariableData::from(Tensor::new([1.0, -2.0], [2])?);
+        let mut p0 = Variable::new(Tensor::new([0.5, 0.5], [2])?.into());
+        let mut optimizer = MomentumSGD::new(0.1, 0.9);
+        optimizer.update_one("w", &mut p0, &grad)?;
+        let state = optimizer.state_dict();
+        assert_eq!(state.keys().collect::<Vec<_>>(), vec!["velocity.w"]);
+
+        let mut p1 = Variable::new(p0.data().clone());
+        let mut resumed = MomentumSGD::new(0.1, 0.9);
+        resumed.load_state_dict(&state)?;
+        optimizer.update_one("w", &mut p0, &grad)?;
+        resumed.update_one("w", &mut p1, &grad)?;
+        assert_eq!(*p0.data(), *p1.data());
+        Ok(())
+    }
+
+    #[test]
+    fn error_load_state_dict_unexpected_key() -> Result<()> {
+        let mut state = HashMap::new();
+        state.insert("m.w".to_string(), VariableData::from(1.0));
+        match MomentumSGD::new(0.1, 0.9).load_state_dict(&state) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::StateKeyMismatch(vec![], vec!["m.w".to_string()]));
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crate::Variable;
+use crate::{Variable, VariableData};
 use super::super::OptimizerContent;
 
 pub struct SGD {
@@ -13,9 +13,17 @@ impl SGD {
 }
 
 impl OptimizerContent for SGD {
-    fn update_one(&mut self, param: &mut Variable) -> Result<()> {
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn update_one(&mut self, _name: &str, param: &mut Variable, grad: &VariableData) -> Result<()> {
         let new_data = param.data()
-            .sub(&param.grad_result()?.data().scalar_mul(self.lr)?)?;
+            .sub(&grad.scalar_mul(self.lr)?)?;
         param.set_data(new_data);
         Ok(())
     }
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use crate::{Variable, VariableData};
+
+/// How the weight decay is applied before the update of the optimizer
+///
+/// * `L2` - Add `weight_decay * param` to the gradient passed to the optimizer
+/// * `Decoupled` - Multiply the parameter by `1 - lr * weight_decay` as AdamW does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightDecay {
+    None,
+    L2(f64),
+    Decoupled(f64),
+}
+
+impl WeightDecay {
+    /// Apply the weight decay to a parameter
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - The parameter to be decayed
+    /// * `grad` - The gradient of the parameter
+    /// * `lr` - The learning rate used for the parameter
+    ///
+    /// # Returns
+    ///
+    /// * `Result<VariableData>` - The gradient passed to the optimizer.
+    ///   The gradient stored in the parameter is not changed.
+    pub(super) fn apply(&self, param: &mut Variable, grad: &VariableData, lr: f64) -> Result<VariableData> {
+        match *self {
+            WeightDecay::None => Ok(grad.clone()),
+            WeightDecay::L2(weight_decay) => grad.add(&param.data().scalar_mul(weight_decay)?),
+            WeightDecay::Decoupled(weight_decay) => {
+                let new_data = param.data().scalar_mul(1.0 - lr * weight_decay)?;
+                param.set_data(new_data);
+                Ok(grad.clone())
+            },
+        }
+    }
+}
+
+/// Hyperparameters for the parameters whose name matches one of the patterns
+///
+/// A pattern is matched against the whole dotted name of the parameter.
+/// `*` matches any sequence of characters including `.`, and `?` matches one character.
+///
+/// # Fields
+///
+/// * `patterns` - The name patterns such as `*.bias` or `l0.*`
+/// * `lr_scale` - The factor applied to the learning rate of the optimizer,
+///   so that the group follows a scheduler
+/// * `weight_decay` - The weight decay of the group
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamGroup {
+    pub patterns: Vec<String>,
+    pub lr_scale: f64,
+    pub weight_decay: WeightDecay,
+}
+
+impl ParamGroup {
+    pub fn new(patterns: &[&str], lr_scale: f64, weight_decay: WeightDecay) -> Self {
+        Self {
+            patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+            lr_scale,
+            weight_decay,
+        }
+    }
+
+    /// Check if the parameter belongs to the group
+    pub fn matches(&self, name: &str) -> bool {
+        self.patterns.iter().any(|pattern| matches_pattern(pattern, name))
+    }
+}
+
+/// Match `name` against a pattern with the wildcards `*` and `?`
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::Tensor;
+    use crate::test_utility::assert_approx_eq_tensor;
+    use crate::{Model, VariableType};
+    use crate::model::MLP;
+    use crate::function::sigmoid;
+    use crate::optimizer::{Optimizer, SGD, Adam};
+
+    #[test]
+    fn matches_pattern_normal() {
+        assert!(matches_pattern("*.bias", "l0.bias"));
+        assert!(matches_pattern("*.bias", "l1.l0.bias"));
+        assert!(!matches_pattern("*.bias", "l0.weight"));
+        assert!(matches_pattern("l0.*", "l0.weight"));
+        assert!(!matches_pattern("l0.*", "l10.weight"));
+        assert!(matches_pattern("l?.weight", "l1.weight"));
+        assert!(matches_pattern("*", "l0.weight"));
+        assert!(matches_pattern("l0.weight", "l0.weight"));
+        assert!(!matches_pattern("l0.weight", "l0.weights"));
+        assert!(matches_pattern("l*.*t", "l0.weight"));
+    }
+
+    /// An optimizer over an MLP whose gradients are filled with 1
+    fn optimizer_with_grads<T>(optimizer: T) -> Result<Optimizer>
+    where
+        T: crate::OptimizerContent + 'static
+    {
+        let model = Model::new(MLP::new(&[2, 1], sigmoid, VariableType::F64)?);
+        for (_, mut param) in model.get_params() {
+            let data = param.data().clone();
+            param.set_data(data.full_like(2.0)?);
+            param.set_grad(Variable::new(data.ones_like()?));
+        }
+        let mut optimizer = Optimizer::new(optimizer);
+        optimizer.set_model(model);
+        Ok(optimizer)
+    }
+
+    fn param(optimizer: &Optimizer, name: &str) -> Result<Tensor<f64>> {
+        let params = optimizer.get_model_result()?.get_params();
+        let data = params[name].data().to_f64_tensor()?.clone();
+        Ok(data)
+    }
+
+    #[test]
+    fn update_with_groups() -> Result<()> {
+        let mut optimizer = optimizer_with_grads(SGD::new(0.1))?;
+        optimizer.set_weight_decay(WeightDecay::Decoupled(0.5));
+        optimizer.add_param_group(ParamGroup::new(&["*.bias"], 0.5, WeightDecay::None));
+        assert_eq!(optimizer.param_lr("l1.bias"), 0.05);
+        assert_eq!(optimizer.param_lr("l1.weight"), 0.1);
+        optimizer.update()?;
+        // weight: 2 * (1 - 0.1 * 0.5) - 0.1, bias: 2 - 0.05
+        assert_approx_eq_tensor(&param(&optimizer, "l1.weight")?, &Tensor::new(vec![1.8; 2], vec![2, 1])?, 1e-12);
+        assert_approx_eq_tensor(&param(&optimizer, "l1.bias")?, &Tensor::new(vec![1.95], vec![1])?, 1e-12);
+        assert_eq!(optimizer.lr(), 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn update_with_l2() -> Result<()> {
+        let mut optimizer = optimizer_with_grads(SGD::new(0.1))?;
+        optimizer.add_param_group(ParamGroup::new(&["l1.weight"], 1.0, WeightDecay::L2(0.5)));
+        optimizer.update()?;
+        // weight: 2 - 0.1 * (1 + 0.5 * 2), bias: 2 - 0.1
+        assert_approx_eq_tensor(&param(&optimizer, "l1.weight")?, &Tensor::new(vec![1.8; 2], vec![2, 1])?, 1e-12);
+        assert_approx_eq_tensor(&param(&optimizer, "l1.bias")?, &Tensor::new(vec![1.9], vec![1])?, 1e-12);
+        let params = optimizer.get_model_result()?.get_params();
+        let grad = params["l1.weight"].grad_result()?.data().to_f64_tensor()?.clone();
+        assert_eq!(grad, Tensor::ones([2, 1]));
+        Ok(())
+    }
+
+    #[test]
+    fn update_frozen_group() -> Result<()> {
+        let mut optimizer = optimizer_with_grads(Adam::new(0.1, 0.9, 0.999, 1e-8))?;
+        optimizer.add_param_group(ParamGroup::new(&["l1.weight"], 0.0, WeightDecay::None));
+        optimizer.update()?;
+        assert_approx_eq_tensor(&param(&optimizer, "l1.weight")?, &Tensor::new(vec![2.0; 2], vec![2, 1])?, 1e-12);
+        assert_approx_eq_tensor(&param(&optimizer, "l1.bias")?, &Tensor::new(vec![1.9], vec![1])?, 1e-6);
+        Ok(())
+    }
+}
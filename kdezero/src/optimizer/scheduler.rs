@@ -0,0 +1,148 @@
+mod step_lr;
+mod exponential_lr;
+mod cosine_annealing_warm_restarts;
+mod linear_warmup;
+mod one_cycle_lr;
+mod reduce_lr_on_plateau;
+
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::VariableData;
+use crate::error::KDeZeroError;
+
+pub use step_lr::StepLR;
+pub use exponential_lr::ExponentialLR;
+pub use cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+pub use linear_warmup::LinearWarmup;
+pub use one_cycle_lr::OneCycleLR;
+pub use reduce_lr_on_plateau::ReduceLROnPlateau;
+
+/// A learning rate schedule attached to an `Optimizer`
+///
+/// The scheduler is asked for the learning rate with `t = 0` when it is attached,
+/// and then once per call of `Optimizer::scheduler_step` with `t` counting the calls.
+/// Whether a step means an iteration or an epoch is up to the caller.
+pub trait LrScheduler {
+    /// Calculate the learning rate of the step `t`
+    ///
+    /// # Arguments
+    ///
+    /// * `base_lr` - The learning rate of the optimizer when the scheduler was attached
+    /// * `t` - The number of steps since the scheduler was attached
+    /// * `metric` - The monitored value passed to `Optimizer::scheduler_step_with_metric`
+    fn get_lr(&mut self, base_lr: f64, t: usize, metric: Option<f64>) -> Result<f64>;
+
+    /// The internal state of the scheduler, empty if the learning rate only depends on `t`
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        HashMap::new()
+    }
+
+    /// Restore the internal state from `state_dict`
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        check_state_keys(state, &[])
+    }
+}
+
+/// Check that `state` has no key other than `keys`
+///
+/// If it has, `KDeZeroError::StateKeyMismatch` is returned with them as the unexpected keys.
+pub(crate) fn check_state_keys(state: &HashMap<String, VariableData>, keys: &[&str]) -> Result<()> {
+    let mut unexpected = state.keys()
+        .filter(|key| !keys.contains(&key.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !unexpected.is_empty() {
+        unexpected.sort();
+        return Err(KDeZeroError::StateKeyMismatch(vec![], unexpected).into())
+    }
+    Ok(())
+}
+
+/// Read a `f64` scalar of a state dict
+pub(crate) fn state_f64(value: &VariableData) -> Result<f64> {
+    Ok(*value.to_f64_tensor()?.at([])?)
+}
+
+/// Read a `usize` scalar of a state dict
+pub(crate) fn state_usize(value: &VariableData) -> Result<usize> {
+    Ok(*value.to_usize_tensor()?.at([])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use crate::optimizer::{Optimizer, SGD};
+
+    #[test]
+    fn optimizer_scheduler_step() -> Result<()> {
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        assert_approx_eq(optimizer.lr(), 0.1, 1e-12);
+        optimizer.set_scheduler(LinearWarmup::new(2, 0.5, Some(Box::new(ExponentialLR::new(0.5)))))?;
+        assert_approx_eq(optimizer.lr(), 0.05, 1e-12);
+        let lrs = (0..4)
+            .map(|_| optimizer.scheduler_step())
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([0.075, 0.1, 0.05, 0.025]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        assert_approx_eq(optimizer.lr(), 0.025, 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn optimizer_scheduler_state_dict_resume() -> Result<()> {
+        let new_optimizer = || -> Result<Optimizer> {
+            let mut optimizer = Optimizer::new(SGD::new(0.1));
+            let mut scheduler = ReduceLROnPlateau::new(0.5, 0);
+            scheduler.cooldown = 1;
+            optimizer.set_scheduler(LinearWarmup::new(1, 0.5, Some(Box::new(scheduler))))?;
+            Ok(optimizer)
+        };
+        let mut optimizer = new_optimizer()?;
+        for metric in [3.0, 2.0, 2.5] {
+            optimizer.scheduler_step_with_metric(metric)?;
+        }
+        let state = optimizer.state_dict();
+        assert_eq!(state["scheduler_steps"], VariableData::from(3usize));
+        assert_eq!(state["scheduler.after.best"], VariableData::from(2.0));
+
+        let mut resumed = new_optimizer()?;
+        resumed.load_state_dict(&state)?;
+        assert_approx_eq(resumed.lr(), optimizer.lr(), 1e-12);
+        for metric in [2.5, 2.5, 2.5] {
+            let lr = optimizer.scheduler_step_with_metric(metric)?;
+            assert_approx_eq(resumed.scheduler_step_with_metric(metric)?, lr, 1e-12);
+        }
+        assert_eq!(resumed.state_dict(), optimizer.state_dict());
+        Ok(())
+    }
+
+    #[test]
+    fn error_load_scheduler_state_without_scheduler() -> Result<()> {
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        optimizer.set_scheduler(ReduceLROnPlateau::new(0.5, 0))?;
+        let state = optimizer.state_dict();
+        match Optimizer::new(SGD::new(0.1)).load_state_dict(&state) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::StateKeyMismatch(vec![], vec![
+                    "scheduler.cooldown_counter".to_string(),
+                    "scheduler.lr".to_string(),
+                    "scheduler.num_bad_steps".to_string(),
+                ]));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn optimizer_scheduler_step_without_scheduler() -> Result<()> {
+        let mut optimizer = Optimizer::new(SGD::new(0.1));
+        assert!(optimizer.scheduler_step().is_err());
+        optimizer.set_lr(0.2);
+        assert_approx_eq(optimizer.lr(), 0.2, 1e-12);
+        Ok(())
+    }
+}
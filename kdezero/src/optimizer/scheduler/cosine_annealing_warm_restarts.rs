@@ -0,0 +1,68 @@
+use anyhow::Result;
+use super::LrScheduler;
+
+/// Anneal the learning rate from the base learning rate to `eta_min` along a cosine curve,
+/// restarting from the base learning rate at the end of each cycle
+///
+/// The first cycle takes `t_0` steps and each following cycle is `t_mult` times longer.
+pub struct CosineAnnealingWarmRestarts {
+    pub t_0: usize,
+    pub t_mult: usize,
+    pub eta_min: f64,
+}
+
+impl CosineAnnealingWarmRestarts {
+    pub fn new(t_0: usize, t_mult: usize, eta_min: f64) -> Self {
+        Self { t_0, t_mult, eta_min }
+    }
+
+    /// The position in the current cycle and the length of the cycle
+    fn cycle(&self, t: usize) -> (usize, usize) {
+        let mut t_cur = t;
+        let mut t_i = self.t_0.max(1);
+        if self.t_mult <= 1 {
+            return (t_cur % t_i, t_i)
+        }
+        while t_cur >= t_i {
+            t_cur -= t_i;
+            t_i *= self.t_mult;
+        }
+        (t_cur, t_i)
+    }
+}
+
+impl LrScheduler for CosineAnnealingWarmRestarts {
+    fn get_lr(&mut self, base_lr: f64, t: usize, _metric: Option<f64>) -> Result<f64> {
+        let (t_cur, t_i) = self.cycle(t);
+        let cos = (std::f64::consts::PI * t_cur as f64 / t_i as f64).cos();
+        Ok(self.eta_min + (base_lr - self.eta_min) * (1.0 + cos) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = CosineAnnealingWarmRestarts::new(2, 2, 0.0);
+        let lrs = (0..8)
+            .map(|t| scheduler.get_lr(1.0, t, None))
+            .collect::<Result<Vec<_>>>()?;
+        let expected = [1.0, 0.5, 1.0, 0.8535533905932737, 0.5, 0.14644660940672627, 1.0, 0.9619397662556434];
+        for (lr, expected) in lrs.iter().zip(expected) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn get_lr_constant_cycle() -> Result<()> {
+        let mut scheduler = CosineAnnealingWarmRestarts::new(4, 1, 0.1);
+        assert_approx_eq(scheduler.get_lr(1.0, 2, None)?, 0.55, 1e-12);
+        assert_approx_eq(scheduler.get_lr(1.0, 4, None)?, 1.0, 1e-12);
+        assert_approx_eq(scheduler.get_lr(1.0, 6, None)?, 0.55, 1e-12);
+        Ok(())
+    }
+}
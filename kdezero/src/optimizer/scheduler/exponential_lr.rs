@@ -0,0 +1,34 @@
+use anyhow::Result;
+use super::LrScheduler;
+
+/// Multiply the learning rate by `gamma` every step
+pub struct ExponentialLR {
+    pub gamma: f64,
+}
+
+impl ExponentialLR {
+    pub fn new(gamma: f64) -> Self {
+        Self { gamma }
+    }
+}
+
+impl LrScheduler for ExponentialLR {
+    fn get_lr(&mut self, base_lr: f64, t: usize, _metric: Option<f64>) -> Result<f64> {
+        Ok(base_lr * self.gamma.powi(t as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = ExponentialLR::new(0.9);
+        assert_approx_eq(scheduler.get_lr(0.1, 0, None)?, 0.1, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.1, 1, None)?, 0.09, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.1, 3, None)?, 0.0729, 1e-12);
+        Ok(())
+    }
+}
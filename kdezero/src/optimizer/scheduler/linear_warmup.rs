@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::VariableData;
+use super::{LrScheduler, check_state_keys};
+
+/// Increase the learning rate linearly from `start_factor * base_lr` to `base_lr`
+/// over `warmup_steps` steps, then hand over to `after`
+///
+/// After the warmup, `after` sees the step counted from the end of the warmup.
+/// Without `after`, the learning rate stays at `base_lr`.
+pub struct LinearWarmup {
+    pub warmup_steps: usize,
+    pub start_factor: f64,
+    pub after: Option<Box<dyn LrScheduler>>,
+}
+
+impl LinearWarmup {
+    pub fn new(warmup_steps: usize, start_factor: f64, after: Option<Box<dyn LrScheduler>>) -> Self {
+        Self { warmup_steps, start_factor, after }
+    }
+}
+
+impl LrScheduler for LinearWarmup {
+    fn get_lr(&mut self, base_lr: f64, t: usize, metric: Option<f64>) -> Result<f64> {
+        if t < self.warmup_steps {
+            let ratio = t as f64 / self.warmup_steps as f64;
+            return Ok(base_lr * (self.start_factor + (1.0 - self.start_factor) * ratio))
+        }
+        match &mut self.after {
+            Some(after) => after.get_lr(base_lr, t - self.warmup_steps, metric),
+            None => Ok(base_lr),
+        }
+    }
+
+    /// The state of the scheduler after the warmup keyed by `after.<key>`
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        match &self.after {
+            Some(after) => after.state_dict().into_iter()
+                .map(|(key, value)| (format!("after.{}", key), value))
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        let after = match &mut self.after {
+            Some(after) => after,
+            None => return check_state_keys(state, &[]),
+        };
+        let (after_state, others): (HashMap<_, _>, HashMap<_, _>) = state.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .partition(|(key, _)| key.starts_with("after."));
+        check_state_keys(&others, &[])?;
+        let after_state = after_state.into_iter()
+            .map(|(key, value)| (key["after.".len()..].to_string(), value))
+            .collect();
+        after.load_state_dict(&after_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+    use crate::optimizer::scheduler::StepLR;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = LinearWarmup::new(4, 0.0, None);
+        let lrs = (0..6)
+            .map(|t| scheduler.get_lr(0.4, t, None))
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([0.0, 0.1, 0.2, 0.3, 0.4, 0.4]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn get_lr_after() -> Result<()> {
+        let mut scheduler = LinearWarmup::new(2, 0.5, Some(Box::new(StepLR::new(2, 0.1))));
+        let lrs = (0..6)
+            .map(|t| scheduler.get_lr(1.0, t, None))
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([0.5, 0.75, 1.0, 1.0, 0.1, 0.1]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+}
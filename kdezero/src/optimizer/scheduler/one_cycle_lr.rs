@@ -0,0 +1,76 @@
+use anyhow::Result;
+use super::LrScheduler;
+
+/// The one-cycle policy
+///
+/// The learning rate goes from `max_lr / div_factor` up to `max_lr` along a cosine curve
+/// in the first `pct_start` of `total_steps`, then down to
+/// `max_lr / (div_factor * final_div_factor)` at the step `total_steps - 1`,
+/// and stays there afterwards.
+///
+/// # Note
+///
+/// The learning rate of the optimizer is ignored; the schedule depends only on `max_lr`.
+pub struct OneCycleLR {
+    pub max_lr: f64,
+    pub total_steps: usize,
+    pub pct_start: f64,
+    pub div_factor: f64,
+    pub final_div_factor: f64,
+}
+
+impl OneCycleLR {
+    /// Create a one-cycle schedule with `pct_start = 0.3`, `div_factor = 25`
+    /// and `final_div_factor = 1e4`
+    pub fn new(max_lr: f64, total_steps: usize) -> Self {
+        Self {
+            max_lr,
+            total_steps,
+            pct_start: 0.3,
+            div_factor: 25.0,
+            final_div_factor: 1e4,
+        }
+    }
+
+    fn anneal(start: f64, end: f64, pct: f64) -> f64 {
+        end + (start - end) / 2.0 * (1.0 + (std::f64::consts::PI * pct).cos())
+    }
+}
+
+impl LrScheduler for OneCycleLR {
+    fn get_lr(&mut self, _base_lr: f64, t: usize, _metric: Option<f64>) -> Result<f64> {
+        let initial_lr = self.max_lr / self.div_factor;
+        let min_lr = initial_lr / self.final_div_factor;
+        let end_step = self.total_steps.saturating_sub(1) as f64;
+        let peak_step = (self.pct_start * self.total_steps as f64 - 1.0).clamp(0.0, end_step);
+        let t = (t as f64).min(end_step);
+        if t <= peak_step {
+            let pct = if peak_step > 0.0 { t / peak_step } else { 1.0 };
+            Ok(Self::anneal(initial_lr, self.max_lr, pct))
+        } else {
+            let pct = (t - peak_step) / (end_step - peak_step);
+            Ok(Self::anneal(self.max_lr, min_lr, pct))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = OneCycleLR::new(1.0, 11);
+        scheduler.final_div_factor = 10.0;
+        scheduler.div_factor = 10.0;
+        scheduler.pct_start = 0.3;
+        // peak at the step 2.3 and the end at the step 10
+        assert_approx_eq(scheduler.get_lr(0.5, 0, None)?, 0.1, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.5, 1, None)?, 0.4584447941263148, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.5, 3, None)?, 0.9799490219391762, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.5, 10, None)?, 0.01, 1e-12);
+        assert_approx_eq(scheduler.get_lr(0.5, 20, None)?, 0.01, 1e-12);
+        Ok(())
+    }
+}
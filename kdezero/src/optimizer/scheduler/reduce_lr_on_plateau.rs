@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::VariableData;
+use crate::error::KDeZeroError;
+use super::{LrScheduler, check_state_keys, state_f64, state_usize};
+
+/// Multiply the learning rate by `factor` when the metric has not improved
+/// for more than `patience` steps
+///
+/// The metric improves when it is lower than `best * (1 - threshold)`,
+/// or higher than `best * (1 + threshold)` if `maximize` is set.
+/// After a reduction, `cooldown` steps are not counted and the learning rate never goes below `min_lr`.
+///
+/// # Note
+///
+/// Every step after the first has to be done with `Optimizer::scheduler_step_with_metric`.
+pub struct ReduceLROnPlateau {
+    pub factor: f64,
+    pub patience: usize,
+    pub threshold: f64,
+    pub cooldown: usize,
+    pub min_lr: f64,
+    pub maximize: bool,
+    lr: Option<f64>,
+    best: Option<f64>,
+    num_bad_steps: usize,
+    cooldown_counter: usize,
+}
+
+impl ReduceLROnPlateau {
+    /// Create a scheduler minimizing the metric with `threshold = 1e-4`, `cooldown = 0` and `min_lr = 0`
+    pub fn new(factor: f64, patience: usize) -> Self {
+        Self {
+            factor,
+            patience,
+            threshold: 1e-4,
+            cooldown: 0,
+            min_lr: 0.0,
+            maximize: false,
+            lr: None,
+            best: None,
+            num_bad_steps: 0,
+            cooldown_counter: 0,
+        }
+    }
+
+    fn is_better(&self, metric: f64) -> bool {
+        match self.best {
+            None => true,
+            Some(best) if self.maximize => metric > best * (1.0 + self.threshold.copysign(best)),
+            Some(best) => metric < best * (1.0 - self.threshold.copysign(best)),
+        }
+    }
+}
+
+impl LrScheduler for ReduceLROnPlateau {
+    fn get_lr(&mut self, base_lr: f64, t: usize, metric: Option<f64>) -> Result<f64> {
+        if t == 0 {
+            self.lr = Some(base_lr);
+            self.best = None;
+            self.num_bad_steps = 0;
+            self.cooldown_counter = 0;
+            return Ok(base_lr)
+        }
+        let metric = metric.ok_or_else(|| KDeZeroError::Error(
+            "ReduceLROnPlateau needs a metric".to_string()
+        ))?;
+        let mut lr = self.lr.unwrap_or(base_lr);
+        if self.is_better(metric) {
+            self.best = Some(metric);
+            self.num_bad_steps = 0;
+        } else {
+            self.num_bad_steps += 1;
+        }
+        if self.cooldown_counter > 0 {
+            self.cooldown_counter -= 1;
+            self.num_bad_steps = 0;
+        }
+        if self.num_bad_steps > self.patience {
+            lr = (lr * self.factor).max(self.min_lr);
+            self.cooldown_counter = self.cooldown;
+            self.num_bad_steps = 0;
+        }
+        self.lr = Some(lr);
+        Ok(lr)
+    }
+
+    /// The current learning rate, the best metric, the number of the bad steps and the remaining cooldown
+    fn state_dict(&self) -> HashMap<String, VariableData> {
+        let mut state = HashMap::from([
+            ("num_bad_steps".to_string(), VariableData::from(self.num_bad_steps)),
+            ("cooldown_counter".to_string(), VariableData::from(self.cooldown_counter)),
+        ]);
+        if let Some(lr) = self.lr {
+            state.insert("lr".to_string(), VariableData::from(lr));
+        }
+        if let Some(best) = self.best {
+            state.insert("best".to_string(), VariableData::from(best));
+        }
+        state
+    }
+
+    fn load_state_dict(&mut self, state: &HashMap<String, VariableData>) -> Result<()> {
+        check_state_keys(state, &["lr", "best", "num_bad_steps", "cooldown_counter"])?;
+        self.lr = state.get("lr").map(state_f64).transpose()?;
+        self.best = state.get("best").map(state_f64).transpose()?;
+        self.num_bad_steps = state.get("num_bad_steps").map(state_usize).transpose()?.unwrap_or(0);
+        self.cooldown_counter = state.get("cooldown_counter").map(state_usize).transpose()?.unwrap_or(0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = ReduceLROnPlateau::new(0.5, 1);
+        assert_approx_eq(scheduler.get_lr(1.0, 0, None)?, 1.0, 1e-12);
+        let metrics = [3.0, 2.0, 2.0, 2.0, 1.0, 1.5, 1.5, 1.5];
+        let lrs = metrics.iter().enumerate()
+            .map(|(i, metric)| scheduler.get_lr(1.0, i + 1, Some(*metric)))
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.25, 0.25]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn get_lr_cooldown_min_lr() -> Result<()> {
+        let mut scheduler = ReduceLROnPlateau::new(0.1, 0);
+        scheduler.cooldown = 1;
+        scheduler.min_lr = 0.05;
+        scheduler.maximize = true;
+        scheduler.get_lr(1.0, 0, None)?;
+        let metrics = [1.0, 1.0, 1.0, 1.0];
+        let lrs = metrics.iter().enumerate()
+            .map(|(i, metric)| scheduler.get_lr(1.0, i + 1, Some(*metric)))
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([1.0, 0.1, 0.1, 0.05]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn state_dict_resume() -> Result<()> {
+        let mut scheduler = ReduceLROnPlateau::new(0.5, 1);
+        scheduler.get_lr(1.0, 0, None)?;
+        for (i, metric) in [3.0, 3.0, 3.0].iter().enumerate() {
+            scheduler.get_lr(1.0, i + 1, Some(*metric))?;
+        }
+        let mut resumed = ReduceLROnPlateau::new(0.5, 1);
+        resumed.load_state_dict(&scheduler.state_dict())?;
+        assert_eq!(resumed.state_dict(), scheduler.state_dict());
+        for (i, metric) in [3.0, 3.0].iter().enumerate() {
+            let lr = scheduler.get_lr(1.0, i + 4, Some(*metric))?;
+            assert_approx_eq(resumed.get_lr(1.0, i + 4, Some(*metric))?, lr, 1e-12);
+        }
+        assert_approx_eq(scheduler.get_lr(1.0, 6, Some(3.0))?, 0.25, 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn get_lr_without_metric() {
+        let mut scheduler = ReduceLROnPlateau::new(0.5, 1);
+        assert!(scheduler.get_lr(1.0, 1, None).is_err());
+    }
+}
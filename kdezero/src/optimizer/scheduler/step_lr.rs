@@ -0,0 +1,38 @@
+use anyhow::Result;
+use super::LrScheduler;
+
+/// Multiply the learning rate by `gamma` every `step_size` steps
+pub struct StepLR {
+    pub step_size: usize,
+    pub gamma: f64,
+}
+
+impl StepLR {
+    pub fn new(step_size: usize, gamma: f64) -> Self {
+        Self { step_size, gamma }
+    }
+}
+
+impl LrScheduler for StepLR {
+    fn get_lr(&mut self, base_lr: f64, t: usize, _metric: Option<f64>) -> Result<f64> {
+        Ok(base_lr * self.gamma.powi((t / self.step_size.max(1)) as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utility::assert_approx_eq;
+
+    #[test]
+    fn get_lr() -> Result<()> {
+        let mut scheduler = StepLR::new(3, 0.1);
+        let lrs = (0..7)
+            .map(|t| scheduler.get_lr(1.0, t, None))
+            .collect::<Result<Vec<_>>>()?;
+        for (lr, expected) in lrs.iter().zip([1.0, 1.0, 1.0, 0.1, 0.1, 0.1, 0.01]) {
+            assert_approx_eq(*lr, expected, 1e-12);
+        }
+        Ok(())
+    }
+}
@@ -50,6 +50,38 @@ pub fn numerical_diff(f: &mut dyn FunctionContent, x: &Variable, eps: f64) -> Re
     Ok(x)
 }
 
+/// Calculate the gradient of the sum of `f(x)` numerically by the central difference of each element
+pub fn numerical_grad(f: &mut dyn FnMut(&Variable) -> Result<Variable>, x: &Tensor<f64>, eps: f64) -> Result<Tensor<f64>> {
+    let mut eval = |data: Vec<f64>| -> Result<f64> {
+        let y = f(&Variable::new(Tensor::new(data, x.get_shape().clone())?.into()))?;
+        let sum = y.data().to_f64_tensor()?.get_data().iter().sum::<f64>();
+        Ok(sum)
+    };
+    let mut grad = Vec::with_capacity(x.size());
+    for i in 0..x.size() {
+        let mut data = x.get_data().clone();
+        data[i] = x.get_data()[i] + eps;
+        let y1 = eval(data.clone())?;
+        data[i] = x.get_data()[i] - eps;
+        let y0 = eval(data)?;
+        grad.push((y1 - y0) / (2.0 * eps));
+    }
+    Tensor::new(grad, x.get_shape().clone())
+}
+
+/// Assert that the gradient of `x` set by `backward` is close to the numerical gradient of `f` at `x`
+pub fn assert_numerical_grad(f: &mut dyn FnMut(&Variable) -> Result<Variable>, x: &Variable, eps: f64, tolerance: f64) -> Result<()> {
+    let x_data = x.data().to_f64_tensor()?.clone();
+    let expected = numerical_grad(f, &x_data, eps)?;
+    assert_approx_eq_tensor(x.grad_result()?.data().to_f64_tensor()?, &expected, tolerance);
+    Ok(())
+}
+
+/// Deterministic test data of the given shape, `sin(i * scale)` for the `i`-th element
+pub fn sin_data(shape: &[usize], scale: f64) -> Result<Tensor<f64>> {
+    Ok((&Tensor::<f64>::arrange(shape)? * scale).sin())
+}
+
 pub fn accuracy<T>(y: &Tensor<T>, t: &Tensor<usize>) -> Result<f64>
 where
     T: PartialOrd + Clone
@@ -107,6 +139,17 @@ mod tests {
             &dy, &Tensor::new([4.0], []).unwrap(), 1e-6);
     }
 
+    #[test]
+    fn numerical_grad_normal() -> Result<()> {
+        use crate::function::matmul;
+
+        let a = Variable::new(Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?.into());
+        let x = Tensor::new(vec![1.0, -1.0], vec![2, 1])?;
+        let grad = numerical_grad(&mut |x| matmul(&a, x), &x, 1e-4)?;
+        assert_approx_eq_tensor(&grad, &Tensor::new(vec![4.0, 6.0], vec![2, 1])?, 1e-6);
+        Ok(())
+    }
+
     #[test]
     fn numerical_diff_normal() -> Result<()> {
         use crate::function::Square;
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use crate::error::KDeZeroError;
 use crate::Variable;
+use crate::function::{sum_to, reshape, broadcast_to};
 
 pub fn check_variable_count(xs: &Vec<&Variable>, n: usize) -> Result<()> {
     if xs.len() != n {
@@ -36,3 +37,45 @@ pub fn check_dimensions(x: &Variable, ndim: usize) -> Result<()> {
         Ok(())
     }
 }
+
+/// Check that `channels` and `out_channels` can be split into `groups`
+pub fn check_groups(name: &str, channels: usize, out_channels: usize, groups: usize) -> Result<()> {
+    if groups == 0 || !channels.is_multiple_of(groups) || !out_channels.is_multiple_of(groups) {
+        return Err(KDeZeroError::Error(format!(
+            "{}: channels {} and {} are not divisible by groups {}", name, channels, out_channels, groups
+        )).into())
+    }
+    Ok(())
+}
+
+pub fn sum_to_input_shape(gx: Variable, x: &Variable) -> Result<Variable> {
+    if *gx.shape() == *x.shape() {
+        Ok(gx)
+    } else {
+        let shape = x.shape().to_vec();
+        sum_to(&gx, &shape)
+    }
+}
+
+/// Broadcast the gradient of a reduction back to the shape of its input
+///
+/// # Arguments
+///
+/// * `gy` - The gradient of the output of the reduction
+/// * `x_shape` - The shape of the input of the reduction
+/// * `axis` - The reduced axes
+/// * `keepdims` - Whether the reduction kept the dimensions
+pub fn reshape_sum_backward(gy: &Variable, x_shape: &[usize], axis: &[usize], keepdims: bool) -> Result<Variable> {
+    let gy = if keepdims || x_shape.is_empty() {
+        gy.clone()
+    } else {
+        let mut axis = axis.to_vec();
+        axis.sort();
+        let mut shape = gy.shape().to_vec();
+        for i in axis {
+            shape.insert(i, 1);
+        }
+        reshape(gy, &shape)?
+    };
+    broadcast_to(&gy, x_shape)
+}
@@ -54,7 +54,11 @@ impl Variable {
             let ys = f.outputs_clone_result()?;
             let grad = ys
                 .iter()
-                .map(|y| y.grad_result())
+                .map(|y| if y.is_grad_none() {
+                    Ok(y.data().zeros_like()?.into())
+                } else {
+                    y.grad_result()
+                })
                 .collect::<Result<Vec<_>>>()?;
             let _guard = no_grad_frag(!create_graph);
             let xgs = f.backward(&grad)?;
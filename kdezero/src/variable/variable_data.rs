@@ -2,6 +2,7 @@ mod info;
 mod create;
 mod conversion;
 mod operator;
+mod npy;
 
 use ktensor::Tensor;
 
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::Result;
+use ktensor::Tensor;
+use ktensor::npy::{NpyElement, NpyHeader, read_npz_entries, write_npz_entries};
+use crate::error::KDeZeroError;
+use super::VariableData;
+
+impl VariableData {
+    /// Decode the data from the content of a `.npy` file
+    ///
+    /// The variant is chosen by the dtype of the file.
+    /// `<u8` is read as `USIZE` and `|b1` as `Bool`.
+    pub fn from_npy_bytes(bytes: &[u8]) -> Result<Self> {
+        let (header, _) = NpyHeader::parse(bytes)?;
+        Ok(match header.descr.as_str() {
+            f32::DESCR => Tensor::<f32>::from_npy_bytes(bytes)?.into(),
+            f64::DESCR => Tensor::<f64>::from_npy_bytes(bytes)?.into(),
+            i32::DESCR => Tensor::<i32>::from_npy_bytes(bytes)?.into(),
+            i64::DESCR => Tensor::<i64>::from_npy_bytes(bytes)?.into(),
+            usize::DESCR => Tensor::<usize>::from_npy_bytes(bytes)?.into(),
+            bool::DESCR => Tensor::<bool>::from_npy_bytes(bytes)?.into(),
+            descr => return Err(KDeZeroError::NotImplementedType(
+                "from_npy_bytes".to_string(),
+                descr.to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn to_npy_bytes(&self) -> Result<Vec<u8>> {
+        Ok(match self {
+            Self::F32(x) => x.to_npy_bytes(),
+            Self::F64(x) => x.to_npy_bytes(),
+            Self::I32(x) => x.to_npy_bytes(),
+            Self::I64(x) => x.to_npy_bytes(),
+            Self::USIZE(x) => x.to_npy_bytes(),
+            Self::Bool(x) => x.to_npy_bytes(),
+            Self::None => return Err(KDeZeroError::NotImplementedType(
+                "to_npy_bytes".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn read_npy<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_npy_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn write_npy<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_npy_bytes()?)?;
+        Ok(())
+    }
+
+    /// Read the named data from a `.npz` archive, which may mix dtypes
+    pub fn read_npz<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Self>> {
+        read_npz_entries(path)?
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, Self::from_npy_bytes(&bytes)?)))
+            .collect()
+    }
+
+    pub fn write_npz<P: AsRef<Path>>(path: P, data: &[(&str, &Self)]) -> Result<()> {
+        let entries = data.iter()
+            .map(|(name, data)| Ok((name.to_string(), data.to_npy_bytes()?)))
+            .collect::<Result<Vec<_>>>()?;
+        write_npz_entries(path, &entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktensor::error::TensorError;
+
+    #[test]
+    fn npy_bytes_round_trip() -> Result<()> {
+        let data: Vec<VariableData> = vec![
+            Tensor::<f32>::arrange([2, 2])?.into(),
+            Tensor::<f64>::arrange([3])?.into(),
+            Tensor::<i32>::arrange([1, 2])?.into(),
+            Tensor::<i64>::arrange([2])?.into(),
+            Tensor::<usize>::arrange([2, 1])?.into(),
+            Tensor::new([true, false, true], [3])?.into(),
+        ];
+        for x in data {
+            assert_eq!(VariableData::from_npy_bytes(&x.to_npy_bytes()?)?, x);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_from_npy_bytes_dtype() -> Result<()> {
+        let mut bytes = Tensor::<f32>::arrange([1])?.to_npy_bytes();
+        let position = bytes.windows(3).position(|w| w == b"<f4").unwrap();
+        bytes[position] = b'>';
+        match VariableData::from_npy_bytes(&bytes) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "from_npy_bytes".to_string(), ">f4".to_string()));
+            }
+        }
+        match Tensor::<f32>::from_npy_bytes(&bytes) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::DtypeMismatchError(">f4".to_string(), "<f4".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn npz_round_trip() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("kdezero_npz_{}.npz", std::process::id()));
+        let w: VariableData = Tensor::<f32>::arrange([2, 3])?.into();
+        let t: VariableData = Tensor::<usize>::vector(vec![1, 0]).into();
+        VariableData::write_npz(&path, &[("w", &w), ("t", &t)])?;
+        let data = VariableData::read_npz(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(data.len(), 2);
+        assert_eq!(data["w"], w);
+        assert_eq!(data["t"], t);
+        Ok(())
+    }
+}
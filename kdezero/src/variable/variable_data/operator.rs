@@ -1,15 +1,55 @@
 use anyhow::Result;
+use ktensor::{SliceElem, Tensor, TensorView, linalg};
+use ktensor::tensor::MatMulKernel;
 use super::VariableData;
 use crate::error::KDeZeroError;
 
+/// Compare two `VariableData` of the same type elementwise into `VariableData::Bool`
+macro_rules! compare_variable_data {
+    ($lhs:expr, $rhs:expr, $method:ident) => {
+        Ok(match ($lhs, $rhs) {
+            (VariableData::F32(x), VariableData::F32(y)) => x.$method(y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => x.$method(y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => x.$method(y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => x.$method(y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => x.$method(y)?.into(),
+            (VariableData::Bool(x), VariableData::Bool(y)) => x.$method(y)?.into(),
+            (lhs, rhs) => return Err(KDeZeroError::NotImplementedType(
+                stringify!($method).to_string(),
+                format!("{:?}, {:?}", lhs.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    };
+}
+
+/// Compare `VariableData` elementwise with a `f64` scalar into `VariableData::Bool`
+///
+/// Integer elements are converted to `f64` instead of the scalar,
+/// so a non-integral or negative scalar is not truncated.
+macro_rules! compare_scalar_variable_data {
+    ($lhs:expr, $value:expr, $method:ident) => {
+        Ok(match $lhs {
+            VariableData::F32(x) => x.$method(&($value as f32)).into(),
+            VariableData::F64(x) => x.$method(&$value).into(),
+            VariableData::I32(x) => x.view().map(|&v| v as f64).$method(&$value).into(),
+            VariableData::I64(x) => x.view().map(|&v| v as f64).$method(&$value).into(),
+            VariableData::USIZE(x) => x.view().map(|&v| v as f64).$method(&$value).into(),
+            lhs => return Err(KDeZeroError::NotImplementedType(
+                stringify!($method).to_string(),
+                lhs.data_type().to_string(),
+            ).into()),
+        })
+    };
+}
+
 impl VariableData {
     pub fn add(&self, other: &VariableData) -> Result<VariableData> {
         Ok(match (self, other) {
-            (VariableData::F32(x), VariableData::F32(y)) => (x + y).into(),
-            (VariableData::F64(x), VariableData::F64(y)) => (x + y).into(),
-            (VariableData::I32(x), VariableData::I32(y)) => (x + y).into(),
-            (VariableData::I64(x), VariableData::I64(y)) => (x + y).into(),
-            (VariableData::USIZE(x), VariableData::USIZE(y)) => (x + y).into(),
+            (VariableData::F32(x), VariableData::F32(y)) => x.try_add(y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => x.try_add(y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => x.try_add(y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => x.try_add(y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => x.try_add(y)?.into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "add".to_string(),
                 format!("{:?}, {:?}", self.data_type(), other.data_type()),
@@ -19,11 +59,11 @@ impl VariableData {
 
     pub fn sub(&self, other: &VariableData) -> Result<VariableData> {
         Ok(match (self, other) {
-            (VariableData::F32(x), VariableData::F32(y)) => (x - y).into(),
-            (VariableData::F64(x), VariableData::F64(y)) => (x - y).into(),
-            (VariableData::I32(x), VariableData::I32(y)) => (x - y).into(),
-            (VariableData::I64(x), VariableData::I64(y)) => (x - y).into(),
-            (VariableData::USIZE(x), VariableData::USIZE(y)) => (x - y).into(),
+            (VariableData::F32(x), VariableData::F32(y)) => x.try_sub(y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => x.try_sub(y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => x.try_sub(y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => x.try_sub(y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => x.try_sub(y)?.into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "sub".to_string(),
                 format!("{:?}, {:?}", self.data_type(), other.data_type()),
@@ -33,11 +73,11 @@ impl VariableData {
 
     pub fn mul(&self, other: &VariableData) -> Result<VariableData> {
         Ok(match (self, other) {
-            (VariableData::F32(x), VariableData::F32(y)) => (x * y).into(),
-            (VariableData::F64(x), VariableData::F64(y)) => (x * y).into(),
-            (VariableData::I32(x), VariableData::I32(y)) => (x * y).into(),
-            (VariableData::I64(x), VariableData::I64(y)) => (x * y).into(),
-            (VariableData::USIZE(x), VariableData::USIZE(y)) => (x * y).into(),
+            (VariableData::F32(x), VariableData::F32(y)) => x.try_mul(y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => x.try_mul(y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => x.try_mul(y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => x.try_mul(y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => x.try_mul(y)?.into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "mul".to_string(),
                 format!("{:?}, {:?}", self.data_type(), other.data_type()),
@@ -47,11 +87,11 @@ impl VariableData {
 
     pub fn div(&self, other: &VariableData) -> Result<VariableData> {
         Ok(match (self, other) {
-            (VariableData::F32(x), VariableData::F32(y)) => (x / y).into(),
-            (VariableData::F64(x), VariableData::F64(y)) => (x / y).into(),
-            (VariableData::I32(x), VariableData::I32(y)) => (x / y).into(),
-            (VariableData::I64(x), VariableData::I64(y)) => (x / y).into(),
-            (VariableData::USIZE(x), VariableData::USIZE(y)) => (x / y).into(),
+            (VariableData::F32(x), VariableData::F32(y)) => x.try_div(y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => x.try_div(y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => x.try_div(y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => x.try_div(y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => x.try_div(y)?.into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "div".to_string(),
                 format!("{:?}, {:?}", self.data_type(), other.data_type()),
@@ -100,6 +140,28 @@ impl VariableData {
         })
     }
 
+    pub fn clip(&self, min: f64, max: f64) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.clip(min as f32, max as f32).into(),
+            VariableData::F64(x) => x.clip(min, max).into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "clip".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn all_finite(&self) -> Result<bool> {
+        match self {
+            VariableData::F32(x) => Ok(x.all_finite()),
+            VariableData::F64(x) => Ok(x.all_finite()),
+            _ => Err(KDeZeroError::NotImplementedType(
+                "all_finite".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        }
+    }
+
     pub fn square(&self) -> Result<VariableData> {
         Ok(match self {
             VariableData::F32(x) => (x * x).into(),
@@ -225,14 +287,55 @@ impl VariableData {
                 self.data_type().to_string(),
             ).into()),
         }
-        let x_max = self.max_with_axis(axis, true)?
-            .broadcast_to(self.shape())?;
-        let y = self.sub(&x_max)?
-            .exp()?
-            .sum(Some(&vec![axis]), true)?
-            .log()?
-            .broadcast_to(self.shape())?;
-        Ok(x_max.add(&y)?)
+        self.logsumexp_with_axis(axis, true)?
+            .broadcast_to(self.shape())
+    }
+
+    pub fn logsumexp_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.logsumexp_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.logsumexp_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "logsumexp_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn mean_with_axis(&self, axis: &[usize], keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.mean_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.mean_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "mean_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn var_with_axis(&self, axis: &[usize], keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.var_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.var_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "var_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn prod_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.prod_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.prod_with_axis(axis, keepdims)?.into(),
+            VariableData::I32(x) => x.prod_with_axis(axis, keepdims)?.into(),
+            VariableData::I64(x) => x.prod_with_axis(axis, keepdims)?.into(),
+            VariableData::USIZE(x) => x.prod_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "prod_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
     }
 
     pub fn matmul(&self, other: &VariableData) -> Result<VariableData> {
@@ -249,6 +352,44 @@ impl VariableData {
         })
     }
 
+    /// Matrix multiplication with the last two axes of the operands optionally swapped
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The right hand side
+    /// * `transpose_lhs` - Whether the last two axes of `self` are swapped
+    /// * `transpose_rhs` - Whether the last two axes of `other` are swapped
+    ///
+    /// # Note
+    ///
+    /// The swapped operands are passed to `matmul` as views, so they are not copied.
+    /// A swapped operand must have at least 2 dimensions, otherwise `KDeZeroError::InvalidDimension` is returned.
+    pub fn matmul_transposed(&self, other: &VariableData, transpose_lhs: bool, transpose_rhs: bool) -> Result<VariableData> {
+        fn matmul_views<T: MatMulKernel>(x: &Tensor<T>, y: &Tensor<T>, transpose_lhs: bool, transpose_rhs: bool) -> Result<Tensor<T>> {
+            let swapped = |x: &Tensor<T>, transpose: bool| -> Result<TensorView<T>> {
+                if !transpose {
+                    return Ok(x.view())
+                }
+                if x.ndim() < 2 {
+                    return Err(KDeZeroError::InvalidDimension(2, x.ndim()).into())
+                }
+                x.swapaxes(x.ndim() - 2, x.ndim() - 1)
+            };
+            swapped(x, transpose_lhs)?.matmul(&swapped(y, transpose_rhs)?)
+        }
+        Ok(match (self, other) {
+            (VariableData::F32(x), VariableData::F32(y)) => matmul_views(x, y, transpose_lhs, transpose_rhs)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) => matmul_views(x, y, transpose_lhs, transpose_rhs)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) => matmul_views(x, y, transpose_lhs, transpose_rhs)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) => matmul_views(x, y, transpose_lhs, transpose_rhs)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) => matmul_views(x, y, transpose_lhs, transpose_rhs)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "matmul_transposed".to_string(),
+                format!("{:?}, {:?}", self.data_type(), other.data_type()),
+            ).into()),
+        })
+    }
+
     pub fn reshape(&self, shape: &[usize]) -> Result<VariableData> {
         Ok(match self {
             VariableData::F32(x) => x.reshape(shape)?.into(),
@@ -266,12 +407,12 @@ impl VariableData {
 
     pub fn transpose(&self) -> Result<VariableData> {
         Ok(match self {
-            VariableData::F32(x) => x.transpose().into(),
-            VariableData::F64(x) => x.transpose().into(),
-            VariableData::I32(x) => x.transpose().into(),
-            VariableData::I64(x) => x.transpose().into(),
-            VariableData::USIZE(x) => x.transpose().into(),
-            VariableData::Bool(x) => x.transpose().into(),
+            VariableData::F32(x) => x.transpose().contiguous().into(),
+            VariableData::F64(x) => x.transpose().contiguous().into(),
+            VariableData::I32(x) => x.transpose().contiguous().into(),
+            VariableData::I64(x) => x.transpose().contiguous().into(),
+            VariableData::USIZE(x) => x.transpose().contiguous().into(),
+            VariableData::Bool(x) => x.transpose().contiguous().into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "transpose".to_string(),
                 self.data_type().to_string(),
@@ -279,14 +420,208 @@ impl VariableData {
         })
     }
 
+    pub fn permute(&self, axes: &[usize]) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.permute(axes)?.contiguous().into(),
+            VariableData::F64(x) => x.permute(axes)?.contiguous().into(),
+            VariableData::I32(x) => x.permute(axes)?.contiguous().into(),
+            VariableData::I64(x) => x.permute(axes)?.contiguous().into(),
+            VariableData::USIZE(x) => x.permute(axes)?.contiguous().into(),
+            VariableData::Bool(x) => x.permute(axes)?.contiguous().into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "permute".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn swapaxes(&self, axis0: usize, axis1: usize) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            VariableData::F64(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            VariableData::I32(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            VariableData::I64(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            VariableData::USIZE(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            VariableData::Bool(x) => x.swapaxes(axis0, axis1)?.contiguous().into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "swapaxes".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn concat(xs: &[&VariableData], axis: usize) -> Result<VariableData> {
+        let first = xs.first()
+            .ok_or(KDeZeroError::Error("concat needs at least one data".to_string()))?;
+        Ok(match first {
+            VariableData::F32(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_f32_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            VariableData::F64(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_f64_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            VariableData::I32(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_i32_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            VariableData::I64(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_i64_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            VariableData::USIZE(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_usize_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            VariableData::Bool(_) => ktensor::Tensor::concat(
+                &xs.iter().map(|x| x.to_bool_tensor()).collect::<Result<Vec<_>>>()?, axis)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "concat".to_string(),
+                first.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn einsum(subscripts: &str, xs: &[&VariableData]) -> Result<VariableData> {
+        let first = xs.first()
+            .ok_or(KDeZeroError::Error("einsum needs at least one data".to_string()))?;
+        Ok(match first {
+            VariableData::F32(_) => Tensor::einsum(subscripts,
+                &xs.iter().map(|x| x.to_f32_tensor()).collect::<Result<Vec<_>>>()?)?.into(),
+            VariableData::F64(_) => Tensor::einsum(subscripts,
+                &xs.iter().map(|x| x.to_f64_tensor()).collect::<Result<Vec<_>>>()?)?.into(),
+            VariableData::I32(_) => Tensor::einsum(subscripts,
+                &xs.iter().map(|x| x.to_i32_tensor()).collect::<Result<Vec<_>>>()?)?.into(),
+            VariableData::I64(_) => Tensor::einsum(subscripts,
+                &xs.iter().map(|x| x.to_i64_tensor()).collect::<Result<Vec<_>>>()?)?.into(),
+            VariableData::USIZE(_) => Tensor::einsum(subscripts,
+                &xs.iter().map(|x| x.to_usize_tensor()).collect::<Result<Vec<_>>>()?)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "einsum".to_string(),
+                first.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn tensordot(&self, rhs: &VariableData, lhs_axes: &[usize], rhs_axes: &[usize]) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::F32(x), VariableData::F32(y)) =>
+                x.tensordot(y, (lhs_axes, rhs_axes))?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) =>
+                x.tensordot(y, (lhs_axes, rhs_axes))?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) =>
+                x.tensordot(y, (lhs_axes, rhs_axes))?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) =>
+                x.tensordot(y, (lhs_axes, rhs_axes))?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) =>
+                x.tensordot(y, (lhs_axes, rhs_axes))?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "tensordot".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn inv(&self) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => linalg::inv(x)?.into(),
+            VariableData::F64(x) => linalg::inv(x)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "inv".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn solve(&self, rhs: &VariableData) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::F32(a), VariableData::F32(b)) => linalg::solve(a, b)?.into(),
+            (VariableData::F64(a), VariableData::F64(b)) => linalg::solve(a, b)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "solve".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn det(&self) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => Tensor::scalar(linalg::det(x)?).into(),
+            VariableData::F64(x) => Tensor::scalar(linalg::det(x)?).into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "det".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn cholesky(&self) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => linalg::cholesky(x)?.into(),
+            VariableData::F64(x) => linalg::cholesky(x)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "cholesky".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn im2col(
+        &self,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+        pad_value: f64,
+    ) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.im2col(kernel, stride, pad, dilation, pad_value as f32)?.into(),
+            VariableData::F64(x) => x.im2col(kernel, stride, pad, dilation, pad_value)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "im2col".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn col2im(
+        &self,
+        image_shape: &[usize],
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.col2im(image_shape, kernel, stride, pad, dilation)?.into(),
+            VariableData::F64(x) => x.col2im(image_shape, kernel, stride, pad, dilation)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "col2im".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn split(&self, sizes: &[usize], axis: usize) -> Result<Vec<VariableData>> {
+        fn into_data<T>(xs: Vec<ktensor::Tensor<T>>) -> Vec<VariableData>
+        where
+            VariableData: From<ktensor::Tensor<T>>
+        {
+            xs.into_iter().map(|x| x.into()).collect()
+        }
+        Ok(match self {
+            VariableData::F32(x) => into_data(x.split(sizes, axis)?),
+            VariableData::F64(x) => into_data(x.split(sizes, axis)?),
+            VariableData::I32(x) => into_data(x.split(sizes, axis)?),
+            VariableData::I64(x) => into_data(x.split(sizes, axis)?),
+            VariableData::USIZE(x) => into_data(x.split(sizes, axis)?),
+            VariableData::Bool(x) => into_data(x.split(sizes, axis)?),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "split".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
     pub fn broadcast_to(&self, shape: &[usize]) -> Result<VariableData> {
         Ok(match self {
-            VariableData::F32(x) => x.broadcast_to(shape)?.into(),
-            VariableData::F64(x) => x.broadcast_to(shape)?.into(),
-            VariableData::I32(x) => x.broadcast_to(shape)?.into(),
-            VariableData::I64(x) => x.broadcast_to(shape)?.into(),
-            VariableData::USIZE(x) => x.broadcast_to(shape)?.into(),
-            VariableData::Bool(x) => x.broadcast_to(shape)?.into(),
+            VariableData::F32(x) => x.broadcast_to(shape)?.contiguous().into(),
+            VariableData::F64(x) => x.broadcast_to(shape)?.contiguous().into(),
+            VariableData::I32(x) => x.broadcast_to(shape)?.contiguous().into(),
+            VariableData::I64(x) => x.broadcast_to(shape)?.contiguous().into(),
+            VariableData::USIZE(x) => x.broadcast_to(shape)?.contiguous().into(),
+            VariableData::Bool(x) => x.broadcast_to(shape)?.contiguous().into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "broadcast_to".to_string(),
                 self.data_type().to_string(),
@@ -296,12 +631,12 @@ impl VariableData {
 
     pub fn slice_with_one_index(&self, index: usize) -> Result<VariableData> {
         Ok(match self {
-            VariableData::F32(x) => x.slice_with_one_index(index)?.into(),
-            VariableData::F64(x) => x.slice_with_one_index(index)?.into(),
-            VariableData::I32(x) => x.slice_with_one_index(index)?.into(),
-            VariableData::I64(x) => x.slice_with_one_index(index)?.into(),
-            VariableData::USIZE(x) => x.slice_with_one_index(index)?.into(),
-            VariableData::Bool(x) => x.slice_with_one_index(index)?.into(),
+            VariableData::F32(x) => x.slice_with_one_index(index)?.contiguous().into(),
+            VariableData::F64(x) => x.slice_with_one_index(index)?.contiguous().into(),
+            VariableData::I32(x) => x.slice_with_one_index(index)?.contiguous().into(),
+            VariableData::I64(x) => x.slice_with_one_index(index)?.contiguous().into(),
+            VariableData::USIZE(x) => x.slice_with_one_index(index)?.contiguous().into(),
+            VariableData::Bool(x) => x.slice_with_one_index(index)?.contiguous().into(),
             _ => return Err(KDeZeroError::NotImplementedType(
                 "slice_with_one_index".to_string(),
                 self.data_type().to_string(),
@@ -339,6 +674,21 @@ impl VariableData {
         })
     }
 
+    pub fn slice(&self, elems: &[SliceElem]) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.slice(elems)?.into(),
+            VariableData::F64(x) => x.slice(elems)?.into(),
+            VariableData::I32(x) => x.slice(elems)?.into(),
+            VariableData::I64(x) => x.slice(elems)?.into(),
+            VariableData::USIZE(x) => x.slice(elems)?.into(),
+            VariableData::Bool(x) => x.slice(elems)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "slice".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
     pub fn add_at_one_index(&self, rhs: &VariableData, index: usize) -> Result<VariableData> {
         Ok(match (self, rhs) {
             (VariableData::F32(x), VariableData::F32(y)) =>
@@ -396,6 +746,89 @@ impl VariableData {
         })
     }
 
+    pub fn add_at_slice(&self, rhs: &VariableData, elems: &[SliceElem]) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::F32(x), VariableData::F32(y)) =>
+                x.add_at_slice(y, elems)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) =>
+                x.add_at_slice(y, elems)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) =>
+                x.add_at_slice(y, elems)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) =>
+                x.add_at_slice(y, elems)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) =>
+                x.add_at_slice(y, elems)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "add_at_slice".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn gather(&self, axis: usize, index: &Tensor<usize>) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.gather(axis, index)?.into(),
+            VariableData::F64(x) => x.gather(axis, index)?.into(),
+            VariableData::I32(x) => x.gather(axis, index)?.into(),
+            VariableData::I64(x) => x.gather(axis, index)?.into(),
+            VariableData::USIZE(x) => x.gather(axis, index)?.into(),
+            VariableData::Bool(x) => x.gather(axis, index)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "gather".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn take_along_axis(&self, indices: &Tensor<usize>, axis: usize) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.take_along_axis(indices, axis)?.into(),
+            VariableData::F64(x) => x.take_along_axis(indices, axis)?.into(),
+            VariableData::I32(x) => x.take_along_axis(indices, axis)?.into(),
+            VariableData::I64(x) => x.take_along_axis(indices, axis)?.into(),
+            VariableData::USIZE(x) => x.take_along_axis(indices, axis)?.into(),
+            VariableData::Bool(x) => x.take_along_axis(indices, axis)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "take_along_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn index_select(&self, axis: usize, indices: &[usize]) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.index_select(axis, indices)?.into(),
+            VariableData::F64(x) => x.index_select(axis, indices)?.into(),
+            VariableData::I32(x) => x.index_select(axis, indices)?.into(),
+            VariableData::I64(x) => x.index_select(axis, indices)?.into(),
+            VariableData::USIZE(x) => x.index_select(axis, indices)?.into(),
+            VariableData::Bool(x) => x.index_select(axis, indices)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "index_select".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn scatter_add(&self, axis: usize, index: &Tensor<usize>, src: &VariableData) -> Result<VariableData> {
+        Ok(match (self, src) {
+            (VariableData::F32(x), VariableData::F32(y)) =>
+                x.scatter_add(axis, index, y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) =>
+                x.scatter_add(axis, index, y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) =>
+                x.scatter_add(axis, index, y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) =>
+                x.scatter_add(axis, index, y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) =>
+                x.scatter_add(axis, index, y)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "scatter_add".to_string(),
+                format!("{:?}, {:?}", self.data_type(), src.data_type()),
+            ).into()),
+        })
+    }
+
     pub fn max_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
         Ok(match self {
             VariableData::F32(x) => x.max_with_axis(axis, keepdims)?.into(),
@@ -410,6 +843,105 @@ impl VariableData {
         })
     }
 
+    pub fn min_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.min_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.min_with_axis(axis, keepdims)?.into(),
+            VariableData::I32(x) => x.min_with_axis(axis, keepdims)?.into(),
+            VariableData::I64(x) => x.min_with_axis(axis, keepdims)?.into(),
+            VariableData::USIZE(x) => x.min_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "min_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn argmax_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.argmax_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.argmax_with_axis(axis, keepdims)?.into(),
+            VariableData::I32(x) => x.argmax_with_axis(axis, keepdims)?.into(),
+            VariableData::I64(x) => x.argmax_with_axis(axis, keepdims)?.into(),
+            VariableData::USIZE(x) => x.argmax_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "argmax_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn argmin_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.argmin_with_axis(axis, keepdims)?.into(),
+            VariableData::F64(x) => x.argmin_with_axis(axis, keepdims)?.into(),
+            VariableData::I32(x) => x.argmin_with_axis(axis, keepdims)?.into(),
+            VariableData::I64(x) => x.argmin_with_axis(axis, keepdims)?.into(),
+            VariableData::USIZE(x) => x.argmin_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "argmin_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn sort(&self, axis: usize, descending: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.sort(axis, descending)?.into(),
+            VariableData::F64(x) => x.sort(axis, descending)?.into(),
+            VariableData::I32(x) => x.sort(axis, descending)?.into(),
+            VariableData::I64(x) => x.sort(axis, descending)?.into(),
+            VariableData::USIZE(x) => x.sort(axis, descending)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "sort".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn argsort(&self, axis: usize, descending: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::F32(x) => x.argsort(axis, descending)?.into(),
+            VariableData::F64(x) => x.argsort(axis, descending)?.into(),
+            VariableData::I32(x) => x.argsort(axis, descending)?.into(),
+            VariableData::I64(x) => x.argsort(axis, descending)?.into(),
+            VariableData::USIZE(x) => x.argsort(axis, descending)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "argsort".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn topk(&self, k: usize, axis: usize) -> Result<(VariableData, VariableData)> {
+        Ok(match self {
+            VariableData::F32(x) => {
+                let (values, indices) = x.topk(k, axis)?;
+                (values.into(), indices.into())
+            },
+            VariableData::F64(x) => {
+                let (values, indices) = x.topk(k, axis)?;
+                (values.into(), indices.into())
+            },
+            VariableData::I32(x) => {
+                let (values, indices) = x.topk(k, axis)?;
+                (values.into(), indices.into())
+            },
+            VariableData::I64(x) => {
+                let (values, indices) = x.topk(k, axis)?;
+                (values.into(), indices.into())
+            },
+            VariableData::USIZE(x) => {
+                let (values, indices) = x.topk(k, axis)?;
+                (values.into(), indices.into())
+            },
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "topk".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
     pub fn maximum(&self, rhs: &VariableData) -> Result<VariableData> {
         Ok(match (self, rhs) {
             (VariableData::F32(x), VariableData::F32(y)) =>
@@ -492,12 +1024,170 @@ impl VariableData {
             ).into()),
         })
     }
+
+    pub fn eq(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, eq)
+    }
+
+    pub fn ne(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, ne)
+    }
+
+    pub fn lt(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, lt)
+    }
+
+    pub fn le(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, le)
+    }
+
+    pub fn gt(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, gt)
+    }
+
+    pub fn ge(&self, rhs: &VariableData) -> Result<VariableData> {
+        compare_variable_data!(self, rhs, ge)
+    }
+
+    pub fn eq_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, eq_scalar)
+    }
+
+    pub fn ne_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, ne_scalar)
+    }
+
+    pub fn lt_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, lt_scalar)
+    }
+
+    pub fn le_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, le_scalar)
+    }
+
+    pub fn gt_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, gt_scalar)
+    }
+
+    pub fn ge_scalar(&self, value: f64) -> Result<VariableData> {
+        compare_scalar_variable_data!(self, value, ge_scalar)
+    }
+
+    pub fn logical_and(&self, rhs: &VariableData) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::Bool(x), VariableData::Bool(y)) =>
+                x.logical_and(y)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "logical_and".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn logical_or(&self, rhs: &VariableData) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::Bool(x), VariableData::Bool(y)) =>
+                x.logical_or(y)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "logical_or".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn logical_xor(&self, rhs: &VariableData) -> Result<VariableData> {
+        Ok(match (self, rhs) {
+            (VariableData::Bool(x), VariableData::Bool(y)) =>
+                x.logical_xor(y)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "logical_xor".to_string(),
+                format!("{:?}, {:?}", self.data_type(), rhs.data_type()),
+            ).into()),
+        })
+    }
+
+    pub fn logical_not(&self) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::Bool(x) => x.logical_not().into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "logical_not".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn any(&self) -> Result<bool> {
+        match self {
+            VariableData::Bool(x) => Ok(x.any()),
+            _ => Err(KDeZeroError::NotImplementedType(
+                "any".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        }
+    }
+
+    pub fn all(&self) -> Result<bool> {
+        match self {
+            VariableData::Bool(x) => Ok(x.all()),
+            _ => Err(KDeZeroError::NotImplementedType(
+                "all".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        }
+    }
+
+    pub fn any_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::Bool(x) => x.any_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "any_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn all_with_axis(&self, axis: usize, keepdims: bool) -> Result<VariableData> {
+        Ok(match self {
+            VariableData::Bool(x) => x.all_with_axis(axis, keepdims)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "all_with_axis".to_string(),
+                self.data_type().to_string(),
+            ).into()),
+        })
+    }
+
+    pub fn where_(condition: &VariableData, x: &VariableData, y: &VariableData) -> Result<VariableData> {
+        let condition_tensor = match condition {
+            VariableData::Bool(condition) => condition,
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "where_".to_string(),
+                condition.data_type().to_string(),
+            ).into()),
+        };
+        Ok(match (x, y) {
+            (VariableData::F32(x), VariableData::F32(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            (VariableData::F64(x), VariableData::F64(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            (VariableData::I32(x), VariableData::I32(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            (VariableData::I64(x), VariableData::I64(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            (VariableData::USIZE(x), VariableData::USIZE(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            (VariableData::Bool(x), VariableData::Bool(y)) =>
+                Tensor::where_(condition_tensor, x, y)?.into(),
+            _ => return Err(KDeZeroError::NotImplementedType(
+                "where_".to_string(),
+                format!("{:?}, {:?}", x.data_type(), y.data_type()),
+            ).into()),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ktensor::Tensor;
     use ktensor::error::TensorError;
 
     #[test]
@@ -660,6 +1350,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn clip_f32() -> Result<()> {
+        let x = VariableData::from(Tensor::new(vec![-2.0f32, 0.5, 3.0], vec![3])?);
+        let y = x.clip(-1.0, 1.0)?;
+        assert_eq!(y, VariableData::from(Tensor::new(vec![-1.0f32, 0.5, 1.0], vec![3])?));
+        Ok(())
+    }
+
+    #[test]
+    fn all_finite_f64() -> Result<()> {
+        assert!(VariableData::from(2.0f64).all_finite()?);
+        assert!(!VariableData::from(f64::NAN).all_finite()?);
+        assert!(VariableData::from(true).all_finite().is_err());
+        Ok(())
+    }
+
     #[test]
     fn error_scalar_mul_bool() -> Result<()> {
         let x = VariableData::from(true);
@@ -1018,11 +1724,72 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn slice_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 6, 3])?);
+        let y = x.slice(&ktensor::s![.., 1..5;2, -1])?;
+        assert_eq!(y, Tensor::new([5.0, 11.0, 23.0, 29.0], [2, 2])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn add_at_slice_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::zeros([2, 3]));
+        let y = VariableData::from(Tensor::<f64>::ones([2, 2]));
+        let z = x.add_at_slice(&y, &ktensor::s![.., [0, 0]])?;
+        assert_eq!(z, Tensor::new([2.0, 0.0, 0.0, 2.0, 0.0, 0.0], [2, 3])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn concat_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 1])?);
+        let y = VariableData::from(Tensor::<f64>::arrange([2, 2])?);
+        let z = VariableData::concat(&[&x, &y], 1)?;
+        assert_eq!(z, Tensor::new([0.0, 0.0, 1.0, 1.0, 2.0, 3.0], [2, 3])?.into());
+        Ok(())
+    }
+
+    #[test]
+    fn error_concat_mismatch_type() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 1])?);
+        let y = VariableData::from(Tensor::<f32>::arrange([2, 2])?);
+        assert!(VariableData::concat(&[&x, &y], 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn split_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([3, 2])?);
+        let ys = x.split(&[1, 2], 0)?;
+        assert_eq!(ys, vec![
+            Tensor::new([0.0, 1.0], [1, 2])?.into(),
+            Tensor::new([2.0, 3.0, 4.0, 5.0], [2, 2])?.into(),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn permute_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 3, 4])?);
+        let y = x.permute(&[1, 2, 0])?;
+        assert_eq!(y, Tensor::<f64>::arrange([2, 3, 4])?.permute([1, 2, 0])?.contiguous().into());
+        Ok(())
+    }
+
+    #[test]
+    fn swapaxes_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 3, 4])?);
+        let y = x.swapaxes(0, 2)?;
+        assert_eq!(y, Tensor::<f64>::arrange([2, 3, 4])?.swapaxes(0, 2)?.contiguous().into());
+        Ok(())
+    }
+
     #[test]
     fn broadcast_to_f32() -> Result<()> {
         let x = VariableData::from(Tensor::<f64>::arrange([3,])?);
         let y = x.broadcast_to(&[2, 3])?;
-        assert_eq!(y, Tensor::<f64>::arrange([3,])?.broadcast_to([2, 3])?.into());
+        assert_eq!(y, Tensor::<f64>::arrange([3,])?.broadcast_to([2, 3])?.contiguous().into());
         Ok(())
     }
 
@@ -1239,4 +2006,211 @@ mod tests {
         ], [2, 3])?.into());
         Ok(())
     }
+
+    #[test]
+    fn compare_f32() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1.0f32, 2.0, 3.0], [3])?);
+        let y = VariableData::from(2.0f32);
+        assert_eq!(x.eq(&y)?, VariableData::from(Tensor::new([false, true, false], [3])?));
+        assert_eq!(x.ne(&y)?, VariableData::from(Tensor::new([true, false, true], [3])?));
+        assert_eq!(x.lt(&y)?, VariableData::from(Tensor::new([true, false, false], [3])?));
+        assert_eq!(x.le(&y)?, VariableData::from(Tensor::new([true, true, false], [3])?));
+        assert_eq!(x.gt(&y)?, VariableData::from(Tensor::new([false, false, true], [3])?));
+        assert_eq!(x.ge(&y)?, VariableData::from(Tensor::new([false, true, true], [3])?));
+        Ok(())
+    }
+
+    #[test]
+    fn compare_scalar() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1.0f32, 2.0, 3.0], [3])?);
+        assert_eq!(x.eq_scalar(2.0)?, VariableData::from(Tensor::new([false, true, false], [3])?));
+        assert_eq!(x.ne_scalar(2.0)?, VariableData::from(Tensor::new([true, false, true], [3])?));
+        assert_eq!(x.lt_scalar(2.0)?, VariableData::from(Tensor::new([true, false, false], [3])?));
+        assert_eq!(x.le_scalar(2.0)?, VariableData::from(Tensor::new([true, true, false], [3])?));
+        let x = VariableData::from(Tensor::new([1i64, 2, 3], [3])?);
+        assert_eq!(x.gt_scalar(2.0)?, VariableData::from(Tensor::new([false, false, true], [3])?));
+        assert_eq!(x.ge_scalar(2.0)?, VariableData::from(Tensor::new([false, true, true], [3])?));
+        let x = VariableData::from(Tensor::new([true, false], [2])?);
+        match x.eq_scalar(1.0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "eq_scalar".to_string(),
+                    x.data_type().to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn compare_scalar_non_integral() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1i32, 2, 3], [3])?);
+        assert_eq!(x.ge_scalar(2.5)?, VariableData::from(Tensor::new([false, false, true], [3])?));
+        assert_eq!(x.eq_scalar(2.5)?, VariableData::from(Tensor::new([false, false, false], [3])?));
+        assert_eq!(x.lt_scalar(-0.5)?, VariableData::from(Tensor::new([false, false, false], [3])?));
+        let x = VariableData::from(Tensor::new([0usize, 1, 2], [3])?);
+        assert_eq!(x.gt_scalar(-0.5)?, VariableData::from(Tensor::new([true, true, true], [3])?));
+        assert_eq!(x.le_scalar(1.5)?, VariableData::from(Tensor::new([true, true, false], [3])?));
+        let x = VariableData::from(Tensor::new([-2i64, 0, 2], [3])?);
+        assert_eq!(x.gt_scalar(-1.5)?, VariableData::from(Tensor::new([false, true, true], [3])?));
+        assert_eq!(x.ne_scalar(-2.0)?, VariableData::from(Tensor::new([false, true, true], [3])?));
+        Ok(())
+    }
+
+    #[test]
+    fn error_compare_mismatch_type() -> Result<()> {
+        let x = VariableData::from(1.0f32);
+        let y = VariableData::from(1.0f64);
+        match x.lt(&y) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "lt".to_string(),
+                    format!("{:?}, {:?}", x.data_type(), y.data_type()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn logical_bool() -> Result<()> {
+        let x = VariableData::from(Tensor::new([true, true, false, false], [2, 2])?);
+        let y = VariableData::from(Tensor::new([true, false], [2])?);
+        assert_eq!(x.logical_and(&y)?, VariableData::from(Tensor::new([true, false, false, false], [2, 2])?));
+        assert_eq!(x.logical_or(&y)?, VariableData::from(Tensor::new([true, true, true, false], [2, 2])?));
+        assert_eq!(x.logical_xor(&y)?, VariableData::from(Tensor::new([false, true, true, false], [2, 2])?));
+        assert_eq!(x.logical_not()?, VariableData::from(Tensor::new([false, false, true, true], [2, 2])?));
+        assert!(x.any()?);
+        assert!(!x.all()?);
+        assert_eq!(x.all_with_axis(1, false)?, VariableData::from(Tensor::new([true, false], [2])?));
+        assert_eq!(x.any_with_axis(0, true)?, VariableData::from(Tensor::new([true, true], [1, 2])?));
+        Ok(())
+    }
+
+    #[test]
+    fn error_logical_f32() -> Result<()> {
+        let x = VariableData::from(1.0f32);
+        match x.logical_not() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "logical_not".to_string(),
+                    x.data_type().to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn where_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::new([-1.0, 2.0, -3.0], [3])?);
+        let zero = VariableData::from(0.0);
+        let condition = x.gt(&zero)?;
+        let y = VariableData::where_(&condition, &x, &zero)?;
+        assert_eq!(y, VariableData::from(Tensor::new([0.0, 2.0, 0.0], [3])?));
+        Ok(())
+    }
+
+    #[test]
+    fn reductions_with_axis_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1.0, 4.0, 3.0, 2.0], [2, 2])?);
+        assert_eq!(x.mean_with_axis(&[0], false)?, VariableData::from(Tensor::new([2.0, 3.0], [2])?));
+        assert_eq!(x.var_with_axis(&[1], true)?, VariableData::from(Tensor::new([2.25, 0.25], [2, 1])?));
+        assert_eq!(x.prod_with_axis(1, false)?, VariableData::from(Tensor::new([4.0, 6.0], [2])?));
+        assert_eq!(x.min_with_axis(0, false)?, VariableData::from(Tensor::new([1.0, 2.0], [2])?));
+        assert_eq!(x.argmin_with_axis(1, false)?, VariableData::from(Tensor::<usize>::new([0, 1], [2])?));
+        assert_eq!(x.argmax_with_axis(1, false)?, VariableData::from(Tensor::<usize>::new([1, 0], [2])?));
+        assert_eq!(x.logsumexp_with_axis(1, true)?.shape(), &[2, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn error_mean_with_axis_i32() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1, 2], [2])?);
+        match x.mean_with_axis(&[0], false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "mean_with_axis".to_string(),
+                    x.data_type().to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sort_topk_i32() -> Result<()> {
+        let x = VariableData::from(Tensor::new([3, 1, 2], [3])?);
+        assert_eq!(x.sort(0, false)?, VariableData::from(Tensor::new([1, 2, 3], [3])?));
+        assert_eq!(x.argsort(0, true)?, VariableData::from(Tensor::<usize>::new([0, 2, 1], [3])?));
+        let (values, indices) = x.topk(2, 0)?;
+        assert_eq!(values, VariableData::from(Tensor::new([3, 2], [2])?));
+        assert_eq!(indices, VariableData::from(Tensor::<usize>::new([0, 2], [2])?));
+        Ok(())
+    }
+
+    #[test]
+    fn gather_scatter_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1.0, 2.0, 3.0, 4.0], [2, 2])?);
+        let index = Tensor::new([1, 0], [2, 1])?;
+        let y = x.gather(1, &index)?;
+        assert_eq!(y, VariableData::from(Tensor::new([2.0, 3.0], [2, 1])?));
+        assert_eq!(x.take_along_axis(&index, 1)?, y);
+        assert_eq!(x.index_select(0, &[1, 1])?, VariableData::from(Tensor::new([3.0, 4.0, 3.0, 4.0], [2, 2])?));
+        let z = x.zeros_like()?.scatter_add(1, &index, &y)?;
+        assert_eq!(z, VariableData::from(Tensor::new([0.0, 2.0, 3.0, 0.0], [2, 2])?));
+        Ok(())
+    }
+
+    #[test]
+    fn error_scatter_add_mismatch_type() -> Result<()> {
+        let x = VariableData::from(Tensor::new([1.0, 2.0], [2])?);
+        let src = VariableData::from(Tensor::new([1.0f32], [1])?);
+        match x.scatter_add(0, &Tensor::new([0], [1])?, &src) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotImplementedType(
+                    "scatter_add".to_string(),
+                    format!("{:?}, {:?}", x.data_type(), src.data_type()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn einsum_tensordot_f64() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2, 3])?);
+        let y = VariableData::from(Tensor::<f64>::arrange([3, 2])?);
+        let z = VariableData::einsum("ij,jk->ik", &[&x, &y])?;
+        assert_eq!(z, x.matmul(&y)?);
+        assert_eq!(x.tensordot(&y, &[1], &[0])?, z);
+        Ok(())
+    }
+
+    #[test]
+    fn error_einsum_mismatch_type() -> Result<()> {
+        let x = VariableData::from(Tensor::<f64>::arrange([2])?);
+        let y = VariableData::from(Tensor::<f32>::arrange([2])?);
+        match VariableData::einsum("i,i", &[&x, &y]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<KDeZeroError>()?;
+                assert_eq!(e, KDeZeroError::NotCollectType(
+                    y.data_type().to_string(),
+                    "F64".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
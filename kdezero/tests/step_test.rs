@@ -1838,3 +1838,78 @@ fn step51() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn simple_cnn_mnist() -> Result<()> {
+    if std::env::var("LONG_TEST").is_err() {
+        println!("skip simple_cnn_mnist");
+        return Ok(());
+    }
+
+    use std::fs::create_dir;
+    use kdezero::{Model, Optimizer, VariableType};
+    use kdezero::function::softmax_cross_entropy;
+    use kdezero::model::SimpleCNN;
+    use kdezero::optimizer::Adam;
+    use kdezero::data_set::{sample::Mnist, DataLoader};
+    use kdezero::test_utility::accuracy;
+    use kdezero::no_grad;
+
+    match create_dir("output") {
+        Ok(_) => println!("Create data directory"),
+        Err(_) => {},
+    }
+    match create_dir("output/mnist") {
+        Ok(_) => println!("Create mnist directory"),
+        Err(_) => {},
+    }
+
+    let max_epoch = 3;
+    let batch_size = 100;
+
+    let layer = SimpleCNN::new(None, (28, 28), 10, VariableType::F32)?;
+    let model = Model::new(layer);
+    let mut optimizer = Optimizer::new(Adam::new(0.001, 0.9, 0.999, 1e-8));
+    optimizer.set_model(model);
+
+    let train_set = Mnist::new(
+        true,
+        Some("output/mnist"),
+        |x| Ok(x.reshape([1, 28, 28])? / 255.))?;
+    let mut train_loader = DataLoader::new(
+        Box::new(train_set), batch_size, true)?;
+    let test_set = Mnist::new(
+        false,
+        Some("output/mnist"),
+        |x| Ok(x.reshape([1, 28, 28])? / 255.))?;
+    let mut test_loader = DataLoader::new(
+        Box::new(test_set), batch_size, false)?;
+
+    let mut test_acc = 0.0;
+    for epoch in 0..max_epoch {
+        for r in train_loader.iter() {
+            let (x, t) = r?;
+            let model = optimizer.get_model_mut_result()?;
+            let t = t.unwrap();
+            let y = model.forward(&[x.into()])?.remove(0);
+            let mut loss = softmax_cross_entropy(&y, &t.into())?;
+            model.clear_grads();
+            loss.backward()?;
+            optimizer.update()?;
+        }
+
+        let mut sum_acc = 0.0;
+        for r in test_loader.iter() {
+            let (x, t) = r?;
+            let model = optimizer.get_model_mut_result()?;
+            let t = t.unwrap();
+            let _guard = no_grad();
+            let y = model.forward(&[x.into()])?.remove(0);
+            sum_acc += accuracy(y.data().to_f32_tensor()?, &t)? * t.len() as f64;
+        }
+        test_acc = sum_acc / test_loader.len() as f64;
+        println!("epoch {} test accuracy {:.10}", epoch, test_acc);
+    }
+    assert!(test_acc > 0.98);
+    Ok(())
+}
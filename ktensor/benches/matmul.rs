@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use num_traits::NumAssign;
+use ktensor::Tensor;
+
+/// The previous implementation of `Tensor::matmul` (naive `i-j-k` loop) used as the baseline
+fn naive_matmul<T: NumAssign + Clone>(lhs: &Tensor<T>, rhs: &Tensor<T>) -> Tensor<T> {
+    let (n, k) = (lhs.get_shape()[0], lhs.get_shape()[1]);
+    let m = rhs.get_shape()[1];
+    let lhs_data = lhs.get_data();
+    let rhs_data = rhs.get_data();
+    let mut data = vec![T::zero(); n * m];
+    for i in 0..n {
+        for j in 0..m {
+            for p in 0..k {
+                data[i * m + j] += lhs_data[i * k + p].clone() * rhs_data[p * m + j].clone();
+            }
+        }
+    }
+    Tensor::new(data, [n, m]).unwrap()
+}
+
+fn bench_matmul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matmul_f32");
+    for size in [32, 128, 256] {
+        let x = Tensor::<f32>::arrange([size, size]).unwrap();
+        let y = Tensor::<f32>::arrange([size, size]).unwrap();
+        group.bench_with_input(BenchmarkId::new("naive", size), &size, |b, _| {
+            b.iter(|| naive_matmul(black_box(&x), black_box(&y)))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked", size), &size, |b, _| {
+            b.iter(|| black_box(&x).matmul(black_box(&y)).unwrap())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("matmul_f64");
+    for size in [32, 128, 256] {
+        let x = Tensor::<f64>::arrange([size, size]).unwrap();
+        let y = Tensor::<f64>::arrange([size, size]).unwrap();
+        group.bench_with_input(BenchmarkId::new("naive", size), &size, |b, _| {
+            b.iter(|| naive_matmul(black_box(&x), black_box(&y)))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked", size), &size, |b, _| {
+            b.iter(|| black_box(&x).matmul(black_box(&y)).unwrap())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("matmul_batched_f32");
+    let x = Tensor::<f32>::arrange([16, 64, 64]).unwrap();
+    let y = Tensor::<f32>::arrange([16, 64, 64]).unwrap();
+    group.bench_function("blocked", |b| {
+        b.iter(|| black_box(&x).matmul(black_box(&y)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_matmul);
+criterion_main!(benches);
@@ -30,6 +30,16 @@ pub enum TensorError {
     EmptyTensorError(),
     #[error("NewRandomNormalError: Failed to create a normal distribution.")]
     NewRandomNormalError(),
+    #[error("NotSquareMatrixError: shape: {0:?}")]
+    NotSquareMatrixError(Vec<usize>),
+    #[error("SingularMatrixError: The matrix is singular.")]
+    SingularMatrixError(),
+    #[error("NotPositiveDefiniteError: The matrix is not positive definite.")]
+    NotPositiveDefiniteError(),
+    #[error("NpyFormatError: {0}")]
+    NpyFormatError(String),
+    #[error("DtypeMismatchError: dtype: {0}, expected: {1}")]
+    DtypeMismatchError(String, String),
 }
 
 #[cfg(test)]
@@ -272,4 +282,52 @@ mod tests {
             }
         }
     }
+
+    fn error_not_square_matrix() -> Result<()> {
+        Err(TensorError::NotSquareMatrixError(vec![2, 3]).into())
+    }
+
+    #[test]
+    fn tensor_error_not_square_matrix() -> Result<()> {
+        match error_not_square_matrix() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().context("downcast error")?;
+                assert_eq!(e.to_string(), "NotSquareMatrixError: shape: [2, 3]");
+                Ok(())
+            }
+        }
+    }
+
+    fn error_singular_matrix() -> Result<()> {
+        Err(TensorError::SingularMatrixError().into())
+    }
+
+    #[test]
+    fn tensor_error_singular_matrix() -> Result<()> {
+        match error_singular_matrix() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().context("downcast error")?;
+                assert_eq!(e.to_string(), "SingularMatrixError: The matrix is singular.");
+                Ok(())
+            }
+        }
+    }
+
+    fn error_dtype_mismatch() -> Result<()> {
+        Err(TensorError::DtypeMismatchError(">f4".to_string(), "<f4".to_string()).into())
+    }
+
+    #[test]
+    fn tensor_error_dtype_mismatch() -> Result<()> {
+        match error_dtype_mismatch() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().context("downcast error")?;
+                assert_eq!(e.to_string(), "DtypeMismatchError: dtype: >f4, expected: <f4");
+                Ok(())
+            }
+        }
+    }
 }
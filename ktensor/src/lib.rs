@@ -1,4 +1,6 @@
 pub mod error;
 pub mod tensor;
+pub mod linalg;
+pub mod npy;
 
-pub use tensor::Tensor;
+pub use tensor::{Tensor, TensorView, AsView, SliceElem, NewAxis, EinsumSubscripts};
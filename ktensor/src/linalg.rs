@@ -0,0 +1,691 @@
+//! Dense linear algebra for 2-D floating point tensors
+//!
+//! All the functions take matrices of shape `[rows, cols]`.
+//! Batched inputs are not supported.
+
+use anyhow::Result;
+use num_traits::{Float, NumAssign};
+use crate::Tensor;
+use crate::error::TensorError;
+
+/// The maximum number of sweeps of the Jacobi methods used by `eigh` and `svd`
+const MAX_SWEEPS: usize = 100;
+
+/// Calculate the inverse of the square matrix
+///
+/// # Arguments
+///
+/// * `a` - The square matrix
+///
+/// # Returns
+///
+/// * `Result<Tensor<T>>` - The inverse of the matrix
+///
+/// # Note
+///
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+/// If the matrix is not square, `TensorError::NotSquareMatrixError` is returned.
+/// If the matrix is singular, `TensorError::SingularMatrixError` is returned.
+pub fn inv<T>(a: &Tensor<T>) -> Result<Tensor<T>>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    solve(a, &Tensor::eye(n))
+}
+
+/// Solve the linear equation `a x = b`
+///
+/// # Arguments
+///
+/// * `a` - The square matrix of shape `[n, n]`
+/// * `b` - The right hand side of shape `[n]` or `[n, k]`
+///
+/// # Returns
+///
+/// * `Result<Tensor<T>>` - The solution `x` with the same shape as `b`
+///
+/// # Note
+///
+/// If `a` is not 2-D, `TensorError::DimensionError` is returned.
+/// If `a` is not square, `TensorError::NotSquareMatrixError` is returned.
+/// If the first dimension of `b` is not `n`, `TensorError::ShapeMismatchError` is returned.
+/// If `a` is singular, `TensorError::SingularMatrixError` is returned.
+pub fn solve<T>(a: &Tensor<T>, b: &Tensor<T>) -> Result<Tensor<T>>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    let b_shape = b.get_shape();
+    let k = match b_shape.len() {
+        1 => 1,
+        2 => b_shape[1],
+        ndim => return Err(TensorError::DimensionError(ndim, 2).into()),
+    };
+    if b_shape[0] != n {
+        let mut expected = b_shape.clone();
+        expected[0] = n;
+        return Err(TensorError::ShapeMismatchError(b_shape.clone(), expected).into())
+    }
+    let lu = Lu::new(a.get_data(), n);
+    if lu.is_singular(a.get_data()) {
+        return Err(TensorError::SingularMatrixError().into())
+    }
+    Tensor::new(lu.solve(b.get_data(), k), b_shape.clone())
+}
+
+/// Calculate the determinant of the square matrix
+///
+/// # Arguments
+///
+/// * `a` - The square matrix
+///
+/// # Returns
+///
+/// * `Result<T>` - The determinant
+///
+/// # Note
+///
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+/// If the matrix is not square, `TensorError::NotSquareMatrixError` is returned.
+/// The determinant of a `0 x 0` matrix is one.
+pub fn det<T>(a: &Tensor<T>) -> Result<T>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    let lu = Lu::new(a.get_data(), n);
+    Ok((0..n).fold(lu.sign, |acc, i| acc * lu.lu[i * n + i]))
+}
+
+/// Calculate the sign and the natural logarithm of the absolute value of the determinant
+///
+/// # Arguments
+///
+/// * `a` - The square matrix
+///
+/// # Returns
+///
+/// * `Result<(T, T)>` - The sign (`1`, `-1` or `0`) and the log of the absolute value of the determinant
+///
+/// # Note
+///
+/// This is more robust than `det` against overflow and underflow.
+/// If the matrix is singular, `(0, -inf)` is returned.
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+/// If the matrix is not square, `TensorError::NotSquareMatrixError` is returned.
+pub fn slogdet<T>(a: &Tensor<T>) -> Result<(T, T)>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    let lu = Lu::new(a.get_data(), n);
+    let mut sign = lu.sign;
+    let mut logdet = T::zero();
+    for i in 0..n {
+        let value = lu.lu[i * n + i];
+        if value == T::zero() {
+            return Ok((T::zero(), T::neg_infinity()))
+        }
+        sign *= value.signum();
+        logdet += value.abs().ln();
+    }
+    Ok((sign, logdet))
+}
+
+/// Calculate the Cholesky decomposition `a = l l^T` of the symmetric positive definite matrix
+///
+/// # Arguments
+///
+/// * `a` - The symmetric positive definite matrix
+///
+/// # Returns
+///
+/// * `Result<Tensor<T>>` - The lower triangular matrix `l`
+///
+/// # Note
+///
+/// Only the lower triangle of `a` is used.
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+/// If the matrix is not square, `TensorError::NotSquareMatrixError` is returned.
+/// If the matrix is not positive definite, `TensorError::NotPositiveDefiniteError` is returned.
+pub fn cholesky<T>(a: &Tensor<T>) -> Result<Tensor<T>>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    let a = a.get_data();
+    let mut l = vec![T::zero(); n * n];
+    for j in 0..n {
+        let mut diag = a[j * n + j];
+        for k in 0..j {
+            diag -= l[j * n + k] * l[j * n + k];
+        }
+        if diag <= T::zero() || diag.is_nan() {
+            return Err(TensorError::NotPositiveDefiniteError().into())
+        }
+        let diag = diag.sqrt();
+        l[j * n + j] = diag;
+        for i in j + 1..n {
+            let mut value = a[i * n + j];
+            for k in 0..j {
+                value -= l[i * n + k] * l[j * n + k];
+            }
+            l[i * n + j] = value / diag;
+        }
+    }
+    Tensor::new(l, [n, n])
+}
+
+/// Calculate the reduced QR decomposition `a = q r` with Householder reflections
+///
+/// # Arguments
+///
+/// * `a` - The matrix of shape `[m, n]`
+///
+/// # Returns
+///
+/// * `Result<(Tensor<T>, Tensor<T>)>` - `q` of shape `[m, k]` with orthonormal columns
+///   and the upper triangular `r` of shape `[k, n]`, where `k = min(m, n)`
+///
+/// # Note
+///
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+pub fn qr<T>(a: &Tensor<T>) -> Result<(Tensor<T>, Tensor<T>)>
+where
+    T: Float + NumAssign
+{
+    let (m, n) = matrix_size(a)?;
+    let k = m.min(n);
+    let mut r = a.get_data().clone();
+    let mut q = Tensor::<T>::eye(m).get_data().clone();
+    let two = T::one() + T::one();
+    for j in 0..k.min(m.saturating_sub(1)) {
+        let norm = (j..m).map(|i| r[i * n + j] * r[i * n + j])
+            .fold(T::zero(), |acc, x| acc + x)
+            .sqrt();
+        if norm == T::zero() {
+            continue
+        }
+        let alpha = if r[j * n + j] > T::zero() { -norm } else { norm };
+        let mut v = (j..m).map(|i| r[i * n + j]).collect::<Vec<_>>();
+        v[0] -= alpha;
+        let v_norm2 = v.iter().fold(T::zero(), |acc, &x| acc + x * x);
+        if v_norm2 == T::zero() {
+            continue
+        }
+        for c in j..n {
+            let dot = v.iter().enumerate()
+                .fold(T::zero(), |acc, (i, &x)| acc + x * r[(j + i) * n + c]);
+            let scale = two * dot / v_norm2;
+            for (i, &x) in v.iter().enumerate() {
+                r[(j + i) * n + c] -= scale * x;
+            }
+        }
+        for row in 0..m {
+            let dot = v.iter().enumerate()
+                .fold(T::zero(), |acc, (i, &x)| acc + x * q[row * m + j + i]);
+            let scale = two * dot / v_norm2;
+            for (i, &x) in v.iter().enumerate() {
+                q[row * m + j + i] -= scale * x;
+            }
+        }
+    }
+    let q = (0..m).flat_map(|i| q[i * m..i * m + k].to_vec()).collect::<Vec<_>>();
+    let r = (0..k)
+        .flat_map(|i| (0..n).map(|j| if j < i { T::zero() } else { r[i * n + j] }).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    Ok((Tensor::new(q, [m, k])?, Tensor::new(r, [k, n])?))
+}
+
+/// Calculate the eigenvalues and the eigenvectors of the symmetric matrix
+///
+/// # Arguments
+///
+/// * `a` - The symmetric matrix
+///
+/// # Returns
+///
+/// * `Result<(Tensor<T>, Tensor<T>)>` - The eigenvalues of shape `[n]` in ascending order
+///   and the matrix of shape `[n, n]` whose columns are the corresponding unit eigenvectors
+///
+/// # Note
+///
+/// The cyclic Jacobi method is used.
+/// Only the lower triangle of `a` is used.
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+/// If the matrix is not square, `TensorError::NotSquareMatrixError` is returned.
+pub fn eigh<T>(a: &Tensor<T>) -> Result<(Tensor<T>, Tensor<T>)>
+where
+    T: Float + NumAssign
+{
+    let n = square_size(a)?;
+    let data = a.get_data();
+    let mut a = (0..n * n)
+        .map(|index| {
+            let (i, j) = (index / n, index % n);
+            if i >= j { data[index] } else { data[j * n + i] }
+        })
+        .collect::<Vec<_>>();
+    let mut v = Tensor::<T>::eye(n).get_data().clone();
+    let total = a.iter().fold(T::zero(), |acc, &x| acc + x * x);
+    let two = T::one() + T::one();
+    for _ in 0..MAX_SWEEPS {
+        let off = (0..n * n)
+            .filter(|index| index / n != index % n)
+            .fold(T::zero(), |acc, index| acc + a[index] * a[index]);
+        if off <= T::epsilon() * T::epsilon() * total {
+            break
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq == T::zero() {
+                    continue
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (two * apq);
+                let t = T::one().copysign(theta) / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                rotate_columns(&mut a, n, n, p, q, c, s);
+                rotate_rows(&mut a, n, p, q, c, s);
+                rotate_columns(&mut v, n, n, p, q, c, s);
+            }
+        }
+    }
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| a[i * n + i].partial_cmp(&a[j * n + j]).unwrap_or(std::cmp::Ordering::Equal));
+    let w = order.iter().map(|&i| a[i * n + i]).collect::<Vec<_>>();
+    let v = (0..n * n).map(|index| v[index / n * n + order[index % n]]).collect::<Vec<_>>();
+    Ok((Tensor::new(w, [n])?, Tensor::new(v, [n, n])?))
+}
+
+/// Calculate the reduced singular value decomposition `a = u diag(s) vt`
+///
+/// # Arguments
+///
+/// * `a` - The matrix of shape `[m, n]`
+///
+/// # Returns
+///
+/// * `Result<(Tensor<T>, Tensor<T>, Tensor<T>)>` - `u` of shape `[m, k]`,
+///   the singular values `s` of shape `[k]` in descending order and `vt` of shape `[k, n]`,
+///   where `k = min(m, n)`
+///
+/// # Note
+///
+/// The one-sided Jacobi method is used.
+/// If the tensor is not 2-D, `TensorError::DimensionError` is returned.
+pub fn svd<T>(a: &Tensor<T>) -> Result<(Tensor<T>, Tensor<T>, Tensor<T>)>
+where
+    T: Float + NumAssign
+{
+    let (m, n) = matrix_size(a)?;
+    if m < n {
+        let (u, s, vt) = svd(&a.transpose().contiguous())?;
+        return Ok((vt.transpose().contiguous(), s, u.transpose().contiguous()))
+    }
+    let mut u = a.get_data().clone();
+    let mut v = Tensor::<T>::eye(n).get_data().clone();
+    let two = T::one() + T::one();
+    for _ in 0..MAX_SWEEPS {
+        let mut converged = true;
+        for i in 0..n {
+            for j in i + 1..n {
+                let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+                for k in 0..m {
+                    alpha += u[k * n + i] * u[k * n + i];
+                    beta += u[k * n + j] * u[k * n + j];
+                    gamma += u[k * n + i] * u[k * n + j];
+                }
+                if gamma == T::zero() || gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue
+                }
+                converged = false;
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = T::one().copysign(zeta) / (zeta.abs() + (zeta * zeta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                rotate_columns(&mut u, m, n, i, j, c, s);
+                rotate_columns(&mut v, n, n, i, j, c, s);
+            }
+        }
+        if converged {
+            break
+        }
+    }
+    let norms = (0..n)
+        .map(|j| (0..m).fold(T::zero(), |acc, k| acc + u[k * n + j] * u[k * n + j]).sqrt())
+        .collect::<Vec<_>>();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(std::cmp::Ordering::Equal));
+    let s = order.iter().map(|&j| norms[j]).collect::<Vec<_>>();
+    let tol = T::epsilon() * T::from(m).unwrap_or(T::one()) * s.first().copied().unwrap_or(T::zero());
+    let mut left = vec![T::zero(); m * n];
+    let mut filled = vec![false; n];
+    for (col, &j) in order.iter().enumerate() {
+        if s[col] > tol {
+            for k in 0..m {
+                left[k * n + col] = u[k * n + j] / s[col];
+            }
+            filled[col] = true;
+        }
+    }
+    complete_orthonormal_columns(&mut left, m, n, &filled);
+    let vt = (0..n * n).map(|index| v[index % n * n + order[index / n]]).collect::<Vec<_>>();
+    Ok((Tensor::new(left, [m, n])?, Tensor::new(s, [n])?, Tensor::new(vt, [n, n])?))
+}
+
+/// LU decomposition with partial pivoting `p a = l u`
+///
+/// # Fields
+///
+/// * `lu` - `l` below the diagonal (with the unit diagonal omitted) and `u` on and above the diagonal
+/// * `perm` - The row of `a` moved to each row
+/// * `sign` - The sign of the permutation
+/// * `n` - The size of the matrix
+struct Lu<T> {
+    lu: Vec<T>,
+    perm: Vec<usize>,
+    sign: T,
+    n: usize,
+}
+
+impl<T> Lu<T>
+where
+    T: Float + NumAssign
+{
+    fn new(a: &[T], n: usize) -> Self {
+        let mut lu = a.to_vec();
+        let mut perm = (0..n).collect::<Vec<_>>();
+        let mut sign = T::one();
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| lu[i * n + k].abs().partial_cmp(&lu[j * n + k].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal))
+                .unwrap_or(k);
+            if pivot != k {
+                for j in 0..n {
+                    lu.swap(k * n + j, pivot * n + j);
+                }
+                perm.swap(k, pivot);
+                sign = -sign;
+            }
+            let diag = lu[k * n + k];
+            if diag == T::zero() {
+                continue
+            }
+            for i in k + 1..n {
+                let factor = lu[i * n + k] / diag;
+                lu[i * n + k] = factor;
+                for j in k + 1..n {
+                    let value = lu[k * n + j];
+                    lu[i * n + j] -= factor * value;
+                }
+            }
+        }
+        Self { lu, perm, sign, n }
+    }
+
+    /// Check if a pivot is negligible compared with the entries of the original matrix
+    fn is_singular(&self, a: &[T]) -> bool {
+        let n = self.n;
+        let scale = a.iter().fold(T::zero(), |acc, x| acc.max(x.abs()));
+        let tol = T::epsilon() * T::from(n).unwrap_or(T::one()) * scale;
+        (0..n).any(|i| self.lu[i * n + i].abs() <= tol || self.lu[i * n + i].is_nan())
+    }
+
+    /// Solve `a x = b` for `b` of shape `[n, k]`
+    fn solve(&self, b: &[T], k: usize) -> Vec<T> {
+        let n = self.n;
+        let lu = &self.lu;
+        let mut x = self.perm.iter()
+            .flat_map(|&i| b[i * k..(i + 1) * k].to_vec())
+            .collect::<Vec<_>>();
+        for c in 0..k {
+            for i in 0..n {
+                for j in 0..i {
+                    let value = x[j * k + c];
+                    x[i * k + c] -= lu[i * n + j] * value;
+                }
+            }
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    let value = x[j * k + c];
+                    x[i * k + c] -= lu[i * n + j] * value;
+                }
+                x[i * k + c] /= lu[i * n + i];
+            }
+        }
+        x
+    }
+}
+
+/// Get the shape of the 2-D tensor
+fn matrix_size<T>(a: &Tensor<T>) -> Result<(usize, usize)> {
+    let shape = a.get_shape();
+    if shape.len() != 2 {
+        return Err(TensorError::DimensionError(shape.len(), 2).into())
+    }
+    Ok((shape[0], shape[1]))
+}
+
+/// Get the size of the square matrix
+fn square_size<T>(a: &Tensor<T>) -> Result<usize> {
+    let (m, n) = matrix_size(a)?;
+    if m != n {
+        return Err(TensorError::NotSquareMatrixError(a.get_shape().clone()).into())
+    }
+    Ok(m)
+}
+
+/// Apply the Givens rotation to the columns `p` and `q` of the `[rows, cols]` matrix
+fn rotate_columns<T: Float>(a: &mut [T], rows: usize, cols: usize, p: usize, q: usize, c: T, s: T) {
+    for k in 0..rows {
+        let x = a[k * cols + p];
+        let y = a[k * cols + q];
+        a[k * cols + p] = c * x - s * y;
+        a[k * cols + q] = s * x + c * y;
+    }
+}
+
+/// Apply the Givens rotation to the rows `p` and `q` of the `[n, n]` matrix
+fn rotate_rows<T: Float>(a: &mut [T], n: usize, p: usize, q: usize, c: T, s: T) {
+    for k in 0..n {
+        let x = a[p * n + k];
+        let y = a[q * n + k];
+        a[p * n + k] = c * x - s * y;
+        a[q * n + k] = s * x + c * y;
+    }
+}
+
+/// Replace the columns not `filled` with unit vectors orthogonal to all the other columns
+fn complete_orthonormal_columns<T: Float>(a: &mut [T], rows: usize, cols: usize, filled: &[bool]) {
+    let mut filled = filled.to_vec();
+    for col in 0..cols {
+        if filled[col] {
+            continue
+        }
+        let mut best = (T::zero(), vec![T::zero(); rows]);
+        for e in 0..rows {
+            let mut w = vec![T::zero(); rows];
+            w[e] = T::one();
+            for other in (0..cols).filter(|&other| filled[other]) {
+                let dot = (0..rows).fold(T::zero(), |acc, k| acc + a[k * cols + other] * w[k]);
+                for (k, value) in w.iter_mut().enumerate() {
+                    *value = *value - dot * a[k * cols + other];
+                }
+            }
+            let norm = w.iter().fold(T::zero(), |acc, &x| acc + x * x).sqrt();
+            if norm > best.0 {
+                best = (norm, w);
+            }
+        }
+        if best.0 > T::zero() {
+            for k in 0..rows {
+                a[k * cols + col] = best.1[k] / best.0;
+            }
+        }
+        filled[col] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(x: &Tensor<f64>, y: &Tensor<f64>) {
+        assert_eq!(x.get_shape(), y.get_shape());
+        for (a, b) in x.get_data().iter().zip(y.get_data().iter()) {
+            assert!((a - b).abs() < 1e-9, "{:?} != {:?}", x, y);
+        }
+    }
+
+    fn assert_error(result: Result<Tensor<f64>>, expected: TensorError) {
+        match result {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn inv_normal() {
+        let a = Tensor::new([4.0, 7.0, 2.0, 6.0], [2, 2]).unwrap();
+        let b = inv(&a).unwrap();
+        assert_close(&b, &Tensor::new([0.6, -0.7, -0.2, 0.4], [2, 2]).unwrap());
+        assert_close(&a.matmul(&b).unwrap(), &Tensor::eye(2));
+    }
+
+    #[test]
+    fn inv_pivot() {
+        let a = Tensor::new([0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0, 8.0], [3, 3]).unwrap();
+        let b = inv(&a).unwrap();
+        assert_close(&a.matmul(&b).unwrap(), &Tensor::eye(3));
+    }
+
+    #[test]
+    fn inv_f32() {
+        let a = Tensor::<f32>::new([2.0, 0.0, 0.0, 4.0], [2, 2]).unwrap();
+        assert_eq!(inv(&a).unwrap(), Tensor::new([0.5, 0.0, 0.0, 0.25], [2, 2]).unwrap());
+    }
+
+    #[test]
+    fn inv_error_singular() {
+        let a = Tensor::new([1.0, 2.0, 2.0, 4.0], [2, 2]).unwrap();
+        assert_error(inv(&a), TensorError::SingularMatrixError());
+    }
+
+    #[test]
+    fn inv_error_not_square() {
+        let a = Tensor::<f64>::arrange([2, 3]).unwrap();
+        assert_error(inv(&a), TensorError::NotSquareMatrixError(vec![2, 3]));
+    }
+
+    #[test]
+    fn inv_error_dimension() {
+        let a = Tensor::<f64>::arrange([4]).unwrap();
+        assert_error(inv(&a), TensorError::DimensionError(1, 2));
+    }
+
+    #[test]
+    fn solve_normal() {
+        let a = Tensor::new([3.0, 1.0, 1.0, 2.0], [2, 2]).unwrap();
+        let b = Tensor::new([9.0, 8.0], [2]).unwrap();
+        assert_close(&solve(&a, &b).unwrap(), &Tensor::new([2.0, 3.0], [2]).unwrap());
+        let b = Tensor::new([9.0, 1.0, 8.0, 2.0], [2, 2]).unwrap();
+        assert_close(&solve(&a, &b).unwrap(), &Tensor::new([2.0, 0.0, 3.0, 1.0], [2, 2]).unwrap());
+    }
+
+    #[test]
+    fn solve_error_shape() {
+        let a = Tensor::<f64>::eye(2);
+        let b = Tensor::<f64>::arrange([3, 1]).unwrap();
+        assert_error(solve(&a, &b), TensorError::ShapeMismatchError(vec![3, 1], vec![2, 1]));
+    }
+
+    #[test]
+    fn det_normal() {
+        let a = Tensor::new([0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0, 8.0], [3, 3]).unwrap();
+        assert!((det(&a).unwrap() + 2.0).abs() < 1e-12);
+        let (sign, logdet) = slogdet(&a).unwrap();
+        assert_eq!(sign, -1.0);
+        assert!((logdet - 2.0f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn det_singular() {
+        let a = Tensor::new([1.0, 2.0, 2.0, 4.0], [2, 2]).unwrap();
+        assert_eq!(det(&a).unwrap(), 0.0);
+        assert_eq!(slogdet(&a).unwrap(), (0.0, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn cholesky_normal() {
+        let a = Tensor::new([4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0], [3, 3]).unwrap();
+        let l = cholesky(&a).unwrap();
+        assert_close(&l, &Tensor::new([2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0], [3, 3]).unwrap());
+        assert_close(&l.matmul(&l.transpose()).unwrap(), &a);
+    }
+
+    #[test]
+    fn cholesky_error_not_positive_definite() {
+        let a = Tensor::new([1.0, 2.0, 2.0, 1.0], [2, 2]).unwrap();
+        assert_error(cholesky(&a), TensorError::NotPositiveDefiniteError());
+    }
+
+    #[test]
+    fn qr_normal() {
+        let a = Tensor::new([12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0, 1.0, 2.0, 3.0], [4, 3]).unwrap();
+        let (q, r) = qr(&a).unwrap();
+        assert_eq!(q.get_shape(), &vec![4, 3]);
+        assert_eq!(r.get_shape(), &vec![3, 3]);
+        assert_close(&q.matmul(&r).unwrap(), &a);
+        assert_close(&q.transpose().matmul(&q).unwrap(), &Tensor::eye(3));
+        assert!(r.get_data()[3] == 0.0 && r.get_data()[6] == 0.0 && r.get_data()[7] == 0.0);
+    }
+
+    #[test]
+    fn qr_wide() {
+        let a = Tensor::new([1.0, 2.0, 3.0, 4.0, 5.0, 7.0], [2, 3]).unwrap();
+        let (q, r) = qr(&a).unwrap();
+        assert_eq!(q.get_shape(), &vec![2, 2]);
+        assert_eq!(r.get_shape(), &vec![2, 3]);
+        assert_close(&q.matmul(&r).unwrap(), &a);
+    }
+
+    #[test]
+    fn eigh_normal() {
+        let a = Tensor::new([2.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 5.0], [3, 3]).unwrap();
+        let (w, v) = eigh(&a).unwrap();
+        assert_close(&w, &Tensor::new([1.0, 3.0, 5.0], [3]).unwrap());
+        let wv = &v * &w;
+        assert_close(&a.matmul(&v).unwrap(), &wv);
+        assert_close(&v.transpose().matmul(&v).unwrap(), &Tensor::eye(3));
+    }
+
+    #[test]
+    fn svd_normal() {
+        let a = Tensor::new([3.0, 2.0, 2.0, 2.0, 3.0, -2.0], [2, 3]).unwrap();
+        let (u, s, vt) = svd(&a).unwrap();
+        assert_eq!(u.get_shape(), &vec![2, 2]);
+        assert_eq!(vt.get_shape(), &vec![2, 3]);
+        assert_close(&s, &Tensor::new([5.0, 3.0], [2]).unwrap());
+        let us = &u * &s;
+        assert_close(&us.matmul(&vt).unwrap(), &a);
+        assert_close(&vt.matmul(&vt.transpose()).unwrap(), &Tensor::eye(2));
+    }
+
+    #[test]
+    fn svd_rank_deficient() {
+        let a = Tensor::new([1.0, 2.0, 2.0, 4.0, 3.0, 6.0], [3, 2]).unwrap();
+        let (u, s, vt) = svd(&a).unwrap();
+        assert!((s.get_data()[0] - 70.0f64.sqrt()).abs() < 1e-9);
+        assert!(s.get_data()[1].abs() < 1e-9);
+        assert_close(&u.transpose().matmul(&u).unwrap(), &Tensor::eye(2));
+        let us = &u * &s;
+        assert_close(&us.matmul(&vt).unwrap(), &a);
+    }
+}
@@ -0,0 +1,524 @@
+//! Reading and writing NumPy `.npy` files and `.npz` archives
+//!
+//! Only little-endian data is supported, which is what NumPy writes on common platforms.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write, BufReader, BufWriter};
+use std::path::Path;
+use anyhow::Result;
+use crate::Tensor;
+use crate::error::TensorError;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Element type which can be stored in a `.npy` file
+pub trait NpyElement: Clone {
+    /// The NumPy dtype string like `<f4`
+    const DESCR: &'static str;
+    /// The number of bytes of an element
+    const SIZE: usize;
+
+    /// Decode an element from `SIZE` little-endian bytes
+    fn from_le_slice(bytes: &[u8]) -> Self;
+
+    /// Append the little-endian bytes of the element
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+macro_rules! impl_npy_element {
+    ($($type:ty => $descr:expr),*) => {
+        $(
+            impl NpyElement for $type {
+                const DESCR: &'static str = $descr;
+                const SIZE: usize = std::mem::size_of::<$type>();
+
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    let mut buffer = [0u8; std::mem::size_of::<$type>()];
+                    buffer.copy_from_slice(bytes);
+                    <$type>::from_le_bytes(buffer)
+                }
+
+                fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_npy_element!(f32 => "<f4", f64 => "<f8", i32 => "<i4", i64 => "<i8");
+
+impl NpyElement for usize {
+    const DESCR: &'static str = "<u8";
+    const SIZE: usize = 8;
+
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        u64::from_le_slice(bytes) as usize
+    }
+
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(*self as u64).to_le_bytes());
+    }
+}
+
+impl NpyElement for u64 {
+    const DESCR: &'static str = "<u8";
+    const SIZE: usize = 8;
+
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut buffer = [0u8; 8];
+        buffer.copy_from_slice(bytes);
+        u64::from_le_bytes(buffer)
+    }
+
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl NpyElement for bool {
+    const DESCR: &'static str = "|b1";
+    const SIZE: usize = 1;
+
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+}
+
+/// Header of a `.npy` file
+///
+/// # Fields
+///
+/// * `descr` - The NumPy dtype string like `<f4`
+/// * `fortran_order` - Whether the data is stored in column-major order
+/// * `shape` - The shape of the array
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpyHeader {
+    pub descr: String,
+    pub fortran_order: bool,
+    pub shape: Vec<usize>,
+}
+
+impl NpyHeader {
+    /// Parse the header at the beginning of the `.npy` bytes
+    ///
+    /// The native byte order `=` in the dtype is replaced with the actual one.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The content of the `.npy` file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Self, usize)>` - The header and the offset of the data
+    ///
+    /// # Note
+    ///
+    /// If the bytes are not in the `.npy` format, `TensorError::NpyFormatError` is returned.
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize)> {
+        if bytes.len() < 10 || &bytes[..6] != MAGIC {
+            return Err(TensorError::NpyFormatError("invalid magic string".to_string()).into())
+        }
+        let (header_len, start) = match bytes[6] {
+            1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+            2 | 3 if bytes.len() >= 12 =>
+                (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12),
+            version => return Err(TensorError::NpyFormatError(
+                format!("unsupported version: {}", version)
+            ).into()),
+        };
+        let end = start + header_len;
+        if bytes.len() < end {
+            return Err(TensorError::NpyFormatError("header is truncated".to_string()).into())
+        }
+        let header = std::str::from_utf8(&bytes[start..end])
+            .map_err(|_| TensorError::NpyFormatError("header is not utf-8".to_string()))?;
+        let descr = native_descr(header_value(header, "descr")?.trim_matches(|c| c == '\'' || c == '"'));
+        let fortran_order = match header_value(header, "fortran_order")? {
+            "True" => true,
+            "False" => false,
+            value => return Err(TensorError::NpyFormatError(
+                format!("invalid fortran_order: {}", value)
+            ).into()),
+        };
+        let shape = header_value(header, "shape")?
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .map(|dim| dim.trim())
+            .filter(|dim| !dim.is_empty())
+            .map(|dim| dim.trim_end_matches('L').parse::<usize>()
+                .map_err(|_| TensorError::NpyFormatError(format!("invalid shape: {}", header)).into()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((Self { descr, fortran_order, shape }, end))
+    }
+
+    /// Encode the header with the magic string and the padding
+    fn to_bytes(&self) -> Vec<u8> {
+        let shape = match self.shape.len() {
+            1 => format!("({},)", self.shape[0]),
+            _ => format!("({})", self.shape.iter().map(|dim| dim.to_string()).collect::<Vec<_>>().join(", ")),
+        };
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            self.descr,
+            if self.fortran_order { "True" } else { "False" },
+            shape,
+        );
+        let (version, prefix_len) = if header.len() + 11 > u16::MAX as usize { (2, 12) } else { (1, 10) };
+        let padding = (64 - (prefix_len + header.len() + 1) % 64) % 64;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        } else {
+            bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        }
+        bytes.extend_from_slice(header.as_bytes());
+        bytes
+    }
+}
+
+/// Find the value of the key in the header dictionary
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str> {
+    let position = [format!("'{}'", key), format!("\"{}\"", key)].iter()
+        .find_map(|quoted| header.find(quoted.as_str()).map(|position| position + quoted.len()))
+        .ok_or_else(|| TensorError::NpyFormatError(format!("{} is not found in header: {}", key, header)))?;
+    let rest = header[position..].trim_start()
+        .strip_prefix(':')
+        .ok_or_else(|| TensorError::NpyFormatError(format!("invalid header: {}", header)))?
+        .trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|end| end + 1)
+    } else {
+        rest.find([',', '}'])
+    }.ok_or_else(|| TensorError::NpyFormatError(format!("invalid header: {}", header)))?;
+    Ok(rest[..end].trim())
+}
+
+impl<T> Tensor<T>
+where
+    T: NpyElement
+{
+    /// Decode the tensor from the content of a `.npy` file
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The content of the `.npy` file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The decoded tensor
+    ///
+    /// # Note
+    ///
+    /// If the dtype of the file is not `T::DESCR`, `TensorError::DtypeMismatchError` is returned.
+    /// This includes big-endian data like `>f4`.
+    /// If the bytes are not in the `.npy` format, `TensorError::NpyFormatError` is returned.
+    pub fn from_npy_bytes(bytes: &[u8]) -> Result<Self> {
+        let (header, offset) = NpyHeader::parse(bytes)?;
+        if header.descr != T::DESCR {
+            return Err(TensorError::DtypeMismatchError(header.descr, T::DESCR.to_string()).into())
+        }
+        let size = header.shape.iter().product::<usize>();
+        let data = &bytes[offset..];
+        if data.len() != size * T::SIZE {
+            return Err(TensorError::NpyFormatError(
+                format!("data size {} does not match the shape {:?}", data.len(), header.shape)
+            ).into())
+        }
+        let data = data.chunks_exact(T::SIZE).map(T::from_le_slice).collect::<Vec<_>>();
+        if header.fortran_order {
+            let shape = header.shape.iter().rev().copied().collect::<Vec<_>>();
+            let axes = (0..shape.len()).rev().collect::<Vec<_>>();
+            return Ok(Self::new(data, shape)?.permute(axes)?.contiguous())
+        }
+        Self::new(data, header.shape)
+    }
+
+    /// Encode the tensor into the content of a `.npy` file
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The content of the `.npy` file in C order
+    pub fn to_npy_bytes(&self) -> Vec<u8> {
+        let header = NpyHeader {
+            descr: T::DESCR.to_string(),
+            fortran_order: false,
+            shape: self.get_shape().clone(),
+        };
+        let mut bytes = header.to_bytes();
+        bytes.reserve(self.size() * T::SIZE);
+        for value in self.get_data().iter() {
+            value.extend_le_bytes(&mut bytes);
+        }
+        bytes
+    }
+
+    /// Read the tensor from a `.npy` file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The tensor in the file
+    ///
+    /// # Note
+    ///
+    /// See `from_npy_bytes` for the errors about the content.
+    pub fn read_npy<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut bytes = Vec::new();
+        BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+        Self::from_npy_bytes(&bytes)
+    }
+
+    /// Write the tensor to a `.npy` file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file
+    pub fn write_npy<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&self.to_npy_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read the named tensors from a `.npz` archive
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the archive
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<String, Self>>` - The tensors keyed by the names without the `.npy` extension
+    ///
+    /// # Note
+    ///
+    /// All the arrays in the archive must have the dtype `T::DESCR`.
+    /// Use `read_npz_entries` to read arrays of different dtypes.
+    pub fn read_npz<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Self>> {
+        read_npz_entries(path)?
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, Self::from_npy_bytes(&bytes)?)))
+            .collect()
+    }
+
+    /// Write the named tensors to an uncompressed `.npz` archive like `numpy.savez`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the archive
+    /// * `tensors` - The pairs of the name and the tensor
+    pub fn write_npz<P: AsRef<Path>>(path: P, tensors: &[(&str, &Self)]) -> Result<()> {
+        let entries = tensors.iter()
+            .map(|(name, tensor)| (name.to_string(), tensor.to_npy_bytes()))
+            .collect::<Vec<_>>();
+        write_npz_entries(path, &entries)
+    }
+}
+
+/// Replace the native byte order `=` with `<` or `>`
+fn native_descr(descr: &str) -> String {
+    match descr.strip_prefix('=') {
+        Some(rest) if cfg!(target_endian = "little") => format!("<{}", rest),
+        Some(rest) => format!(">{}", rest),
+        None => descr.to_string(),
+    }
+}
+
+/// Read the raw `.npy` contents from a `.npz` archive
+///
+/// # Arguments
+///
+/// * `path` - The path of the archive
+///
+/// # Returns
+///
+/// * `Result<Vec<(String, Vec<u8>)>>` - The pairs of the name without the `.npy` extension and the `.npy` content
+///
+/// # Note
+///
+/// Both stored and deflated (`numpy.savez_compressed`) archives are supported.
+pub fn read_npz_entries<P: AsRef<Path>>(path: P) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(File::open(path)?))?;
+    (0..archive.len())
+        .map(|i| {
+            let mut file = archive.by_index(i)?;
+            let name = file.name().strip_suffix(".npy").unwrap_or(file.name()).to_string();
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            Ok((name, bytes))
+        })
+        .collect()
+}
+
+/// Write the raw `.npy` contents to an uncompressed `.npz` archive
+///
+/// # Arguments
+///
+/// * `path` - The path of the archive
+/// * `entries` - The pairs of the name and the `.npy` content
+///
+/// # Note
+///
+/// The `.npy` extension is appended to each name.
+pub fn write_npz_entries<P: AsRef<Path>>(path: P, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let mut archive = zip::ZipWriter::new(BufWriter::new(File::create(path)?));
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored);
+    for (name, bytes) in entries {
+        archive.start_file(format!("{}.npy", name), options)?;
+        archive.write_all(bytes)?;
+    }
+    archive.finish()?.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ktensor_npy_{}_{}", std::process::id(), name))
+    }
+
+    /// `np.save` of `np.arange(6, dtype='<f4').reshape(2, 3)`
+    fn numpy_f32_bytes() -> Vec<u8> {
+        let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }";
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        let padded = format!("{:<117}\n", header);
+        bytes.extend_from_slice(&(padded.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(padded.as_bytes());
+        for i in 0..6 {
+            bytes.extend_from_slice(&(i as f32).to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn header_parse() {
+        let (header, offset) = NpyHeader::parse(&numpy_f32_bytes()).unwrap();
+        assert_eq!(header, NpyHeader {
+            descr: "<f4".to_string(),
+            fortran_order: false,
+            shape: vec![2, 3],
+        });
+        assert_eq!(offset, 128);
+    }
+
+    #[test]
+    fn header_to_bytes() {
+        for shape in [vec![], vec![3], vec![2, 3, 4]] {
+            let header = NpyHeader { descr: "<i8".to_string(), fortran_order: false, shape };
+            let bytes = header.to_bytes();
+            assert_eq!(bytes.len() % 64, 0);
+            assert_eq!(NpyHeader::parse(&bytes).unwrap(), (header, bytes.len()));
+        }
+    }
+
+    #[test]
+    fn from_npy_bytes_numpy() {
+        let x = Tensor::<f32>::from_npy_bytes(&numpy_f32_bytes()).unwrap();
+        assert_eq!(x, Tensor::<f32>::arrange([2, 3]).unwrap());
+    }
+
+    #[test]
+    fn from_npy_bytes_fortran_order() {
+        let header = NpyHeader { descr: "<i4".to_string(), fortran_order: true, shape: vec![2, 3] };
+        let mut bytes = header.to_bytes();
+        for value in [0, 3, 1, 4, 2, 5] {
+            value.extend_le_bytes(&mut bytes);
+        }
+        let x = Tensor::<i32>::from_npy_bytes(&bytes).unwrap();
+        assert_eq!(x, Tensor::<i32>::arrange([2, 3]).unwrap());
+    }
+
+    #[test]
+    fn npy_bytes_round_trip() {
+        let x = Tensor::new([1.5, -2.0, 3.25], [3]).unwrap();
+        assert_eq!(Tensor::<f64>::from_npy_bytes(&x.to_npy_bytes()).unwrap(), x);
+        let x = Tensor::<usize>::arrange([2, 2]).unwrap();
+        assert_eq!(Tensor::<usize>::from_npy_bytes(&x.to_npy_bytes()).unwrap(), x);
+        let x = Tensor::new([true, false], [1, 2]).unwrap();
+        assert_eq!(Tensor::<bool>::from_npy_bytes(&x.to_npy_bytes()).unwrap(), x);
+        let x = Tensor::scalar(7i64);
+        assert_eq!(Tensor::<i64>::from_npy_bytes(&x.to_npy_bytes()).unwrap(), x);
+    }
+
+    #[test]
+    fn from_npy_bytes_error_dtype() {
+        match Tensor::<f64>::from_npy_bytes(&numpy_f32_bytes()) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DtypeMismatchError("<f4".to_string(), "<f8".to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn from_npy_bytes_error_big_endian() {
+        let header = NpyHeader { descr: ">f4".to_string(), fortran_order: false, shape: vec![1] };
+        let mut bytes = header.to_bytes();
+        bytes.extend_from_slice(&1.0f32.to_be_bytes());
+        match Tensor::<f32>::from_npy_bytes(&bytes) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DtypeMismatchError(">f4".to_string(), "<f4".to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn from_npy_bytes_error_size() {
+        let mut bytes = numpy_f32_bytes();
+        bytes.pop();
+        match Tensor::<f32>::from_npy_bytes(&bytes) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::NpyFormatError(
+                    "data size 23 does not match the shape [2, 3]".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn npy_file_round_trip() {
+        let path = temp_path("round_trip.npy");
+        let x = Tensor::<f32>::arrange([3, 2]).unwrap();
+        x.write_npy(&path).unwrap();
+        let y = Tensor::<f32>::read_npy(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn npz_file_round_trip() {
+        let path = temp_path("round_trip.npz");
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let y = Tensor::vector(vec![1.0, 2.0]);
+        Tensor::write_npz(&path, &[("x", &x), ("y", &y)]).unwrap();
+        let tensors = Tensor::<f64>::read_npz(&path).unwrap();
+        let entries = read_npz_entries(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(tensors.len(), 2);
+        assert_eq!(tensors["x"], x);
+        assert_eq!(tensors["y"], y);
+        assert_eq!(entries.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), vec!["x", "y"]);
+    }
+}
@@ -12,11 +12,26 @@ mod convert;
 pub mod iter;
 mod display;
 mod slice;
+mod slice_elem;
+mod join;
+mod logical;
+mod sort;
+mod gather;
+mod einsum;
 mod ops_at;
 mod compare;
+mod view;
+mod view_math;
+mod conv;
 
+use std::sync::Arc;
 use anyhow::Result;
-pub use random::TensorRng;
+pub use random::{TensorRng, TensorRngState};
+pub use matmul::{MatMulKernel, StridedMatrix};
+pub use slice_elem::{SliceElem, SliceRange, NewAxis};
+pub use einsum::EinsumSubscripts;
+pub use conv::conv_output_size;
+pub use view::AsView;
 
 /// Tensor
 /// 
@@ -24,13 +39,40 @@ pub use random::TensorRng;
 /// 
 /// * `data` - The data of the tensor
 /// * `shape` - The shape of the tensor
+/// 
+/// # Note
+/// 
+/// The data is shared between clones and views of the tensor,
+/// and is copied only when a shared tensor is modified.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tensor<T>
 {
-    data: Vec<T>,
+    data: Arc<Vec<T>>,
     shape: Vec<usize>,
 }
 
+/// Strided view of a tensor
+/// 
+/// # Fields
+/// 
+/// * `data` - The data shared with the viewed tensor
+/// * `shape` - The shape of the view
+/// * `strides` - The step in `data` when the index of each dimension increases by one
+/// * `offset` - The position in `data` of the first element of the view
+/// 
+/// # Note
+/// 
+/// Creating a view and reshaping, transposing, broadcasting or slicing it does not copy the data.
+/// Use `contiguous` to materialize the view as a `Tensor`.
+#[derive(Debug, Clone)]
+pub struct TensorView<T>
+{
+    data: Arc<Vec<T>>,
+    shape: Vec<usize>,
+    strides: Vec<isize>,
+    offset: usize,
+}
+
 impl<T> Tensor<T>
 {
     /// Create a new Tensor
@@ -51,7 +93,7 @@ impl<T> Tensor<T>
         let data = data.into();
         let shape = shape.into();
         Self::check_shape(&data, &shape)?;
-        Ok(Self { data, shape })
+        Ok(Self { data: Arc::new(data), shape })
     }
 
     /// Get the data of the tensor
@@ -65,6 +107,33 @@ impl<T> Tensor<T>
     }
 }
 
+impl<T> Tensor<T>
+where
+    T: Clone
+{
+    /// Take the data out of the tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Vec<T>` - The data of the tensor
+    /// 
+    /// # Note
+    /// 
+    /// The data is copied only if it is shared with another tensor or view
+    pub fn into_data(self) -> Vec<T> {
+        Arc::try_unwrap(self.data).unwrap_or_else(|data| (*data).clone())
+    }
+
+    /// Get the mutable data of the tensor
+    /// 
+    /// # Note
+    /// 
+    /// If the data is shared with another tensor or view, it is copied before being returned
+    pub(crate) fn data_mut(&mut self) -> &mut Vec<T> {
+        Arc::make_mut(&mut self.data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,4 +174,11 @@ mod tests {
         assert_eq!(x.get_data(), &vec![0.0, 1.0, 2.0]);
         assert_eq!(x.get_shape(), &vec![3]);
     }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_sync<U: Send + Sync>() {}
+        assert_send_sync::<Tensor<f64>>();
+        assert_send_sync::<TensorView<f64>>();
+    }
 }
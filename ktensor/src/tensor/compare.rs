@@ -192,6 +192,62 @@ where
             Ok(min)
         }
     }
+
+    /// Get the minimum value of the tensor with the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `keepdims` - Whether to keep the dimension
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The minimum value
+    /// 
+    /// # Note
+    /// 
+    /// If the tensor is empty, `EmptyTensorError` is returned
+    /// If axis is out of range, `DimensionLargerError` is returned
+    pub fn min_with_axis(&self, axis: usize, keepdims: bool) -> Result<Self> {
+        if self.data.is_empty() {
+            return Err(TensorError::EmptyTensorError().into())
+        }
+        self.map_axis(axis, keepdims, |lane| {
+            lane.fold(None, |min: Option<&T>, x| match min {
+                Some(min) if min <= x => Some(min),
+                _ => Some(x),
+            }).unwrap().clone()
+        })
+    }
+
+    /// Get the minimum value index of the tensor with the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `keepdims` - Whether to keep the dimension
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Tensor<usize>>` - The minimum value index
+    /// 
+    /// # Note
+    /// 
+    /// If there are multiple minimum values, the first index is returned
+    /// If the tensor is empty, `EmptyTensorError` is returned
+    /// If axis is out of range, `DimensionLargerError` is returned
+    pub fn argmin_with_axis(&self, axis: usize, keepdims: bool) -> Result<Tensor<usize>> {
+        if self.data.is_empty() {
+            return Err(TensorError::EmptyTensorError().into())
+        }
+        self.map_axis(axis, keepdims, |lane| {
+            lane.enumerate()
+                .fold(None, |min: Option<(usize, &T)>, (i, x)| match min {
+                    Some((j, min)) if min <= x => Some((j, min)),
+                    _ => Some((i, x)),
+                }).unwrap().0
+        })
+    }
 }
 
 impl<T> Tensor<T>
@@ -220,6 +276,55 @@ where
     }
 }
 
+macro_rules! def_compare_ops {
+    ($(($method:ident, $scalar_method:ident, $op:tt, $name:literal)),*) => {
+        impl<T> Tensor<T>
+        where
+            T: PartialOrd,
+        {
+            $(
+                #[doc = concat!("Compare whether each element is ", $name, " the element of the other tensor")]
+                ///
+                /// # Arguments
+                ///
+                /// * `rhs` - The tensor to compare
+                ///
+                /// # Returns
+                ///
+                /// * `Result<Tensor<bool>>` - The result of the comparison of the broadcast shape
+                ///
+                /// # Note
+                ///
+                /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+                pub fn $method(&self, rhs: &Self) -> Result<Tensor<bool>> {
+                    self.broadcast_zip_with(rhs, |x, y| x $op y)
+                }
+
+                #[doc = concat!("Compare whether each element is ", $name, " the scalar")]
+                ///
+                /// # Arguments
+                ///
+                /// * `rhs` - The scalar to compare
+                pub fn $scalar_method(&self, rhs: &T) -> Tensor<bool> {
+                    Tensor::new(
+                        self.data.iter().map(|x| x $op rhs).collect::<Vec<_>>(),
+                        self.shape.clone(),
+                    ).unwrap()
+                }
+            )*
+        }
+    };
+}
+
+def_compare_ops!(
+    (eq, eq_scalar, ==, "equal to"),
+    (ne, ne_scalar, !=, "not equal to"),
+    (lt, lt_scalar, <, "less than"),
+    (le, le_scalar, <=, "less than or equal to"),
+    (gt, gt_scalar, >, "greater than"),
+    (ge, ge_scalar, >=, "greater than or equal to")
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,4 +514,64 @@ mod tests {
         assert_eq!(y.get_data(), &vec![0.0, 0.0, 1.0, 1.0]);
         assert_eq!(y.get_shape(), &vec![2, 2]);
     }
+
+    #[test]
+    fn compare_broadcast() {
+        let x = Tensor::new([0.0, 1.0, 2.0, 3.0], [2, 2]).unwrap();
+        let y = Tensor::new([1.0, 3.0], [2]).unwrap();
+        assert_eq!(x.eq(&y).unwrap().get_data(), &vec![false, false, false, true]);
+        assert_eq!(x.ne(&y).unwrap().get_data(), &vec![true, true, true, false]);
+        assert_eq!(x.lt(&y).unwrap().get_data(), &vec![true, true, false, false]);
+        assert_eq!(x.le(&y).unwrap().get_data(), &vec![true, true, false, true]);
+        assert_eq!(x.gt(&y).unwrap().get_data(), &vec![false, false, true, false]);
+        assert_eq!(x.ge(&y).unwrap().get_data(), &vec![false, false, true, true]);
+        assert_eq!(x.ge(&y).unwrap().get_shape(), &vec![2, 2]);
+    }
+
+    #[test]
+    fn compare_scalar() {
+        let x = Tensor::new([0, 1, 2], [3]).unwrap();
+        assert_eq!(x.eq_scalar(&1).get_data(), &vec![false, true, false]);
+        assert_eq!(x.gt_scalar(&0).get_data(), &vec![false, true, true]);
+        assert_eq!(x.le_scalar(&1).get_shape(), &vec![3]);
+    }
+
+    #[test]
+    fn compare_error_mismatch() {
+        let x = Tensor::new([0, 1, 2], [3]).unwrap();
+        let y = Tensor::new([0, 1], [2]).unwrap();
+        match x.lt(&y) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![3], vec![2]));
+            }
+        }
+    }
+
+    #[test]
+    fn min_with_axis_normal() {
+        let x = Tensor::new([3, 1, 2, 0, 5, 0], [2, 3]).unwrap();
+        assert_eq!(x.min_with_axis(0, false).unwrap(), Tensor::new([0, 1, 0], [3]).unwrap());
+        assert_eq!(x.min_with_axis(1, true).unwrap(), Tensor::new([1, 0], [2, 1]).unwrap());
+    }
+
+    #[test]
+    fn argmin_with_axis_normal() {
+        let x = Tensor::new([3, 1, 2, 0, 5, 0], [2, 3]).unwrap();
+        assert_eq!(x.argmin_with_axis(0, false).unwrap(), Tensor::new([1, 0, 1], [3]).unwrap());
+        assert_eq!(x.argmin_with_axis(1, true).unwrap(), Tensor::new([1, 0], [2, 1]).unwrap());
+    }
+
+    #[test]
+    fn min_with_axis_error_axis() {
+        let x = Tensor::new([3, 1, 2, 0, 5, 0], [2, 3]).unwrap();
+        match x.argmin_with_axis(2, false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(2, 1));
+            }
+        }
+    }
 }
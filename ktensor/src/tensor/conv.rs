@@ -0,0 +1,279 @@
+use std::sync::Arc;
+use anyhow::Result;
+use num_traits::NumAssign;
+use super::Tensor;
+use crate::error::TensorError;
+
+/// Calculate the output size of a convolution along one axis
+///
+/// # Arguments
+///
+/// * `input_size` - The size of the input
+/// * `kernel_size` - The size of the kernel
+/// * `stride` - The stride
+/// * `pad` - The padding added to both sides
+/// * `dilation` - The spacing between the kernel elements
+///
+/// # Note
+///
+/// If `kernel_size`, `stride` or `dilation` is 0, or the dilated kernel is larger than the padded input,
+/// `TensorError::InvalidArgumentError` is returned
+pub fn conv_output_size(input_size: usize, kernel_size: usize, stride: usize, pad: usize, dilation: usize) -> Result<usize> {
+    if kernel_size == 0 || stride == 0 || dilation == 0 {
+        return Err(TensorError::InvalidArgumentError(
+            format!("kernel_size, stride and dilation must be positive: {}, {}, {}", kernel_size, stride, dilation)
+        ).into())
+    }
+    let extent = dilation * (kernel_size - 1) + 1;
+    let padded = input_size + 2 * pad;
+    if padded < extent {
+        return Err(TensorError::InvalidArgumentError(
+            format!("the kernel extent {} is larger than the padded input {}", extent, padded)
+        ).into())
+    }
+    Ok((padded - extent) / stride + 1)
+}
+
+/// The sizes of an image and its columns
+struct ColGeometry {
+    batch: usize,
+    channels: usize,
+    height: usize,
+    width: usize,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    pad: (usize, usize),
+    dilation: (usize, usize),
+    out_height: usize,
+    out_width: usize,
+}
+
+impl ColGeometry {
+    fn new(
+        shape: &[usize],
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Result<Self> {
+        if shape.len() != 4 {
+            return Err(TensorError::DimensionError(shape.len(), 4).into())
+        }
+        Ok(Self {
+            batch: shape[0],
+            channels: shape[1],
+            height: shape[2],
+            width: shape[3],
+            kernel,
+            stride,
+            pad,
+            dilation,
+            out_height: conv_output_size(shape[2], kernel.0, stride.0, pad.0, dilation.0)?,
+            out_width: conv_output_size(shape[3], kernel.1, stride.1, pad.1, dilation.1)?,
+        })
+    }
+
+    fn col_shape(&self) -> Vec<usize> {
+        vec![
+            self.batch,
+            self.channels * self.kernel.0 * self.kernel.1,
+            self.out_height * self.out_width,
+        ]
+    }
+
+    /// Call `f(image index, column index)` for every column element inside the image,
+    /// and `f(None, column index)` for the ones in the padding
+    fn for_each(&self, mut f: impl FnMut(Option<usize>, usize)) {
+        let mut col_index = 0;
+        for n in 0..self.batch {
+            for c in 0..self.channels {
+                let image_offset = (n * self.channels + c) * self.height * self.width;
+                for kh in 0..self.kernel.0 {
+                    for kw in 0..self.kernel.1 {
+                        for oh in 0..self.out_height {
+                            let h = (oh * self.stride.0 + kh * self.dilation.0) as isize - self.pad.0 as isize;
+                            for ow in 0..self.out_width {
+                                let w = (ow * self.stride.1 + kw * self.dilation.1) as isize - self.pad.1 as isize;
+                                let inside = h >= 0 && (h as usize) < self.height && w >= 0 && (w as usize) < self.width;
+                                let image_index = if inside {
+                                    Some(image_offset + h as usize * self.width + w as usize)
+                                } else {
+                                    None
+                                };
+                                f(image_index, col_index);
+                                col_index += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: Copy + NumAssign
+{
+    /// Extract the patches of an image of shape `[N, C, H, W]` as columns
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel` - The height and the width of the kernel
+    /// * `stride` - The strides along the height and the width
+    /// * `pad` - The padding of the height and the width
+    /// * `dilation` - The dilation along the height and the width
+    /// * `pad_value` - The value used for the padding
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The columns of shape `[N, C * KH * KW, OH * OW]`,
+    ///   where the second axis is ordered by the channel, the kernel row and the kernel column
+    ///
+    /// # Note
+    ///
+    /// If `self` is not 4-D, `TensorError::DimensionError` is returned
+    pub fn im2col(
+        &self,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+        pad_value: T,
+    ) -> Result<Self> {
+        let geometry = ColGeometry::new(&self.shape, kernel, stride, pad, dilation)?;
+        let shape = geometry.col_shape();
+        let mut data = Vec::with_capacity(shape.iter().product());
+        geometry.for_each(|image_index, _| {
+            data.push(match image_index {
+                Some(i) => self.data[i],
+                None => pad_value,
+            });
+        });
+        Ok(Self { data: Arc::new(data), shape })
+    }
+
+    /// Sum the columns back into an image, the adjoint of `im2col`
+    ///
+    /// # Arguments
+    ///
+    /// * `image_shape` - The shape `[N, C, H, W]` of the image
+    /// * `kernel` - The height and the width of the kernel
+    /// * `stride` - The strides along the height and the width
+    /// * `pad` - The padding of the height and the width
+    /// * `dilation` - The dilation along the height and the width
+    ///
+    /// # Note
+    ///
+    /// If `image_shape` is not 4-D, `TensorError::DimensionError` is returned
+    /// If the shape of `self` is not the one `im2col` returns, `TensorError::ShapeMismatchError` is returned
+    pub fn col2im(
+        &self,
+        image_shape: &[usize],
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        pad: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Result<Self> {
+        let geometry = ColGeometry::new(image_shape, kernel, stride, pad, dilation)?;
+        let col_shape = geometry.col_shape();
+        if self.shape != col_shape {
+            return Err(TensorError::ShapeMismatchError(self.shape.clone(), col_shape).into())
+        }
+        let mut data = vec![T::zero(); image_shape.iter().product()];
+        geometry.for_each(|image_index, col_index| {
+            if let Some(i) = image_index {
+                data[i] += self.data[col_index];
+            }
+        });
+        Ok(Self { data: Arc::new(data), shape: image_shape.to_vec() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conv_output_size_normal() -> Result<()> {
+        assert_eq!(conv_output_size(5, 3, 1, 0, 1)?, 3);
+        assert_eq!(conv_output_size(5, 3, 2, 1, 1)?, 3);
+        assert_eq!(conv_output_size(7, 3, 1, 0, 2)?, 3);
+        assert!(conv_output_size(2, 3, 1, 0, 1).is_err());
+        assert!(conv_output_size(5, 3, 0, 0, 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn im2col_normal() -> Result<()> {
+        let x = Tensor::<f64>::arrange([1, 1, 3, 3])?;
+        let col = x.im2col((2, 2), (1, 1), (0, 0), (1, 1), 0.0)?;
+        assert_eq!(col, Tensor::new(vec![
+            0.0, 1.0, 3.0, 4.0,
+            1.0, 2.0, 4.0, 5.0,
+            3.0, 4.0, 6.0, 7.0,
+            4.0, 5.0, 7.0, 8.0,
+        ], vec![1, 4, 4])?);
+        Ok(())
+    }
+
+    #[test]
+    fn im2col_pad_stride_dilation() -> Result<()> {
+        let x = Tensor::<f64>::arrange([1, 1, 3, 3])?;
+        let col = x.im2col((2, 2), (2, 2), (1, 1), (1, 1), -1.0)?;
+        assert_eq!(col, Tensor::new(vec![
+            -1.0, -1.0, -1.0, 4.0,
+            -1.0, -1.0, 3.0, 5.0,
+            -1.0, 1.0, -1.0, 7.0,
+            0.0, 2.0, 6.0, 8.0,
+        ], vec![1, 4, 4])?);
+        let col = x.im2col((2, 2), (1, 1), (0, 0), (2, 2), 0.0)?;
+        assert_eq!(col, Tensor::new(vec![0.0, 2.0, 6.0, 8.0], vec![1, 4, 1])?);
+        Ok(())
+    }
+
+    #[test]
+    fn col2im_normal() -> Result<()> {
+        let col = Tensor::<f64>::ones([1, 4, 4]);
+        let x = col.col2im(&[1, 1, 3, 3], (2, 2), (1, 1), (0, 0), (1, 1))?;
+        assert_eq!(x, Tensor::new(vec![
+            1.0, 2.0, 1.0,
+            2.0, 4.0, 2.0,
+            1.0, 2.0, 1.0,
+        ], vec![1, 1, 3, 3])?);
+        Ok(())
+    }
+
+    #[test]
+    fn col2im_is_adjoint_of_im2col() -> Result<()> {
+        let x = Tensor::<f64>::arrange([2, 3, 5, 4])?;
+        let col = x.im2col((3, 2), (2, 1), (1, 1), (1, 2), 0.0)?;
+        let y = Tensor::<f64>::arrange(col.get_shape().clone())?;
+        let image = y.col2im(&[2, 3, 5, 4], (3, 2), (2, 1), (1, 1), (1, 2))?;
+        let lhs = (&col * &y).get_data().iter().sum::<f64>();
+        let rhs = (&x * &image).get_data().iter().sum::<f64>();
+        assert_eq!(lhs, rhs);
+        Ok(())
+    }
+
+    #[test]
+    fn error_im2col() -> Result<()> {
+        let x = Tensor::<f64>::arrange([3, 3])?;
+        match x.im2col((2, 2), (1, 1), (0, 0), (1, 1), 0.0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::DimensionError(2, 4));
+            }
+        }
+        let col = Tensor::<f64>::ones([1, 4, 3]);
+        match col.col2im(&[1, 1, 3, 3], (2, 2), (1, 1), (0, 0), (1, 1)) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>()?;
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![1, 4, 3], vec![1, 4, 4]));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use anyhow::Result;
 use num_traits::NumCast;
 use super::Tensor;
@@ -39,7 +40,7 @@ where
     /// If the tensor is not vector, `TensorError::NotVectorError` is returned
     pub fn to_vector(&self) -> Result<Vec<T>> {
         if self.is_vector() {
-            Ok(self.data.clone())
+            Ok(self.data.to_vec())
         } else {
             Err(
                 TensorError::NotVectorError(
@@ -52,19 +53,20 @@ where
 
 impl<T> Tensor<T>
 where
-    T: NumCast,
+    T: NumCast + Clone,
 {
     /// Convert to tensor of the specified type
     pub fn as_type<U: NumCast>(self) -> Result<Tensor<U>> {
-        let data: Result<Vec<_>, _> = self.data.into_iter()
+        let shape = self.shape.clone();
+        let data: Result<Vec<_>, _> = self.into_data().into_iter()
             .map(|x| NumCast::from(x)
                 .ok_or_else(|| TensorError::CastError(
                     std::any::type_name::<U>().to_string())))
             .collect();
         let data = data?;
         Ok(Tensor {
-            data,
-            shape: self.shape.clone(),
+            data: Arc::new(data),
+            shape,
         })
     }
 }
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use anyhow::Result;
 use num_traits::{NumCast, Zero, One, Float};
 use super::Tensor;
@@ -38,7 +39,7 @@ where
         }
         let mut shape = vec![len];
         shape.extend_from_slice(one_shape);
-        Ok(Self { data, shape })
+        Ok(Self { data: Arc::new(data), shape })
     }
 }
 
@@ -64,7 +65,7 @@ where
         let size: usize = shape.iter().product();
         if size == 0 {
             return Ok(Self {
-                data: vec![],
+                data: Arc::new(vec![]),
                 shape,
             })
         }
@@ -74,7 +75,7 @@ where
                     std::any::type_name::<T>().to_string())))
             .collect();
         let data = data?;
-        Ok(Self { data, shape })
+        Ok(Self { data: Arc::new(data), shape })
     }
 }
 
@@ -96,7 +97,7 @@ where
     pub fn linspace(start: T, end: T, num: usize) -> Self {
         if num == 0 {
             return Self {
-                data: vec![],
+                data: Arc::new(vec![]),
                 shape: vec![0],
             }
         }
@@ -106,7 +107,7 @@ where
             data.push(start + step * T::from(i).unwrap());
         }
         Self {
-            data,
+            data: Arc::new(data),
             shape: vec![num],
         }
     }
@@ -125,7 +126,7 @@ where
         let shape = shape.into();
         let size: usize = shape.iter().product();
         Self {
-            data: vec![T::zero(); size],
+            data: Arc::new(vec![T::zero(); size]),
             shape,
         }
     }
@@ -153,7 +154,7 @@ where
         let shape = shape.into();
         let size: usize = shape.iter().product();
         Self {
-            data: vec![T::one(); size],
+            data: Arc::new(vec![T::one(); size]),
             shape,
         }
     }
@@ -182,7 +183,7 @@ where
         let shape = shape.into();
         let size: usize = shape.iter().product();
         Self {
-            data: vec![value; size],
+            data: Arc::new(vec![value; size]),
             shape,
         }
     }
@@ -206,7 +207,7 @@ impl<T> Tensor<T> {
     /// * `value` - The value to be used for the tensor
     pub fn scalar(value: T) -> Self {
         Self {
-            data: vec![value],
+            data: Arc::new(vec![value]),
             shape: vec![],
         }
     }
@@ -219,7 +220,7 @@ impl<T> Tensor<T> {
     pub fn vector(vector: Vec<T>) -> Self {
         let len = vector.len();
         Self {
-            data: vector,
+            data: Arc::new(vector),
             shape: vec![len],
         }
     }
@@ -240,7 +241,7 @@ where
             data[i * n + i] = T::one();
         }
         Self {
-            data,
+            data: Arc::new(data),
             shape: vec![n, n],
         }
     }
@@ -20,7 +20,12 @@ impl<T> Tensor<T>
         let index = self.calc_data_index(indexes)?;
         Ok(&self.data[index])
     }
+}
 
+impl<T> Tensor<T>
+where
+    T: Clone
+{
     /// Get the mutable value of the tensor at the specified index
     /// 
     /// # Arguments
@@ -36,7 +41,7 @@ impl<T> Tensor<T>
     /// If the indexes are not correct, `TensorError::IndexError` is returned
     pub fn at_mut<U: AsRef<[usize]>>(&mut self, indexes: U) -> Result<&mut T> {
         let index = self.calc_data_index(indexes)?;
-        Ok(&mut self.data[index])
+        Ok(&mut self.data_mut()[index])
     }
 }
 
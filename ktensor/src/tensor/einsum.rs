@@ -0,0 +1,368 @@
+use std::sync::Arc;
+use anyhow::Result;
+use num_traits::NumAssign;
+use super::{Tensor, MatMulKernel};
+use crate::error::TensorError;
+
+/// The parsed subscripts of `Tensor::einsum`
+///
+/// # Fields
+///
+/// * `inputs` - The subscripts of each operand
+/// * `output` - The subscripts of the output
+#[derive(Debug, Clone, PartialEq)]
+pub struct EinsumSubscripts {
+    pub inputs: Vec<Vec<char>>,
+    pub output: Vec<char>,
+}
+
+impl EinsumSubscripts {
+    /// Parse the subscripts like `"bij,bjk->bik"`
+    ///
+    /// # Arguments
+    ///
+    /// * `subscripts` - The subscripts
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The parsed subscripts
+    ///
+    /// # Note
+    ///
+    /// Without `->`, the output is the subscripts which appear only once, in alphabetical order.
+    /// The subscripts must be ASCII letters and whitespace is ignored.
+    /// If the subscripts are not correct, `TensorError::InvalidArgumentError` is returned
+    pub fn parse(subscripts: &str) -> Result<Self> {
+        let subscripts = subscripts.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let (inputs, output) = match subscripts.split_once("->") {
+            Some((inputs, output)) => (inputs, Some(output)),
+            None => (subscripts.as_str(), None),
+        };
+        let check = |part: &str| -> Result<Vec<char>> {
+            match part.chars().find(|c| !c.is_ascii_alphabetic()) {
+                Some(c) => Err(TensorError::InvalidArgumentError(
+                    format!("invalid subscript character: '{}'", c)
+                ).into()),
+                None => Ok(part.chars().collect()),
+            }
+        };
+        let inputs = inputs.split(',')
+            .map(check)
+            .collect::<Result<Vec<_>>>()?;
+        let count = |c: char| inputs.iter().flatten().filter(|&&x| x == c).count();
+        let output = match output {
+            Some(output) => {
+                let output = check(output)?;
+                for (i, &c) in output.iter().enumerate() {
+                    if output[..i].contains(&c) {
+                        return Err(TensorError::InvalidArgumentError(
+                            format!("duplicate output subscript: '{}'", c)
+                        ).into())
+                    }
+                    if count(c) == 0 {
+                        return Err(TensorError::InvalidArgumentError(
+                            format!("output subscript '{}' does not appear in the inputs", c)
+                        ).into())
+                    }
+                }
+                output
+            },
+            None => {
+                let mut output = inputs.iter()
+                    .flatten()
+                    .copied()
+                    .filter(|&c| count(c) == 1)
+                    .collect::<Vec<_>>();
+                output.sort();
+                output
+            },
+        };
+        Ok(Self { inputs, output })
+    }
+}
+
+impl std::fmt::Display for EinsumSubscripts {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let inputs = self.inputs.iter()
+            .map(|input| input.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        write!(f, "{}->{}", inputs.join(","), self.output.iter().collect::<String>())
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: NumAssign + Clone
+{
+    /// Evaluate the Einstein summation of the operands
+    ///
+    /// # Arguments
+    ///
+    /// * `subscripts` - The subscripts like `"bij,bjk->bik"`
+    /// * `operands` - The operands
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The result of the summation
+    ///
+    /// # Note
+    ///
+    /// The subscripts repeated in an operand take the diagonal like `"ii->i"`.
+    /// See `EinsumSubscripts::parse` for the format of the subscripts.
+    ///
+    /// If the subscripts are not correct or the number of the operands does not match,
+    /// `TensorError::InvalidArgumentError` is returned.
+    /// If the number of dimensions of an operand does not match, `TensorError::DimensionError` is returned.
+    /// If the sizes of the same subscript do not match, `TensorError::ShapeError` is returned.
+    pub fn einsum(subscripts: &str, operands: &[&Self]) -> Result<Self> {
+        let parsed = EinsumSubscripts::parse(subscripts)?;
+        if parsed.inputs.len() != operands.len() {
+            return Err(TensorError::InvalidArgumentError(
+                format!("subscripts {:?} need {} operands but {} are given",
+                    subscripts, parsed.inputs.len(), operands.len())
+            ).into())
+        }
+
+        // The output subscripts come first so that the contracted ones are iterated innermost
+        let mut letters = parsed.output.clone();
+        let mut sizes = Vec::new();
+        for c in parsed.inputs.iter().flatten() {
+            if !letters.contains(c) {
+                letters.push(*c);
+            }
+        }
+        sizes.resize(letters.len(), None);
+        for (input, operand) in parsed.inputs.iter().zip(operands.iter()) {
+            if input.len() != operand.ndim() {
+                return Err(TensorError::DimensionError(operand.ndim(), input.len()).into())
+            }
+            for (c, &size) in input.iter().zip(operand.shape.iter()) {
+                let position = letters.iter().position(|x| x == c).unwrap();
+                match sizes[position] {
+                    Some(s) if s != size => {
+                        return Err(TensorError::ShapeError(
+                            operands[0].shape.clone(),
+                            operand.shape.clone(),
+                        ).into())
+                    },
+                    _ => sizes[position] = Some(size),
+                }
+            }
+        }
+        let sizes = sizes.into_iter().map(|s| s.unwrap()).collect::<Vec<_>>();
+
+        let strides_of = |input: &[char], shape: &[usize]| -> Vec<usize> {
+            let mut strides = vec![0; letters.len()];
+            let mut stride = 1;
+            for (c, &size) in input.iter().zip(shape.iter()).rev() {
+                strides[letters.iter().position(|x| x == c).unwrap()] += stride;
+                stride *= size;
+            }
+            strides
+        };
+        let shape = parsed.output.iter()
+            .map(|c| sizes[letters.iter().position(|x| x == c).unwrap()])
+            .collect::<Vec<_>>();
+        let out_strides = strides_of(&parsed.output, &shape);
+        let operand_strides = parsed.inputs.iter()
+            .zip(operands.iter())
+            .map(|(input, operand)| strides_of(input, &operand.shape))
+            .collect::<Vec<_>>();
+
+        let mut data = vec![T::zero(); shape.iter().product()];
+        if sizes.contains(&0) {
+            return Ok(Self { data: Arc::new(data), shape })
+        }
+        let mut counter = vec![0; letters.len()];
+        let mut offsets = vec![0; operands.len()];
+        let mut out_offset = 0;
+        'outer: loop {
+            let mut value = T::one();
+            for (operand, &offset) in operands.iter().zip(offsets.iter()) {
+                value *= operand.data[offset].clone();
+            }
+            data[out_offset] += value;
+            let mut l = letters.len();
+            loop {
+                if l == 0 {
+                    break 'outer
+                }
+                l -= 1;
+                counter[l] += 1;
+                for (offset, strides) in offsets.iter_mut().zip(operand_strides.iter()) {
+                    *offset += strides[l];
+                }
+                out_offset += out_strides[l];
+                if counter[l] < sizes[l] {
+                    break
+                }
+                for (offset, strides) in offsets.iter_mut().zip(operand_strides.iter()) {
+                    *offset -= strides[l] * sizes[l];
+                }
+                out_offset -= out_strides[l] * sizes[l];
+                counter[l] = 0;
+            }
+        }
+        Ok(Self { data: Arc::new(data), shape })
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: MatMulKernel
+{
+    /// Contract the axes of two tensors
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The right hand side tensor
+    /// * `axes` - The axes of `self` and the axes of `rhs` to contract, in pairs
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The tensor whose axes are the other axes of `self` followed by those of `rhs`
+    ///
+    /// # Note
+    ///
+    /// The contraction is calculated by `matmul` after permuting and reshaping the tensors.
+    /// If the numbers of the axes are different or an axis is duplicated, `TensorError::InvalidArgumentError` is returned.
+    /// If an axis is out of range, `TensorError::DimensionLargerError` is returned.
+    /// If the sizes of the contracted axes do not match, `TensorError::ShapeError` is returned.
+    pub fn tensordot<U: AsRef<[usize]>, V: AsRef<[usize]>>(&self, rhs: &Self, axes: (U, V)) -> Result<Self> {
+        let (lhs_axes, rhs_axes) = (axes.0.as_ref(), axes.1.as_ref());
+        if lhs_axes.len() != rhs_axes.len() {
+            return Err(TensorError::InvalidArgumentError(
+                format!("the numbers of axes are different: {:?} and {:?}", lhs_axes, rhs_axes)
+            ).into())
+        }
+        self.reduced_size(lhs_axes)?;
+        rhs.reduced_size(rhs_axes)?;
+        for (&a, &b) in lhs_axes.iter().zip(rhs_axes.iter()) {
+            if self.shape[a] != rhs.shape[b] {
+                return Err(TensorError::ShapeError(self.shape.clone(), rhs.shape.clone()).into())
+            }
+        }
+        let lhs_free = (0..self.ndim()).filter(|i| !lhs_axes.contains(i)).collect::<Vec<_>>();
+        let rhs_free = (0..rhs.ndim()).filter(|i| !rhs_axes.contains(i)).collect::<Vec<_>>();
+        let lhs_free_shape = lhs_free.iter().map(|&i| self.shape[i]).collect::<Vec<_>>();
+        let rhs_free_shape = rhs_free.iter().map(|&i| rhs.shape[i]).collect::<Vec<_>>();
+        let inner = lhs_axes.iter().map(|&i| self.shape[i]).product::<usize>();
+
+        let lhs = self.permute([lhs_free.as_slice(), lhs_axes].concat())?
+            .reshape([lhs_free_shape.iter().product(), inner])?;
+        let rhs = rhs.permute([rhs_axes, rhs_free.as_slice()].concat())?
+            .reshape([inner, rhs_free_shape.iter().product()])?;
+        lhs.matmul(&rhs)?
+            .reshape([lhs_free_shape, rhs_free_shape].concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_normal() {
+        let parsed = EinsumSubscripts::parse("bij, bjk -> bik").unwrap();
+        assert_eq!(parsed.inputs, vec![vec!['b', 'i', 'j'], vec!['b', 'j', 'k']]);
+        assert_eq!(parsed.output, vec!['b', 'i', 'k']);
+        assert_eq!(parsed.to_string(), "bij,bjk->bik");
+        let parsed = EinsumSubscripts::parse("ji,jk").unwrap();
+        assert_eq!(parsed.output, vec!['i', 'k']);
+    }
+
+    #[test]
+    fn parse_error() {
+        match EinsumSubscripts::parse("ij,jk->il") {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "output subscript 'l' does not appear in the inputs".to_string()
+                ));
+            }
+        }
+        assert!(EinsumSubscripts::parse("i1->i").is_err());
+        assert!(EinsumSubscripts::parse("ij->ii").is_err());
+    }
+
+    #[test]
+    fn einsum_matmul() {
+        let a = Tensor::<f64>::arrange([2, 2, 3]).unwrap();
+        let b = Tensor::<f64>::arrange([2, 3, 2]).unwrap();
+        let y = Tensor::einsum("bij,bjk->bik", &[&a, &b]).unwrap();
+        assert_eq!(y, a.matmul(&b).unwrap());
+    }
+
+    #[test]
+    fn einsum_reductions() {
+        let a = Tensor::new([1, 2, 3, 4], [2, 2]).unwrap();
+        assert_eq!(Tensor::einsum("ii->i", &[&a]).unwrap(), Tensor::new([1, 4], [2]).unwrap());
+        assert_eq!(Tensor::einsum("ii", &[&a]).unwrap(), Tensor::scalar(5));
+        assert_eq!(Tensor::einsum("ij->ji", &[&a]).unwrap(), a.transpose().contiguous());
+        assert_eq!(Tensor::einsum("ij->", &[&a]).unwrap(), Tensor::scalar(10));
+        let v = Tensor::new([1, 2], [2]).unwrap();
+        assert_eq!(Tensor::einsum("i,j->ij", &[&v, &v]).unwrap(), Tensor::new([1, 2, 2, 4], [2, 2]).unwrap());
+        assert_eq!(Tensor::einsum("i,ij,j", &[&v, &a, &v]).unwrap(), Tensor::scalar(27));
+    }
+
+    #[test]
+    fn einsum_error_operands() {
+        let a = Tensor::new([1, 2, 3, 4], [2, 2]).unwrap();
+        match Tensor::einsum("ij,jk->ik", &[&a]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "subscripts \"ij,jk->ik\" need 2 operands but 1 are given".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn einsum_error_shape() {
+        let a = Tensor::new([1, 2, 3, 4], [2, 2]).unwrap();
+        let b = Tensor::new([1, 2, 3], [3]).unwrap();
+        match Tensor::einsum("ij,j->i", &[&a, &b]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![2, 2], vec![3]));
+            }
+        }
+        match Tensor::einsum("ijk->i", &[&a]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionError(2, 3));
+            }
+        }
+    }
+
+    #[test]
+    fn tensordot_normal() {
+        let a = Tensor::<f64>::arrange([3, 4, 5]).unwrap();
+        let b = Tensor::<f64>::arrange([4, 3, 2]).unwrap();
+        let y = a.tensordot(&b, ([1, 0], [0, 1])).unwrap();
+        assert_eq!(y.get_shape(), &vec![5, 2]);
+        assert_eq!(y, Tensor::einsum("ijk,jil->kl", &[&a, &b]).unwrap());
+        let y = a.tensordot(&b, ([] as [usize; 0], [] as [usize; 0])).unwrap();
+        assert_eq!(y.get_shape(), &vec![3, 4, 5, 4, 3, 2]);
+    }
+
+    #[test]
+    fn tensordot_error_shape() {
+        let a = Tensor::<f64>::arrange([3, 4]).unwrap();
+        let b = Tensor::<f64>::arrange([3, 4]).unwrap();
+        match a.tensordot(&b, ([1], [0])) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![3, 4], vec![3, 4]));
+            }
+        }
+    }
+}
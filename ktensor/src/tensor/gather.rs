@@ -0,0 +1,275 @@
+use std::sync::Arc;
+use anyhow::Result;
+use num_traits::NumAssign;
+use super::Tensor;
+use crate::error::TensorError;
+
+impl<T> Tensor<T> {
+    /// Calculate the data index of `self` for each element of `index` along the axis
+    /// 
+    /// # Note
+    /// 
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If the number of dimensions is not the same, `TensorError::DimensionError` is returned
+    /// If `index` is larger than `self` except for the axis, `TensorError::ShapeError` is returned
+    /// If an index is out of range, `TensorError::IndexError` is returned
+    fn index_positions_along_axis(&self, axis: usize, index: &Tensor<usize>) -> Result<Vec<usize>> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        if index.ndim() != self.ndim() {
+            return Err(TensorError::DimensionError(index.ndim(), self.ndim()).into())
+        }
+        let fits = index.shape.iter().zip(self.shape.iter())
+            .enumerate()
+            .all(|(i, (a, b))| i == axis || a <= b);
+        if !fits {
+            return Err(TensorError::ShapeError(self.shape.clone(), index.shape.clone()).into())
+        }
+        index.data.iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let mut indexes = Tensor::<usize>::data_index_to_indexes(i, &index.shape);
+                indexes[axis] = value;
+                self.calc_data_index(indexes)
+            })
+            .collect()
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: Clone
+{
+    /// Gather the values along the axis
+    /// 
+    /// `y[i][j][k] = self[index[i][j][k]][j][k]` when the axis is 0.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `index` - The indices along the axis, which has the same number of dimensions as `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The tensor of the shape of `index`
+    /// 
+    /// # Note
+    /// 
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If the number of dimensions is not the same, `TensorError::DimensionError` is returned
+    /// If `index` is larger than `self` except for the axis, `TensorError::ShapeError` is returned
+    /// If an index is out of range, `TensorError::IndexError` is returned
+    pub fn gather(&self, axis: usize, index: &Tensor<usize>) -> Result<Self> {
+        let data = self.index_positions_along_axis(axis, index)?
+            .into_iter()
+            .map(|position| self.data[position].clone())
+            .collect();
+        Ok(Self { data: Arc::new(data), shape: index.shape.clone() })
+    }
+
+    /// Take the values along the axis with the indices broadcast to the shape of `self`
+    /// 
+    /// # Arguments
+    /// 
+    /// * `indices` - The indices along the axis
+    /// * `axis` - The axis
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The result, whose size of the axis is the size of `indices`
+    /// 
+    /// # Note
+    /// 
+    /// Unlike `gather`, the dimensions of size 1 of `indices` except for the axis are broadcast.
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    /// and the other errors are the same as `gather`.
+    pub fn take_along_axis(&self, indices: &Tensor<usize>, axis: usize) -> Result<Self> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        if indices.ndim() != self.ndim() {
+            return Err(TensorError::DimensionError(indices.ndim(), self.ndim()).into())
+        }
+        let mut shape = self.shape.clone();
+        shape[axis] = indices.shape[axis];
+        let shape = Tensor::<usize>::broadcast_shape(&shape, &indices.shape)?;
+        let indices = indices.broadcast_to(shape)?.contiguous();
+        self.gather(axis, &indices)
+    }
+
+    /// Select the slices at the indices along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `indices` - The indices along the axis
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The result, whose size of the axis is the number of the indices
+    /// 
+    /// # Note
+    /// 
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If an index is out of range, `TensorError::IndexError` is returned
+    pub fn index_select<U: AsRef<[usize]>>(&self, axis: usize, indices: U) -> Result<Self> {
+        let indices = indices.as_ref();
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        for &index in indices {
+            if index >= self.shape[axis] {
+                return Err(TensorError::IndexError(self.shape.clone(), vec![index]).into())
+            }
+        }
+        let size = self.shape[axis];
+        let before = self.shape[..axis].iter().product::<usize>();
+        let after = self.shape[axis + 1..].iter().product::<usize>();
+        let mut data = Vec::with_capacity(before * indices.len() * after);
+        for i in 0..before {
+            for &index in indices {
+                let start = (i * size + index) * after;
+                data.extend_from_slice(&self.data[start..start + after]);
+            }
+        }
+        let mut shape = self.shape.clone();
+        shape[axis] = indices.len();
+        Ok(Self { data: Arc::new(data), shape })
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: NumAssign + Clone
+{
+    /// Add the values of `src` at the indices along the axis
+    /// 
+    /// `self[index[i][j][k]][j][k] += src[i][j][k]` when the axis is 0.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `index` - The indices along the axis, which has the same shape as `src`
+    /// * `src` - The values to add
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The result of the addition
+    /// 
+    /// # Note
+    /// 
+    /// The values at the same index are accumulated.
+    /// If the shape of `index` and `src` is not the same, `TensorError::ShapeError` is returned
+    /// and the other errors are the same as `gather`.
+    pub fn scatter_add(&self, axis: usize, index: &Tensor<usize>, src: &Self) -> Result<Self> {
+        if index.shape != src.shape {
+            return Err(TensorError::ShapeError(index.shape.clone(), src.shape.clone()).into())
+        }
+        let mut data = self.data.to_vec();
+        let positions = self.index_positions_along_axis(axis, index)?;
+        for (position, value) in positions.into_iter().zip(src.data.iter()) {
+            data[position] += value.clone();
+        }
+        Ok(Self { data: Arc::new(data), shape: self.shape.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gather_normal() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        let index = Tensor::new([2, 0, 1, 1], [2, 2]).unwrap();
+        assert_eq!(x.gather(1, &index).unwrap(), Tensor::new([3, 1, 5, 5], [2, 2]).unwrap());
+        let index = Tensor::new([1, 0, 1], [1, 3]).unwrap();
+        assert_eq!(x.gather(0, &index).unwrap(), Tensor::new([4, 2, 6], [1, 3]).unwrap());
+    }
+
+    #[test]
+    fn gather_error_index() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        let index = Tensor::new([3], [1, 1]).unwrap();
+        match x.gather(1, &index) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::IndexError(vec![2, 3], vec![0, 3]));
+            }
+        }
+    }
+
+    #[test]
+    fn gather_error_shape() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        let index = Tensor::new([0, 0, 0], [3, 1]).unwrap();
+        match x.gather(1, &index) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![2, 3], vec![3, 1]));
+            }
+        }
+    }
+
+    #[test]
+    fn take_along_axis_normal() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        let indices = Tensor::new([2, 0], [2, 1]).unwrap();
+        assert_eq!(x.take_along_axis(&indices, 1).unwrap(), Tensor::new([3, 4], [2, 1]).unwrap());
+        let indices = Tensor::new([1, 0, 1], [1, 3]).unwrap();
+        assert_eq!(x.take_along_axis(&indices, 1).unwrap(), Tensor::new([2, 1, 2, 5, 4, 5], [2, 3]).unwrap());
+    }
+
+    #[test]
+    fn index_select_normal() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        assert_eq!(x.index_select(1, [2, 0, 2]).unwrap(), Tensor::new([3, 1, 3, 6, 4, 6], [2, 3]).unwrap());
+        assert_eq!(x.index_select(0, [1]).unwrap(), Tensor::new([4, 5, 6], [1, 3]).unwrap());
+    }
+
+    #[test]
+    fn index_select_error_index() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        match x.index_select(0, [2]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::IndexError(vec![2, 3], vec![2]));
+            }
+        }
+    }
+
+    #[test]
+    fn scatter_add_normal() {
+        let x = Tensor::<i32>::zeros([2, 3]);
+        let index = Tensor::new([2, 0, 2, 1, 1, 1], [2, 3]).unwrap();
+        let src = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        assert_eq!(x.scatter_add(1, &index, &src).unwrap(), Tensor::new([2, 0, 4, 0, 15, 0], [2, 3]).unwrap());
+    }
+
+    #[test]
+    fn scatter_add_is_gather_adjoint() {
+        let x = Tensor::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [3, 2]).unwrap();
+        let index = Tensor::new([2, 0, 2, 2], [2, 2]).unwrap();
+        let y = x.gather(0, &index).unwrap();
+        let g = Tensor::<f64>::zeros_like(&x).scatter_add(0, &index, &y).unwrap();
+        assert_eq!(g, Tensor::new([0.0, 2.0, 0.0, 0.0, 10.0, 6.0], [3, 2]).unwrap());
+    }
+
+    #[test]
+    fn scatter_add_error_shape() {
+        let x = Tensor::<i32>::zeros([2, 3]);
+        let index = Tensor::new([0, 1], [1, 2]).unwrap();
+        let src = Tensor::new([1, 2, 3], [1, 3]).unwrap();
+        match x.scatter_add(1, &index, &src) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![1, 2], vec![1, 3]));
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::Tensor;
+use super::{Tensor, TensorView};
 
 /// Iterator for Tensor
 pub struct TensorIter<'a, T> {
@@ -30,6 +30,58 @@ impl<'a, T: 'a> IntoIterator for &'a Tensor<T> {
     }
 }
 
+/// Iterator for TensorView
+/// 
+/// The elements are visited in row-major order of the view.
+pub struct TensorViewIter<'a, T> {
+    view: &'a TensorView<T>,
+    index: Vec<usize>,
+    position: isize,
+    remaining: usize,
+}
+
+impl<T> TensorView<T> {
+    pub fn iter(&self) -> TensorViewIter<'_, T> {
+        TensorViewIter {
+            view: self,
+            index: vec![0; self.ndim()],
+            position: self.offset as isize,
+            remaining: self.size(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for TensorViewIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let value = &self.view.data[self.position as usize];
+        self.remaining -= 1;
+        for i in (0..self.index.len()).rev() {
+            self.index[i] += 1;
+            self.position += self.view.strides[i];
+            if self.index[i] < self.view.shape[i] {
+                break;
+            }
+            self.position -= self.view.strides[i] * self.view.shape[i] as isize;
+            self.index[i] = 0;
+        }
+        Some(value)
+    }
+}
+
+impl<'a, T: 'a> IntoIterator for &'a TensorView<T> {
+    type Item = &'a T;
+    type IntoIter = TensorViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,4 +104,15 @@ mod tests {
         assert_eq!(iter.next(), Some(&4));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn view_iterator_normal() {
+        let x = Tensor::new(vec![1, 2, 3, 4, 5, 6], vec![2, 3]).unwrap();
+        let v = x.view().transpose();
+        assert_eq!(v.iter().cloned().collect::<Vec<_>>(), vec![1, 4, 2, 5, 3, 6]);
+        let v = x.view().slice_axis(1, 1, 2).unwrap();
+        assert_eq!(v.into_iter().cloned().collect::<Vec<_>>(), vec![2, 5]);
+        let v = x.view().slice_axis(0, 1, 1).unwrap();
+        assert_eq!(v.iter().next(), None);
+    }
 }
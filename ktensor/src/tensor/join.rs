@@ -0,0 +1,297 @@
+use std::sync::Arc;
+use anyhow::Result;
+use super::Tensor;
+use crate::error::TensorError;
+
+impl<T> Tensor<T>
+where
+    T: Clone
+{
+    /// Concatenate tensors along the existing axis
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor_list` - The tensor list
+    /// * `axis` - The axis to concatenate
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - Result of the concatenation
+    ///
+    /// # Note
+    ///
+    /// If the list is empty, `TensorError::InvalidArgumentError` is returned.
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned.
+    /// If the number of dimensions of the tensors are not the same, `TensorError::DimensionError` is returned.
+    /// If the shape of the tensors are not the same except for the axis, `TensorError::ShapeError` is returned.
+    pub fn concat(tensor_list: &[&Self], axis: usize) -> Result<Self> {
+        if tensor_list.is_empty() {
+            return Err(TensorError::InvalidArgumentError("tensor list is empty".to_string()).into())
+        }
+        let first_shape = tensor_list[0].get_shape();
+        if axis >= first_shape.len() {
+            return Err(TensorError::DimensionLargerError(axis, first_shape.len()).into())
+        }
+        for tensor in tensor_list {
+            if tensor.ndim() != first_shape.len() {
+                return Err(TensorError::DimensionError(tensor.ndim(), first_shape.len()).into())
+            }
+            let same = tensor.shape.iter().zip(first_shape.iter())
+                .enumerate()
+                .all(|(i, (a, b))| i == axis || a == b);
+            if !same {
+                return Err(TensorError::ShapeError(
+                    first_shape.clone(),
+                    tensor.get_shape().clone()
+                ).into())
+            }
+        }
+        let outer = first_shape[..axis].iter().product::<usize>();
+        let inner = first_shape[axis + 1..].iter().product::<usize>();
+        let mut shape = first_shape.clone();
+        shape[axis] = tensor_list.iter().map(|tensor| tensor.shape[axis]).sum();
+        let mut data = Vec::with_capacity(shape.iter().product());
+        for i in 0..outer {
+            for tensor in tensor_list {
+                let size = tensor.shape[axis] * inner;
+                data.extend_from_slice(&tensor.data[i * size..(i + 1) * size]);
+            }
+        }
+        Ok(Self { data: Arc::new(data), shape })
+    }
+
+    /// Stack tensors along a new axis
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor_list` - The tensor list
+    /// * `axis` - The position of the new axis in the result
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - Result of the stacking
+    ///
+    /// # Note
+    ///
+    /// If the list is empty, `TensorError::InvalidArgumentError` is returned.
+    /// If the axis is larger than the number of dimensions, `TensorError::DimensionLargerError` is returned.
+    /// If the shape of the tensors are not the same, `TensorError::ShapeError` is returned.
+    pub fn stack(tensor_list: &[&Self], axis: usize) -> Result<Self> {
+        if tensor_list.is_empty() {
+            return Err(TensorError::InvalidArgumentError("tensor list is empty".to_string()).into())
+        }
+        let first_shape = tensor_list[0].get_shape();
+        if axis > first_shape.len() {
+            return Err(TensorError::DimensionLargerError(axis, first_shape.len()).into())
+        }
+        let mut shape = first_shape.clone();
+        shape.insert(axis, 1);
+        let tensor_list = tensor_list.iter()
+            .map(|tensor| {
+                if tensor.get_shape() != first_shape {
+                    return Err(TensorError::ShapeError(
+                        first_shape.clone(),
+                        tensor.get_shape().clone()
+                    ).into())
+                }
+                tensor.reshape(shape.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::concat(&tensor_list.iter().collect::<Vec<_>>(), axis)
+    }
+
+    /// Split the tensor into the tensors of the given sizes along the axis
+    ///
+    /// # Arguments
+    ///
+    /// * `sizes` - The size of each tensor along the axis
+    /// * `axis` - The axis to split
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Self>>` - The split tensors
+    ///
+    /// # Note
+    ///
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned.
+    /// If the sum of the sizes is not the size of the axis, `TensorError::InvalidArgumentError` is returned.
+    pub fn split<U: AsRef<[usize]>>(&self, sizes: U, axis: usize) -> Result<Vec<Self>> {
+        let sizes = sizes.as_ref();
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim()).into())
+        }
+        if sizes.iter().sum::<usize>() != self.shape[axis] {
+            return Err(TensorError::InvalidArgumentError(
+                format!("sizes {:?} do not sum to the size of axis {}: {}", sizes, axis, self.shape[axis])
+            ).into())
+        }
+        let view = self.view();
+        let mut start = 0;
+        sizes.iter()
+            .map(|size| {
+                let tensor = view.slice_axis(axis, start, start + size)?.contiguous();
+                start += size;
+                Ok(tensor)
+            })
+            .collect()
+    }
+
+    /// Split the tensor into the chunks of the same size along the axis
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks` - The number of chunks
+    /// * `axis` - The axis to split
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Self>>` - The chunks
+    ///
+    /// # Note
+    ///
+    /// Each chunk has `ceil(size / chunks)` elements along the axis and the last chunk may be smaller,
+    /// so fewer than `chunks` tensors may be returned.
+    /// If `chunks` is zero, `TensorError::InvalidArgumentError` is returned.
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned.
+    pub fn chunk(&self, chunks: usize, axis: usize) -> Result<Vec<Self>> {
+        if chunks == 0 {
+            return Err(TensorError::InvalidArgumentError("chunks must not be zero".to_string()).into())
+        }
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim()).into())
+        }
+        self.split(chunk_sizes(self.shape[axis], chunks), axis)
+    }
+}
+
+/// Calculate the sizes of the chunks like `Tensor::chunk`
+fn chunk_sizes(size: usize, chunks: usize) -> Vec<usize> {
+    let chunk_size = size.div_ceil(chunks).max(1);
+    let mut sizes = vec![chunk_size; size / chunk_size];
+    if !size.is_multiple_of(chunk_size) {
+        sizes.push(size % chunk_size);
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concat_axis0() {
+        let x = Tensor::<i32>::arrange([1, 2]).unwrap();
+        let y = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let z = Tensor::concat(&[&x, &y], 0).unwrap();
+        assert_eq!(z.get_shape(), &vec![3, 2]);
+        assert_eq!(z.get_data(), &vec![0, 1, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn concat_axis1() {
+        let x = Tensor::<i32>::arrange([2, 1]).unwrap();
+        let y = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let z = Tensor::concat(&[&x, &y, &x], 1).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 4]);
+        assert_eq!(z.get_data(), &vec![0, 0, 1, 0, 1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn concat_error_shape() {
+        let x = Tensor::<i32>::arrange([2, 1]).unwrap();
+        let y = Tensor::<i32>::arrange([3, 2]).unwrap();
+        match Tensor::concat(&[&x, &y], 1) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![2, 1], vec![3, 2]));
+            }
+        }
+    }
+
+    #[test]
+    fn concat_error_empty() {
+        match Tensor::<i32>::concat(&[], 0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError("tensor list is empty".to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn stack_axis() {
+        let x = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let y = Tensor::new([4, 5, 6, 7], [2, 2]).unwrap();
+        let z = Tensor::stack(&[&x, &y], 0).unwrap();
+        assert_eq!(z, Tensor::from_tensor_list(&[&x, &y]).unwrap());
+        let z = Tensor::stack(&[&x, &y], 2).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 2, 2]);
+        assert_eq!(z.get_data(), &vec![0, 4, 1, 5, 2, 6, 3, 7]);
+    }
+
+    #[test]
+    fn stack_error_axis() {
+        let x = Tensor::<i32>::arrange([2, 2]).unwrap();
+        match Tensor::stack(&[&x], 3) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(3, 2));
+            }
+        }
+    }
+
+    #[test]
+    fn split_normal() {
+        let x = Tensor::<i32>::arrange([2, 5]).unwrap();
+        let ys = x.split([2, 3], 1).unwrap();
+        assert_eq!(ys[0], Tensor::new([0, 1, 5, 6], [2, 2]).unwrap());
+        assert_eq!(ys[1], Tensor::new([2, 3, 4, 7, 8, 9], [2, 3]).unwrap());
+        assert_eq!(Tensor::concat(&[&ys[0], &ys[1]], 1).unwrap(), x);
+    }
+
+    #[test]
+    fn split_error_sizes() {
+        let x = Tensor::<i32>::arrange([2, 5]).unwrap();
+        match x.split([2, 2], 1) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "sizes [2, 2] do not sum to the size of axis 1: 5".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn chunk_normal() {
+        let x = Tensor::<i32>::arrange([5, 2]).unwrap();
+        let ys = x.chunk(2, 0).unwrap();
+        assert_eq!(ys.len(), 2);
+        assert_eq!(ys[0].get_shape(), &vec![3, 2]);
+        assert_eq!(ys[1], Tensor::new([6, 7, 8, 9], [2, 2]).unwrap());
+    }
+
+    #[test]
+    fn chunk_sizes_normal() {
+        assert_eq!(chunk_sizes(6, 3), vec![2, 2, 2]);
+        assert_eq!(chunk_sizes(5, 3), vec![2, 2, 1]);
+        assert_eq!(chunk_sizes(6, 4), vec![2, 2, 2]);
+        assert_eq!(chunk_sizes(2, 4), vec![1, 1]);
+    }
+
+    #[test]
+    fn chunk_error_zero() {
+        let x = Tensor::<i32>::arrange([5, 2]).unwrap();
+        match x.chunk(0, 0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError("chunks must not be zero".to_string()));
+            }
+        }
+    }
+}
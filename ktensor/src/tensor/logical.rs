@@ -0,0 +1,187 @@
+use std::sync::Arc;
+use anyhow::Result;
+use super::Tensor;
+
+impl Tensor<bool> {
+    /// Logical AND of each element
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The other tensor
+    ///
+    /// # Note
+    ///
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn logical_and(&self, rhs: &Self) -> Result<Self> {
+        self.broadcast_zip_with(rhs, |x, y| *x && *y)
+    }
+
+    /// Logical OR of each element
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The other tensor
+    ///
+    /// # Note
+    ///
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn logical_or(&self, rhs: &Self) -> Result<Self> {
+        self.broadcast_zip_with(rhs, |x, y| *x || *y)
+    }
+
+    /// Logical XOR of each element
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The other tensor
+    ///
+    /// # Note
+    ///
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn logical_xor(&self, rhs: &Self) -> Result<Self> {
+        self.broadcast_zip_with(rhs, |x, y| *x != *y)
+    }
+
+    /// Logical NOT of each element
+    pub fn logical_not(&self) -> Self {
+        Self {
+            data: Arc::new(self.data.iter().map(|x| !x).collect()),
+            shape: self.shape.clone(),
+        }
+    }
+
+    /// Check whether any element is true
+    ///
+    /// An empty tensor returns false.
+    pub fn any(&self) -> bool {
+        self.data.iter().any(|&x| x)
+    }
+
+    /// Check whether all elements are true
+    ///
+    /// An empty tensor returns true.
+    pub fn all(&self) -> bool {
+        self.data.iter().all(|&x| x)
+    }
+
+    /// Check whether any element is true along the axis
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - The axis
+    /// * `keepdims` - Whether to keep the dimension
+    ///
+    /// # Note
+    ///
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn any_with_axis(&self, axis: usize, keepdims: bool) -> Result<Self> {
+        self.map_axis(axis, keepdims, |mut lane| lane.any(|&x| x))
+    }
+
+    /// Check whether all elements are true along the axis
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - The axis
+    /// * `keepdims` - Whether to keep the dimension
+    ///
+    /// # Note
+    ///
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn all_with_axis(&self, axis: usize, keepdims: bool) -> Result<Self> {
+        self.map_axis(axis, keepdims, |mut lane| lane.all(|&x| x))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: Clone
+{
+    /// Select the elements of `x` where the condition is true and the elements of `y` elsewhere
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition
+    /// * `x` - The values selected where the condition is true
+    /// * `y` - The values selected where the condition is false
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The tensor of the shape broadcast from the three tensors
+    ///
+    /// # Note
+    ///
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn where_(condition: &Tensor<bool>, x: &Self, y: &Self) -> Result<Self> {
+        let shape = Self::broadcast_shape(&condition.shape, &x.shape)?;
+        let shape = Self::broadcast_shape(&shape, &y.shape)?;
+        let condition = condition.view().expand(shape.clone());
+        let x = x.view().expand(shape.clone());
+        let y = y.view().expand(shape.clone());
+        let data = condition.iter()
+            .zip(x.iter().zip(y.iter()))
+            .map(|(&c, (x, y))| if c { x.clone() } else { y.clone() })
+            .collect();
+        Ok(Self { data: Arc::new(data), shape })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TensorError;
+
+    #[test]
+    fn logical_ops() {
+        let x = Tensor::new([true, true, false, false], [2, 2]).unwrap();
+        let y = Tensor::new([true, false], [2]).unwrap();
+        assert_eq!(x.logical_and(&y).unwrap().get_data(), &vec![true, false, false, false]);
+        assert_eq!(x.logical_or(&y).unwrap().get_data(), &vec![true, true, true, false]);
+        assert_eq!(x.logical_xor(&y).unwrap().get_data(), &vec![false, true, true, false]);
+        assert_eq!(x.logical_not().get_data(), &vec![false, false, true, true]);
+    }
+
+    #[test]
+    fn logical_error_mismatch() {
+        let x = Tensor::new([true, true, false], [3]).unwrap();
+        let y = Tensor::new([true, false], [2]).unwrap();
+        match x.logical_and(&y) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![3], vec![2]));
+            }
+        }
+    }
+
+    #[test]
+    fn any_all() {
+        let x = Tensor::new([true, false, false, false], [2, 2]).unwrap();
+        assert!(x.any());
+        assert!(!x.all());
+        assert_eq!(x.any_with_axis(0, false).unwrap().get_data(), &vec![true, false]);
+        assert_eq!(x.any_with_axis(1, true).unwrap().get_shape(), &vec![2, 1]);
+        assert_eq!(x.any_with_axis(1, true).unwrap().get_data(), &vec![true, false]);
+        assert_eq!(x.logical_not().all_with_axis(0, false).unwrap().get_data(), &vec![false, true]);
+        let empty = Tensor::<bool>::new([], [0]).unwrap();
+        assert!(!empty.any());
+        assert!(empty.all());
+    }
+
+    #[test]
+    fn where_normal() {
+        let condition = Tensor::new([true, false], [2]).unwrap();
+        let x = Tensor::new([1.0, 2.0, 3.0, 4.0], [2, 2]).unwrap();
+        let y = Tensor::scalar(0.0);
+        let z = Tensor::where_(&condition, &x, &y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 2]);
+        assert_eq!(z.get_data(), &vec![1.0, 0.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn where_with_comparison() {
+        let x = Tensor::new([-1.0, 2.0, -3.0], [3]).unwrap();
+        let z = Tensor::where_(&x.gt_scalar(&0.0), &x, &Tensor::scalar(0.0)).unwrap();
+        assert_eq!(z.get_data(), &vec![0.0, 2.0, 0.0]);
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use anyhow::Result;
 use num_traits::{PrimInt, Float, NumAssign};
 use super::Tensor;
@@ -14,10 +15,10 @@ where
     /// * `n` - The power to be calculated (unsigned integer)
     pub fn pow(&self, n: u32) -> Self {
         Self {
-            data: self.data
+            data: Arc::new(self.data
                 .iter()
                 .map(|x| x.pow(n))
-                .collect(),
+                .collect()),
             shape: self.shape.clone(),
         }
     }
@@ -34,10 +35,10 @@ where
     /// * `n` - The power to be calculated (integer)
     pub fn powi(&self, n: i32) -> Self {
         Self {
-            data: self.data
+            data: Arc::new(self.data
                 .iter()
                 .map(|x| x.powi(n))
-                .collect(),
+                .collect()),
             shape: self.shape.clone(),
         }
     }
@@ -49,10 +50,10 @@ where
     /// * `n` - The power to be calculated (floating point)
     pub fn powf(&self, n: T) -> Self {
         Self {
-            data: self.data
+            data: Arc::new(self.data
                 .iter()
                 .map(|x| x.powf(n))
-                .collect(),
+                .collect()),
             shape: self.shape.clone(),
         }
     }
@@ -91,6 +92,25 @@ where
     pub fn sqrt(&self) -> Self {
         self.iter_func(|x| x.sqrt())
     }
+
+    /// Limit the values of the tensor to `[min, max]`
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The lower bound
+    /// * `max` - The upper bound
+    ///
+    /// # Note
+    ///
+    /// NaN is kept as it is
+    pub fn clip(&self, min: T, max: T) -> Self {
+        self.iter_func(|x| if x < min { min } else if x > max { max } else { x })
+    }
+
+    /// Check if all the values of the tensor are neither infinite nor NaN
+    pub fn all_finite(&self) -> bool {
+        self.data.iter().all(|x| x.is_finite())
+    }
 }
 
 impl<T> Tensor<T>
@@ -108,6 +128,11 @@ where
 
     /// Calculate the standard deviation of the tensor
     pub fn std(&self) -> Result<T> {
+        Ok(self.var()?.sqrt())
+    }
+
+    /// Calculate the variance of the tensor
+    pub fn var(&self) -> Result<T> {
         let len = T::from(self.size())
             .ok_or(TensorError::CastError(
                 std::any::type_name::<T>().to_string()
@@ -117,7 +142,119 @@ where
         for x in self.data.iter() {
             sum += (*x - mean).powi(2);
         }
-        Ok((sum / len).sqrt())
+        Ok(sum / len)
+    }
+
+    /// Calculate the mean of the tensor along the axes
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axes to reduce
+    /// * `keepdims` - Whether to keep the dimensions
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The mean
+    /// 
+    /// # Note
+    /// 
+    /// If an axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If an axis is duplicated, `TensorError::InvalidArgumentError` is returned
+    pub fn mean_with_axis<U: AsRef<[usize]>>(&self, axis: U, keepdims: bool) -> Result<Self> {
+        let axis = axis.as_ref();
+        let len = T::from(self.reduced_size(axis)?)
+            .ok_or(TensorError::CastError(
+                std::any::type_name::<T>().to_string()
+            ))?;
+        Ok(&self.sum(Some(axis), keepdims) / len)
+    }
+
+    /// Calculate the variance of the tensor along the axes
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axes to reduce
+    /// * `keepdims` - Whether to keep the dimensions
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The variance
+    /// 
+    /// # Note
+    /// 
+    /// The variance is divided by the number of the elements (not by `n - 1`).
+    /// If an axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If an axis is duplicated, `TensorError::InvalidArgumentError` is returned
+    pub fn var_with_axis<U: AsRef<[usize]>>(&self, axis: U, keepdims: bool) -> Result<Self> {
+        let axis = axis.as_ref();
+        let mean = self.mean_with_axis(axis, true)?;
+        self.try_sub(&mean)?
+            .powi(2)
+            .mean_with_axis(axis, keepdims)
+    }
+
+    /// Calculate the standard deviation of the tensor along the axes
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axes to reduce
+    /// * `keepdims` - Whether to keep the dimensions
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The standard deviation
+    /// 
+    /// # Note
+    /// 
+    /// If an axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If an axis is duplicated, `TensorError::InvalidArgumentError` is returned
+    pub fn std_with_axis<U: AsRef<[usize]>>(&self, axis: U, keepdims: bool) -> Result<Self> {
+        Ok(self.var_with_axis(axis, keepdims)?.sqrt())
+    }
+
+    /// Calculate `log(sum(exp(x)))` of the tensor in a numerically stable way
+    /// 
+    /// # Note
+    /// 
+    /// If the tensor is empty, `TensorError::EmptyTensorError` is returned
+    pub fn logsumexp(&self) -> Result<T> {
+        let max = self.max()?;
+        let max = if max.is_finite() { max } else { T::zero() };
+        let sum = self.data.iter()
+            .fold(T::zero(), |acc, x| acc + (*x - max).exp());
+        Ok(sum.ln() + max)
+    }
+
+    /// Calculate `log(sum(exp(x)))` of the tensor along the axis in a numerically stable way
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis
+    /// * `keepdims` - Whether to keep the dimension
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The result
+    /// 
+    /// # Note
+    /// 
+    /// If the tensor is empty, `TensorError::EmptyTensorError` is returned
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn logsumexp_with_axis(&self, axis: usize, keepdims: bool) -> Result<Self> {
+        let max = self.max_with_axis(axis, true)?
+            .iter_func(|x| if x.is_finite() { x } else { T::zero() });
+        let y = self.try_sub(&max)?
+            .exp()
+            .sum(Some([axis]), true)
+            .log()
+            .try_add(&max)?;
+        if keepdims {
+            Ok(y)
+        } else {
+            let mut shape = y.shape.clone();
+            shape.remove(axis);
+            y.reshape(shape)
+        }
     }
 }
 
@@ -200,6 +337,27 @@ mod tests {
         assert_eq!(x.sqrt(), Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap());
     }
 
+    #[test]
+    fn clip_normal() {
+        let x = Tensor::<f64>::new(vec![-2.0, 0.5, f64::NAN, 3.0], vec![2, 2]).unwrap();
+        let y = x.clip(-1.0, 1.0);
+        assert_eq!(y.get_data()[0], -1.0);
+        assert_eq!(y.get_data()[1], 0.5);
+        assert!(y.get_data()[2].is_nan());
+        assert_eq!(y.get_data()[3], 1.0);
+        assert_eq!(y.get_shape(), &vec![2, 2]);
+    }
+
+    #[test]
+    fn all_finite_normal() {
+        let x = Tensor::<f64>::new(vec![-2.0, 0.5], vec![2]).unwrap();
+        assert!(x.all_finite());
+        let x = Tensor::<f32>::new(vec![1.0, f32::INFINITY], vec![2]).unwrap();
+        assert!(!x.all_finite());
+        let x = Tensor::<f32>::new(vec![f32::NAN], vec![1]).unwrap();
+        assert!(!x.all_finite());
+    }
+
     #[test]
     fn mean_normal() {
         let x = Tensor::<f64>::arrange([2, 2]).unwrap();
@@ -211,4 +369,62 @@ mod tests {
         let x = Tensor::<f64>::arrange([2, 2]).unwrap();
         assert!(x.std().unwrap() - 1.118033988749895 < 1e-10);
     }
+
+    #[test]
+    fn var_normal() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        assert_eq!(x.var().unwrap(), 1.25);
+    }
+
+    #[test]
+    fn mean_with_axis_normal() {
+        let x = Tensor::<f64>::arrange([2, 3, 2]).unwrap();
+        assert_eq!(x.mean_with_axis([1], false).unwrap(),
+            Tensor::new(vec![2.0, 3.0, 8.0, 9.0], vec![2, 2]).unwrap());
+        assert_eq!(x.mean_with_axis([0, 2], true).unwrap(),
+            Tensor::new(vec![3.5, 5.5, 7.5], vec![1, 3, 1]).unwrap());
+        assert_eq!(x.mean_with_axis([0, 1, 2], false).unwrap(), Tensor::scalar(x.mean().unwrap()));
+    }
+
+    #[test]
+    fn mean_with_axis_error_axis() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.mean_with_axis([2], false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(2, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn var_std_with_axis_normal() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 5.0, 4.0, 3.0], vec![2, 3]).unwrap();
+        let var = x.var_with_axis([1], false).unwrap();
+        assert_eq!(var.get_shape(), &vec![2]);
+        assert!((var.get_data()[0] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((var.get_data()[1] - 2.0 / 3.0).abs() < 1e-12);
+        let std = x.std_with_axis([0], true).unwrap();
+        assert_eq!(std, Tensor::new(vec![2.0, 1.0, 0.0], vec![1, 3]).unwrap());
+    }
+
+    #[test]
+    fn logsumexp_normal() {
+        let x = Tensor::new(vec![0.0, 1.0, 1000.0, 1000.0], vec![2, 2]).unwrap();
+        let y = x.logsumexp_with_axis(1, false).unwrap();
+        assert_eq!(y.get_shape(), &vec![2]);
+        assert!((y.get_data()[0] - (1.0f64.exp() + 1.0).ln()).abs() < 1e-12);
+        assert!((y.get_data()[1] - (1000.0 + 2.0f64.ln())).abs() < 1e-9);
+        assert_eq!(x.logsumexp_with_axis(0, true).unwrap().get_shape(), &vec![1, 2]);
+        let x = Tensor::new(vec![0.0, 1.0], vec![2]).unwrap();
+        assert!((x.logsumexp().unwrap() - (1.0f64.exp() + 1.0).ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn logsumexp_with_infinity() {
+        let x = Tensor::new(vec![f64::NEG_INFINITY, f64::NEG_INFINITY], vec![1, 2]).unwrap();
+        let y = x.logsumexp_with_axis(1, false).unwrap();
+        assert_eq!(y.get_data(), &vec![f64::NEG_INFINITY]);
+    }
 }
@@ -1,17 +1,20 @@
+use std::sync::Arc;
+use std::borrow::Cow;
 use num_traits::NumAssign;
 use anyhow::Result;
-use super::Tensor;
+use super::{Tensor, TensorView};
+use super::view::AsView;
 use crate::error::TensorError;
 
 impl<T> Tensor<T>
 where
-    T: NumAssign + Clone
+    T: MatMulKernel
 {
     /// Matrix multiplication
     /// 
     /// # Arguments
     /// 
-    /// * `rhs` - The right hand side of the matrix multiplication
+    /// * `rhs` - The right hand side of the matrix multiplication, a tensor or a view
     /// 
     /// # Returns
     /// 
@@ -19,36 +22,306 @@ where
     /// 
     /// # Note
     /// 
+    /// The behavior follows NumPy's `matmul`.
+    /// The last two dimensions are multiplied as matrices and the leading dimensions are broadcast as batch dimensions.
+    /// A 1-D left hand side is treated as a row vector and a 1-D right hand side as a column vector,
+    /// and the added dimension is removed from the result.
+    /// 
     /// If the shape is not correct, following errors are returned:
     /// 
-    /// * `TensorError::ShapeSizeError` - If the shape size is not correct
-    /// * `TensorError::ShapeError` - If the shape is not correct
+    /// * `TensorError::DimensionSmallerError` - If the tensor is a scalar
+    /// * `TensorError::ShapeError` - If the inner dimensions do not match
+    /// * `TensorError::ShapeMismatchError` - If the batch dimensions cannot be broadcast
     /// * `TensorError::Error` - If the shape is zero
-    pub fn matmul(&self, rhs: &Self) -> Result<Self> {
-        if self.ndim() != 2 {
-            return Err(TensorError::ShapeSizeError(self.ndim(), 2).into())
+    pub fn matmul<R: AsView<T> + ?Sized>(&self, rhs: &R) -> Result<Self> {
+        self.view().matmul(rhs)
+    }
+}
+
+impl<T> TensorView<T>
+where
+    T: MatMulKernel
+{
+    /// Matrix multiplication of the view
+    /// 
+    /// # Note
+    /// 
+    /// The elements are read through the strides without materializing the views,
+    /// so `x.transpose().matmul(&y)` does not copy `x`.
+    /// See `Tensor::matmul` for the shapes and the errors.
+    pub fn matmul<R: AsView<T> + ?Sized>(&self, rhs: &R) -> Result<Tensor<T>> {
+        let rhs = rhs.as_view();
+        if self.ndim() == 0 {
+            return Err(TensorError::DimensionSmallerError(self.ndim(), 1).into())
         }
-        if rhs.ndim() != 2 {
-            return Err(TensorError::ShapeSizeError(rhs.ndim(), 2).into())
+        if rhs.ndim() == 0 {
+            return Err(TensorError::DimensionSmallerError(rhs.ndim(), 1).into())
         }
-        if self.shape[1] != rhs.shape[0] {
+        let lhs = if self.ndim() == 1 {
+            Self { data: self.data.clone(), shape: vec![1, self.shape[0]], strides: vec![0, self.strides[0]], offset: self.offset }
+        } else {
+            self.as_view()
+        };
+        let rhs_matrix = if rhs.ndim() == 1 {
+            Self { data: rhs.data.clone(), shape: vec![rhs.shape[0], 1], strides: vec![rhs.strides[0], 0], offset: rhs.offset }
+        } else {
+            rhs.as_view()
+        };
+        let (lhs_batch, lhs_matrix) = lhs.shape.split_at(lhs.ndim() - 2);
+        let (rhs_batch, rhs_dims) = rhs_matrix.shape.split_at(rhs_matrix.ndim() - 2);
+        let (n, k, m) = (lhs_matrix[0], lhs_matrix[1], rhs_dims[1]);
+        if k != rhs_dims[0] {
             return Err(TensorError::ShapeError(self.shape.clone(), rhs.shape.clone()).into())
         }
-        if self.shape[0] == 0 || self.shape[1] == 0 {
+        if n == 0 || k == 0 {
             return Err(TensorError::Error(format!("The shape is zero: {:?}", self.shape).to_string()).into())
         }
-        if rhs.shape[1] == 0 {
+        if m == 0 {
             return Err(TensorError::Error(format!("The shape is zero: {:?}", rhs.shape).to_string()).into())
         }
-        let mut data = vec![T::zero(); self.shape[0] * rhs.shape[1]];
-        for i in 0..self.shape[0] {
-            for j in 0..rhs.shape[1] {
-                for k in 0..self.shape[1] {
-                    data[i * rhs.shape[1] + j] += self.data[i * self.shape[1] + k].clone() * rhs.data[k * rhs.shape[1] + j].clone();
+        let batch_shape = Tensor::<T>::broadcast_shape(lhs_batch, rhs_batch)
+            .map_err(|_| TensorError::ShapeMismatchError(self.shape.clone(), rhs.shape.clone()))?;
+        let lhs = lhs.expand([batch_shape.as_slice(), &[n, k]].concat());
+        let rhs_matrix = rhs_matrix.expand([batch_shape.as_slice(), &[k, m]].concat());
+        let batch_size = batch_shape.iter().product::<usize>();
+        let mut data = vec![T::zero(); batch_size * n * m];
+        for (batch, out) in data.chunks_mut(n * m).enumerate() {
+            let indexes = Tensor::<T>::data_index_to_indexes(batch, &batch_shape);
+            T::matmul_kernel_strided(lhs.batch_matrix(&indexes), rhs_matrix.batch_matrix(&indexes), out, n, k, m);
+        }
+        let mut shape = batch_shape;
+        if self.ndim() > 1 {
+            shape.push(n);
+        }
+        if rhs.ndim() > 1 {
+            shape.push(m);
+        }
+        Ok(Tensor { data: Arc::new(data), shape })
+    }
+
+    /// The matrix of the last two dimensions at the batch `indexes`
+    fn batch_matrix(&self, indexes: &[usize]) -> StridedMatrix<'_, T> {
+        let ndim = self.ndim();
+        let offset = indexes.iter()
+            .zip(self.strides.iter())
+            .fold(self.offset as isize, |offset, (&i, &s)| offset + i as isize * s);
+        StridedMatrix {
+            data: &self.data,
+            offset: offset as usize,
+            row_stride: self.strides[ndim - 2],
+            col_stride: self.strides[ndim - 1],
+        }
+    }
+}
+
+/// A matrix stored in `data` with the given offset and strides
+#[derive(Debug, Clone, Copy)]
+pub struct StridedMatrix<'a, T> {
+    pub data: &'a [T],
+    pub offset: usize,
+    pub row_stride: isize,
+    pub col_stride: isize,
+}
+
+impl<T> StridedMatrix<'_, T>
+where
+    T: Clone
+{
+    /// Whether the `rows x cols` matrix is stored in row-major order without gaps
+    pub fn is_row_major(&self, rows: usize, cols: usize) -> bool {
+        (cols == 1 || self.col_stride == 1) && (rows == 1 || self.row_stride == cols as isize)
+    }
+
+    /// The `rows x cols` matrix in row-major order
+    /// 
+    /// # Note
+    /// 
+    /// The data is borrowed without copying when the matrix is row-major.
+    pub fn row_major(&self, rows: usize, cols: usize) -> Cow<'_, [T]> {
+        if self.is_row_major(rows, cols) {
+            return Cow::Borrowed(&self.data[self.offset..self.offset + rows * cols])
+        }
+        let mut packed = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            let row = self.offset as isize + i as isize * self.row_stride;
+            for j in 0..cols {
+                packed.push(self.data[(row + j as isize * self.col_stride) as usize].clone());
+            }
+        }
+        Cow::Owned(packed)
+    }
+}
+
+/// The number of rows of the left hand side processed as a block
+#[cfg(not(feature = "blas"))]
+const BLOCK_ROWS: usize = 64;
+/// The number of the inner dimension processed as a block
+#[cfg(not(feature = "blas"))]
+const BLOCK_INNER: usize = 256;
+/// The number of columns of the right hand side processed as a block
+#[cfg(not(feature = "blas"))]
+const BLOCK_COLS: usize = 512;
+/// The minimum number of multiply-add operations to split the work into threads
+#[cfg(all(feature = "parallel", not(feature = "blas")))]
+const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+/// The kernel of the matrix multiplication
+/// 
+/// # Note
+/// 
+/// The default kernel is a loop-reordered generic implementation.
+/// `f32` and `f64` use a cache-blocked kernel,
+/// which is parallelized across rows when the `parallel` feature is enabled.
+/// When the `blas` feature is enabled, `f32` and `f64` use `sgemm` and `dgemm` instead.
+pub trait MatMulKernel: NumAssign + Clone {
+    /// Multiply a `n x k` matrix and a `k x m` matrix and add the result to `out`
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lhs` - The left hand side matrix in row-major order
+    /// * `rhs` - The right hand side matrix in row-major order
+    /// * `out` - The `n x m` output matrix in row-major order
+    fn matmul_kernel(lhs: &[Self], rhs: &[Self], out: &mut [Self], n: usize, k: usize, m: usize) {
+        for i in 0..n {
+            for p in 0..k {
+                let a = lhs[i * k + p].clone();
+                let rhs_row = &rhs[p * m..(p + 1) * m];
+                for (o, b) in out[i * m..(i + 1) * m].iter_mut().zip(rhs_row.iter()) {
+                    *o += a.clone() * b.clone();
+                }
+            }
+        }
+    }
+
+    /// Multiply a strided `n x k` matrix and a strided `k x m` matrix and add the result to `out`
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lhs` - The left hand side matrix
+    /// * `rhs` - The right hand side matrix
+    /// * `out` - The `n x m` output matrix in row-major order
+    /// 
+    /// # Note
+    /// 
+    /// By default, a matrix which is not row-major is packed into a row-major buffer
+    /// of the size of the matrix and passed to `matmul_kernel`.
+    fn matmul_kernel_strided(lhs: StridedMatrix<Self>, rhs: StridedMatrix<Self>, out: &mut [Self], n: usize, k: usize, m: usize) {
+        Self::matmul_kernel(&lhs.row_major(n, k), &rhs.row_major(k, m), out, n, k, m)
+    }
+}
+
+macro_rules! impl_matmul_kernel {
+    ($($t:ty),*) => {
+        $(impl MatMulKernel for $t {})*
+    };
+}
+
+impl_matmul_kernel!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(not(feature = "blas"))]
+macro_rules! impl_matmul_kernel_float {
+    ($($t:ty),*) => {
+        $(impl MatMulKernel for $t {
+            fn matmul_kernel(lhs: &[Self], rhs: &[Self], out: &mut [Self], n: usize, k: usize, m: usize) {
+                blocked_matmul(lhs, rhs, out, n, k, m)
+            }
+        })*
+    };
+}
+
+#[cfg(not(feature = "blas"))]
+impl_matmul_kernel_float!(f32, f64);
+
+#[cfg(feature = "blas")]
+macro_rules! impl_matmul_kernel_blas {
+    ($($t:ty => $gemm:path),*) => {
+        $(impl MatMulKernel for $t {
+            fn matmul_kernel(lhs: &[Self], rhs: &[Self], out: &mut [Self], n: usize, k: usize, m: usize) {
+                assert!(lhs.len() >= n * k && rhs.len() >= k * m && out.len() >= n * m);
+                // SAFETY: the slices hold the row-major matrices checked above
+                unsafe {
+                    $gemm(
+                        n, k, m,
+                        1.0,
+                        lhs.as_ptr(), k as isize, 1,
+                        rhs.as_ptr(), m as isize, 1,
+                        1.0,
+                        out.as_mut_ptr(), m as isize, 1,
+                    )
+                }
+            }
+
+            fn matmul_kernel_strided(lhs: StridedMatrix<Self>, rhs: StridedMatrix<Self>, out: &mut [Self], n: usize, k: usize, m: usize) {
+                if lhs.row_stride < 0 || lhs.col_stride < 0 || rhs.row_stride < 0 || rhs.col_stride < 0 {
+                    return Self::matmul_kernel(&lhs.row_major(n, k), &rhs.row_major(k, m), out, n, k, m)
+                }
+                let lhs_last = lhs.offset + (n - 1) * lhs.row_stride as usize + (k - 1) * lhs.col_stride as usize;
+                let rhs_last = rhs.offset + (k - 1) * rhs.row_stride as usize + (m - 1) * rhs.col_stride as usize;
+                assert!(lhs_last < lhs.data.len() && rhs_last < rhs.data.len() && out.len() >= n * m);
+                // SAFETY: every element addressed by the non-negative strides is checked above
+                unsafe {
+                    $gemm(
+                        n, k, m,
+                        1.0,
+                        lhs.data.as_ptr().add(lhs.offset), lhs.row_stride, lhs.col_stride,
+                        rhs.data.as_ptr().add(rhs.offset), rhs.row_stride, rhs.col_stride,
+                        1.0,
+                        out.as_mut_ptr(), m as isize, 1,
+                    )
+                }
+            }
+        })*
+    };
+}
+
+#[cfg(feature = "blas")]
+impl_matmul_kernel_blas!(f32 => matrixmultiply::sgemm, f64 => matrixmultiply::dgemm);
+
+/// Cache-blocked matrix multiplication
+/// 
+/// The rows of `out` are split into blocks of `BLOCK_ROWS`,
+/// and each block is computed independently.
+#[cfg(not(feature = "blas"))]
+fn blocked_matmul<T>(lhs: &[T], rhs: &[T], out: &mut [T], n: usize, k: usize, m: usize)
+where
+    T: NumAssign + Copy + Send + Sync
+{
+    debug_assert_eq!(out.len(), n * m);
+    let block = |(index, out): (usize, &mut [T])| {
+        let row_start = index * BLOCK_ROWS;
+        blocked_matmul_rows(&lhs[row_start * k..], rhs, out, k, m);
+    };
+    #[cfg(feature = "parallel")]
+    if n * k * m >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        out.par_chunks_mut(BLOCK_ROWS * m).enumerate().for_each(block);
+        return
+    }
+    out.chunks_mut(BLOCK_ROWS * m).enumerate().for_each(block);
+}
+
+/// Compute the rows of the output in `out` blocked over the inner and column dimensions
+#[cfg(not(feature = "blas"))]
+fn blocked_matmul_rows<T>(lhs: &[T], rhs: &[T], out: &mut [T], k: usize, m: usize)
+where
+    T: NumAssign + Copy
+{
+    let rows = out.len() / m;
+    for p_start in (0..k).step_by(BLOCK_INNER) {
+        let p_end = (p_start + BLOCK_INNER).min(k);
+        for j_start in (0..m).step_by(BLOCK_COLS) {
+            let j_end = (j_start + BLOCK_COLS).min(m);
+            for i in 0..rows {
+                let out_row = &mut out[i * m + j_start..i * m + j_end];
+                for p in p_start..p_end {
+                    let a = lhs[i * k + p];
+                    let rhs_row = &rhs[p * m + j_start..p * m + j_end];
+                    for (o, &b) in out_row.iter_mut().zip(rhs_row.iter()) {
+                        *o += a * b;
+                    }
                 }
             }
         }
-        Ok(Self{ data, shape: vec![self.shape[0], rhs.shape[1]] })
     }
 }
 
@@ -74,6 +347,20 @@ mod tests {
         assert_eq!(z.get_shape(), &vec![3, 2]);
     }
 
+    #[test]
+    fn matmul_blocked_float() {
+        let (n, k, m) = (67, 261, 519);
+        let x = Tensor::new((0..n * k).map(|i| (i % 7) as i64).collect::<Vec<_>>(), [n, k]).unwrap();
+        let y = Tensor::new((0..k * m).map(|i| (i % 5) as i64).collect::<Vec<_>>(), [k, m]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        let x = x.as_type::<f64>().unwrap();
+        let y = y.as_type::<f64>().unwrap();
+        assert_eq!(x.matmul(&y).unwrap(), z.clone().as_type::<f64>().unwrap());
+        let x = x.as_type::<f32>().unwrap();
+        let y = y.as_type::<f32>().unwrap();
+        assert_eq!(x.matmul(&y).unwrap(), z.as_type::<f32>().unwrap());
+    }
+
     #[test]
     fn matmul_error_shape_zero() {
         let x = Tensor::<i32>::arrange([3, 0]).unwrap();
@@ -103,7 +390,21 @@ mod tests {
     }
 
     #[test]
-    fn mutmul_error_mismatch_ndim_left() {
+    fn matmul_error_scalar() {
+        let x = Tensor::<i32>::scalar(1);
+        let y = Tensor::arrange([2, 2]).unwrap();
+        let z = x.matmul(&y);
+        match z {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionSmallerError(0, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn matmul_error_mismatch_ndim_left() {
         let x = Tensor::<i32>::arrange([3, 2, 1]).unwrap();
         let y = Tensor::arrange([2, 2]).unwrap();
         let z = x.matmul(&y);
@@ -111,22 +412,113 @@ mod tests {
             Ok(_) => panic!("error"),
             Err(e) => {
                 let e = e.downcast::<TensorError>().unwrap();
-                assert_eq!(e, TensorError::ShapeSizeError(3, 2));
+                assert_eq!(e, TensorError::ShapeError(vec![3, 2, 1], vec![2, 2]));
             }
         }
     }
 
     #[test]
-    fn mutmul_error_mismatch_ndim_right() {
-        let x = Tensor::<i32>::arrange([3, 2]).unwrap();
-        let y = Tensor::arrange([2, 2, 1]).unwrap();
+    fn matmul_batched() {
+        let x = Tensor::<i32>::arrange([2, 2, 3]).unwrap();
+        let y = Tensor::arrange([2, 3, 1]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 2, 1]);
+        assert_eq!(z.get_data(), &vec![5, 14, 86, 122]);
+    }
+
+    #[test]
+    fn matmul_broadcast_batch() {
+        let x = Tensor::<i32>::arrange([2, 1, 2, 3]).unwrap();
+        let y = Tensor::arrange([3, 3, 2]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 3, 2, 2]);
+        for i in 0..2 {
+            for j in 0..3 {
+                let a = Tensor::new(x.get_data()[i * 6..(i + 1) * 6].to_vec(), [2, 3]).unwrap();
+                let b = Tensor::new(y.get_data()[j * 6..(j + 1) * 6].to_vec(), [3, 2]).unwrap();
+                let offset = (i * 3 + j) * 4;
+                assert_eq!(&z.get_data()[offset..offset + 4], a.matmul(&b).unwrap().get_data().as_slice());
+            }
+        }
+    }
+
+    #[test]
+    fn matmul_batch_with_matrix() {
+        let x = Tensor::<i32>::arrange([2, 2, 3]).unwrap();
+        let y = Tensor::arrange([3, 1]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 2, 1]);
+        assert_eq!(z.get_data(), &vec![5, 14, 23, 32]);
+    }
+
+    #[test]
+    fn matmul_vector_vector() {
+        let x = Tensor::<i32>::arrange([3]).unwrap();
+        let y = Tensor::new([1, 2, 3], [3]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![]);
+        assert_eq!(z.get_data(), &vec![8]);
+    }
+
+    #[test]
+    fn matmul_vector_matrix() {
+        let x = Tensor::<i32>::arrange([2]).unwrap();
+        let y = Tensor::arrange([2, 2, 3]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2, 3]);
+        assert_eq!(z.get_data(), &vec![3, 4, 5, 9, 10, 11]);
+    }
+
+    #[test]
+    fn matmul_matrix_vector() {
+        let x = Tensor::<i32>::arrange([2, 3]).unwrap();
+        let y = Tensor::arrange([3]).unwrap();
+        let z = x.matmul(&y).unwrap();
+        assert_eq!(z.get_shape(), &vec![2]);
+        assert_eq!(z.get_data(), &vec![5, 14]);
+    }
+
+    #[test]
+    fn matmul_error_mismatch_batch() {
+        let x = Tensor::<i32>::arrange([2, 2, 3]).unwrap();
+        let y = Tensor::arrange([3, 3, 1]).unwrap();
         let z = x.matmul(&y);
         match z {
             Ok(_) => panic!("error"),
             Err(e) => {
                 let e = e.downcast::<TensorError>().unwrap();
-                assert_eq!(e, TensorError::ShapeSizeError(3, 2));
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2, 2, 3], vec![3, 3, 1]));
             }
         }
     }
+
+    #[test]
+    fn matmul_transposed_views() {
+        let x = Tensor::<f64>::arrange([3, 2]).unwrap();
+        let y = Tensor::<f64>::arrange([4, 2]).unwrap();
+        let expected = x.matmul(&y.transpose().contiguous()).unwrap();
+        assert_eq!(x.matmul(&y.transpose()).unwrap(), expected);
+        assert_eq!(x.transpose().transpose().matmul(&y.transpose()).unwrap(), expected);
+        let expected = x.transpose().contiguous().matmul(&x).unwrap();
+        assert_eq!(x.transpose().matmul(&x).unwrap(), expected);
+        let z = Tensor::<i32>::arrange([2, 3, 2]).unwrap();
+        let w = Tensor::<i32>::arrange([2, 3]).unwrap();
+        let expected = z.swapaxes(1, 2).unwrap().contiguous().matmul(&w.transpose().contiguous()).unwrap();
+        assert_eq!(z.swapaxes(1, 2).unwrap().matmul(&w.transpose()).unwrap(), expected);
+    }
+
+    #[test]
+    fn matmul_broadcast_and_sliced_views() {
+        let x = Tensor::<i32>::arrange([2, 3]).unwrap();
+        let y = Tensor::<i32>::arrange([4, 3, 2]).unwrap();
+        let expected = x.broadcast_to([4, 2, 3]).unwrap().contiguous().matmul(&y).unwrap();
+        assert_eq!(x.broadcast_to([4, 2, 3]).unwrap().matmul(&y).unwrap(), expected);
+        let v = Tensor::<i32>::arrange([6]).unwrap();
+        let head = v.view().slice_axis(0, 0, 3).unwrap();
+        let tail = v.view().slice_axis(0, 3, 6).unwrap();
+        // [0, 1, 2] . [3, 4, 5]
+        assert_eq!(head.matmul(&tail).unwrap(), Tensor::scalar(14));
+        let odd = v.reshape([3, 2]).unwrap().transpose().slice_with_one_index(1).unwrap();
+        assert_eq!(x.matmul(&odd).unwrap(), x.matmul(&odd.contiguous()).unwrap());
+    }
 }
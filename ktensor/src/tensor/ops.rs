@@ -1,57 +1,110 @@
+use std::sync::Arc;
+use anyhow::Result;
 use super::Tensor;
-
-impl<T> Tensor<T>
-{
-    fn ops(self, rhs: Self, f: fn(T, T) -> T) -> Self {
-        assert_eq!(self.shape, rhs.shape, "Shape mismatch: left: {:?}, right: {:?}", self.shape, rhs.shape);
-        let data =
-            self.data
-            .into_iter()
-            .zip(rhs.data.into_iter())
-            .map(|(x, y)| f(x, y))
-            .collect();
-        Self { data, shape: self.shape }
-    }
-}
+use crate::error::TensorError;
 
 impl<T> Tensor<T>
 where
     T: Clone
 {
-    fn ref_ops(&self, rhs: &Self, f: fn(T, T) -> T) -> Self {
-        assert_eq!(self.shape, rhs.shape, "Shape mismatch: left: {:?}, right: {:?}", self.shape, rhs.shape);
+    fn ops(self, rhs: Self, f: fn(T, T) -> T) -> Result<Self> {
+        if self.shape != rhs.shape {
+            return self.ref_ops(&rhs, f)
+        }
+        let shape = self.shape.clone();
         let data =
-            self.data
-            .iter()
-            .zip(rhs.data.iter())
-            .map(|(x, y)| f(x.clone(), y.clone()))
+            self.into_data()
+            .into_iter()
+            .zip(rhs.into_data().into_iter())
+            .map(|(x, y)| f(x, y))
             .collect();
-        Self { data, shape: self.shape.clone() }
+        Ok(Self { data: Arc::new(data), shape })
+    }
+
+    fn ref_ops(&self, rhs: &Self, f: fn(T, T) -> T) -> Result<Self> {
+        if self.shape == rhs.shape {
+            let data =
+                self.data
+                .iter()
+                .zip(rhs.data.iter())
+                .map(|(x, y)| f(x.clone(), y.clone()))
+                .collect();
+            return Ok(Self { data: Arc::new(data), shape: self.shape.clone() })
+        }
+        let shape = Self::broadcast_shape(&self.shape, &rhs.shape)?;
+        let lhs_strides = Self::broadcast_strides(&self.shape, &shape);
+        let rhs_strides = Self::broadcast_strides(&rhs.shape, &shape);
+        let size = shape.iter().product();
+        let mut data = Vec::with_capacity(size);
+        let mut index = vec![0; shape.len()];
+        let mut lhs_index = 0;
+        let mut rhs_index = 0;
+        for _ in 0..size {
+            data.push(f(self.data[lhs_index].clone(), rhs.data[rhs_index].clone()));
+            for i in (0..shape.len()).rev() {
+                index[i] += 1;
+                lhs_index += lhs_strides[i];
+                rhs_index += rhs_strides[i];
+                if index[i] < shape[i] {
+                    break;
+                }
+                index[i] = 0;
+                lhs_index -= lhs_strides[i] * shape[i];
+                rhs_index -= rhs_strides[i] * shape[i];
+            }
+        }
+        Ok(Self { data: Arc::new(data), shape })
     }
 
-    fn assign_ops(&mut self, rhs: &Self, f: fn(&mut T, T) -> ()) {
-        assert_eq!(self.shape, rhs.shape, "Shape mismatch: left: {:?}, right: {:?}", self.shape, rhs.shape);
-        for (x, y) in self.data.iter_mut().zip(rhs.data.iter()) {
-            f(x, y.clone());
+    fn assign_ops(&mut self, rhs: &Self, f: fn(&mut T, T) -> ()) -> Result<()> {
+        if self.shape == rhs.shape {
+            for (x, y) in self.data_mut().iter_mut().zip(rhs.data.iter()) {
+                f(x, y.clone());
+            }
+            return Ok(())
         }
+        let shape = Self::broadcast_shape(&self.shape, &rhs.shape)?;
+        if shape != self.shape {
+            return Err(TensorError::ShapeMismatchError(
+                self.shape.clone(), rhs.shape.clone()
+            ).into())
+        }
+        let rhs_strides = Self::broadcast_strides(&rhs.shape, &shape);
+        let mut index = vec![0; shape.len()];
+        let mut rhs_index = 0;
+        for x in self.data_mut().iter_mut() {
+            f(x, rhs.data[rhs_index].clone());
+            for i in (0..shape.len()).rev() {
+                index[i] += 1;
+                rhs_index += rhs_strides[i];
+                if index[i] < shape[i] {
+                    break;
+                }
+                index[i] = 0;
+                rhs_index -= rhs_strides[i] * shape[i];
+            }
+        }
+        Ok(())
     }
 
     fn scalar_right_ops(self, rhs: T, f: fn(T, T) -> T) -> Self {
+        let shape = self.shape.clone();
         let data =
-            self.data
+            self.into_data()
             .into_iter()
             .map(|x| f(x, rhs.clone()))
             .collect();
-        Self { data, shape: self.shape }
+        Self { data: Arc::new(data), shape }
     }
 
     fn scalar_left_ops(self, lhs: T, f: fn(T, T) -> T) -> Self {
+        let shape = self.shape.clone();
         let data =
-            self.data
+            self.into_data()
             .into_iter()
             .map(|x| f(lhs.clone(), x))
             .collect();
-        Self { data, shape: self.shape }
+        Self { data: Arc::new(data), shape }
     }
 
     fn scalar_right_ref_ops(&self, rhs: &T, f: fn(T, T) -> T) -> Self {
@@ -60,7 +113,7 @@ where
             .iter()
             .map(|x| f(x.clone(), rhs.clone()))
             .collect();
-        Self { data, shape: self.shape.clone() }
+        Self { data: Arc::new(data), shape: self.shape.clone() }
     }
 
     fn scalar_left_ref_ops(&self, lhs: &T, f: fn(T, T) -> T) -> Self {
@@ -69,18 +122,18 @@ where
             .iter()
             .map(|x| f(lhs.clone(), x.clone()))
             .collect();
-        Self { data, shape: self.shape.clone() }
+        Self { data: Arc::new(data), shape: self.shape.clone() }
     }
 }
 
 impl<T> std::ops::Add for Tensor<T>
 where
-    T: std::ops::Add<Output = T>
+    T: std::ops::Add<Output = T> + Clone
 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        self.ops(rhs, |x, y| x + y)
+        self.ops(rhs, |x, y| x + y).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -91,18 +144,40 @@ where
     type Output = Tensor<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::Add<Output = T> + Clone
+{
+    /// Add two tensors with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn try_add(&self, rhs: &Self) -> Result<Self> {
         self.ref_ops(rhs, |x, y| x + y)
     }
 }
 
 impl<T> std::ops::Sub for Tensor<T>
 where
-    T: std::ops::Sub<Output = T>
+    T: std::ops::Sub<Output = T> + Clone
 {
     type Output = Self;
 
-    fn sub(self, rhs: Self) -> Self {
-        self.ops(rhs, |x, y| x - y)
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.ops(rhs, |x, y| x - y).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -113,18 +188,40 @@ where
     type Output = Tensor<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::Sub<Output = T> + Clone
+{
+    /// Subtract two tensors with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn try_sub(&self, rhs: &Self) -> Result<Self> {
         self.ref_ops(rhs, |x, y| x - y)
     }
 }
 
 impl<T> std::ops::Mul for Tensor<T>
 where
-    T: std::ops::Mul<Output = T>
+    T: std::ops::Mul<Output = T> + Clone
 {
     type Output = Self;
 
-    fn mul(self, rhs: Self) -> Self {
-        self.ops(rhs, |x, y| x * y)
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.ops(rhs, |x, y| x * y).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -135,18 +232,40 @@ where
     type Output = Tensor<T>;
 
     fn mul(self, rhs: Self) -> Self::Output {
+        self.try_mul(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::Mul<Output = T> + Clone
+{
+    /// Multiply two tensors with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self> {
         self.ref_ops(rhs, |x, y| x * y)
     }
 }
 
 impl<T> std::ops::Div for Tensor<T>
 where
-    T: std::ops::Div<Output = T>
+    T: std::ops::Div<Output = T> + Clone
 {
     type Output = Self;
 
-    fn div(self, rhs: Self) -> Self {
-        self.ops(rhs, |x, y| x / y)
+    fn div(self, rhs: Self) -> Self::Output {
+        self.ops(rhs, |x, y| x / y).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -157,18 +276,40 @@ where
     type Output = Tensor<T>;
 
     fn div(self, rhs: Self) -> Self::Output {
+        self.try_div(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::Div<Output = T> + Clone
+{
+    /// Divide two tensors with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn try_div(&self, rhs: &Self) -> Result<Self> {
         self.ref_ops(rhs, |x, y| x / y)
     }
 }
 
 impl<T> std::ops::Rem for Tensor<T>
 where
-    T: std::ops::Rem<Output = T>
+    T: std::ops::Rem<Output = T> + Clone
 {
     type Output = Self;
 
-    fn rem(self, rhs: Self) -> Self {
-        self.ops(rhs, |x, y| x % y)
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.ops(rhs, |x, y| x % y).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -179,19 +320,42 @@ where
     type Output = Tensor<T>;
 
     fn rem(self, rhs: Self) -> Self::Output {
+        self.try_rem(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::Rem<Output = T> + Clone
+{
+    /// Calculate the remainder of two tensors with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub fn try_rem(&self, rhs: &Self) -> Result<Self> {
         self.ref_ops(rhs, |x, y| x % y)
     }
 }
 
 impl<T> std::ops::Neg for Tensor<T>
 where
-    T: std::ops::Neg<Output = T>
+    T: std::ops::Neg<Output = T> + Clone
 {
     type Output = Self;
 
     fn neg(self) -> Self {
-        let data = self.data.into_iter().map(|x| -x).collect();
-        Self { data, shape: self.shape }
+        let shape = self.shape.clone();
+        let data = self.into_data().into_iter().map(|x| -x).collect();
+        Self { data: Arc::new(data), shape }
     }
 }
 
@@ -203,7 +367,7 @@ where
 
     fn neg(self) -> Self::Output {
         let data = self.data.iter().map(|x| -x.clone()).collect();
-        Tensor { data, shape: self.shape.clone() }
+        Tensor { data: Arc::new(data), shape: self.shape.clone() }
     }
 }
 
@@ -212,6 +376,28 @@ where
     T: std::ops::AddAssign + Clone
 {
     fn add_assign(&mut self, other: &Self) {
+        self.try_add_assign(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::AddAssign + Clone
+{
+    /// Add the other tensor in place with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The tensor broadcast to the shape of `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<()>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If `other` cannot be broadcast to the shape of `self`, `TensorError::ShapeMismatchError` is returned
+    pub fn try_add_assign(&mut self, other: &Self) -> Result<()> {
         self.assign_ops(other, |x, y| { *x += y; })
     }
 }
@@ -221,6 +407,28 @@ where
     T: std::ops::SubAssign + Clone
 {
     fn sub_assign(&mut self, other: &Self) {
+        self.try_sub_assign(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::SubAssign + Clone
+{
+    /// Subtract the other tensor in place with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The tensor broadcast to the shape of `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<()>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If `other` cannot be broadcast to the shape of `self`, `TensorError::ShapeMismatchError` is returned
+    pub fn try_sub_assign(&mut self, other: &Self) -> Result<()> {
         self.assign_ops(other, |x, y| { *x -= y; })
     }
 }
@@ -230,6 +438,28 @@ where
     T: std::ops::MulAssign + Clone
 {
     fn mul_assign(&mut self, other: &Self) {
+        self.try_mul_assign(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::MulAssign + Clone
+{
+    /// Multiply the other tensor in place with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The tensor broadcast to the shape of `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<()>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If `other` cannot be broadcast to the shape of `self`, `TensorError::ShapeMismatchError` is returned
+    pub fn try_mul_assign(&mut self, other: &Self) -> Result<()> {
         self.assign_ops(other, |x, y| { *x *= y; })
     }
 }
@@ -239,6 +469,28 @@ where
     T: std::ops::DivAssign + Clone
 {
     fn div_assign(&mut self, other: &Self) {
+        self.try_div_assign(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::DivAssign + Clone
+{
+    /// Divide the other tensor in place with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The tensor broadcast to the shape of `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<()>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If `other` cannot be broadcast to the shape of `self`, `TensorError::ShapeMismatchError` is returned
+    pub fn try_div_assign(&mut self, other: &Self) -> Result<()> {
         self.assign_ops(other, |x, y| { *x /= y; })
     }
 }
@@ -248,6 +500,28 @@ where
     T: std::ops::RemAssign + Clone
 {
     fn rem_assign(&mut self, other: &Self) {
+        self.try_rem_assign(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: std::ops::RemAssign + Clone
+{
+    /// Calculate the remainder of the other tensor in place with broadcasting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The tensor broadcast to the shape of `self`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<()>` - Result of the operation
+    /// 
+    /// # Note
+    /// 
+    /// If `other` cannot be broadcast to the shape of `self`, `TensorError::ShapeMismatchError` is returned
+    pub fn try_rem_assign(&mut self, other: &Self) -> Result<()> {
         self.assign_ops(other, |x, y| { *x %= y; })
     }
 }
@@ -605,6 +879,46 @@ mod tests {
         let _ = &x + &y;
     }
 
+    #[test]
+    fn add_broadcast() {
+        let x = Tensor::new([0, 1, 2, 3, 4, 5], [2, 3]).unwrap();
+        let y = Tensor::new([10, 20, 30], [3]).unwrap();
+        let z = x + y;
+        assert_eq!(z.get_data(), &vec![10, 21, 32, 13, 24, 35]);
+        assert_eq!(z.get_shape(), &vec![2, 3]);
+    }
+
+    #[test]
+    fn add_reference_broadcast_both() {
+        let x = Tensor::new([0, 1], [2, 1]).unwrap();
+        let y = Tensor::new([10, 20, 30], [1, 3]).unwrap();
+        let z = &x + &y;
+        assert_eq!(z.get_data(), &vec![10, 20, 30, 11, 21, 31]);
+        assert_eq!(z.get_shape(), &vec![2, 3]);
+    }
+
+    #[test]
+    fn add_broadcast_scalar_tensor() {
+        let x = Tensor::new([5], []).unwrap();
+        let y = Tensor::new([0, 1, 2, 3], [2, 1, 2]).unwrap();
+        let z = &x + &y;
+        assert_eq!(z.get_data(), &vec![5, 6, 7, 8]);
+        assert_eq!(z.get_shape(), &vec![2, 1, 2]);
+    }
+
+    #[test]
+    fn try_add_error_mismatch_shape() {
+        let x = Tensor::new([0, 1, 2, 3, 4, 5], [2, 3]).unwrap();
+        let y = Tensor::new([2, 3, 4, 5, 6, 7], [3, 2]).unwrap();
+        match x.try_add(&y) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2, 3], vec![3, 2]));
+            }
+        }
+    }
+
     #[test]
     fn sub_normal() {
         let x = Tensor::new([0, 1, 2, 3, 4, 5], [2, 3]).unwrap();
@@ -741,6 +1055,42 @@ mod tests {
         let _ = &x % &y;
     }
 
+    #[test]
+    fn sub_broadcast() {
+        let x = Tensor::new([0, 1, 2, 3, 4, 5], [2, 3]).unwrap();
+        let y = Tensor::new([1, 2], [2, 1]).unwrap();
+        let z = x - y;
+        assert_eq!(z.get_data(), &vec![-1, 0, 1, 1, 2, 3]);
+        assert_eq!(z.get_shape(), &vec![2, 3]);
+    }
+
+    #[test]
+    fn mul_broadcast() {
+        let x = Tensor::new([0, 1, 2, 3, 4, 5], [3, 1, 2]).unwrap();
+        let y = Tensor::new([1, -1], [2, 1]).unwrap();
+        let z = &x * &y;
+        assert_eq!(z.get_data(), &vec![0, 1, 0, -1, 2, 3, -2, -3, 4, 5, -4, -5]);
+        assert_eq!(z.get_shape(), &vec![3, 2, 2]);
+    }
+
+    #[test]
+    fn div_broadcast() {
+        let x = Tensor::new([2, 4, 6, 8], [2, 2]).unwrap();
+        let y = Tensor::new([2], [1]).unwrap();
+        let z = x.try_div(&y).unwrap();
+        assert_eq!(z.get_data(), &vec![1, 2, 3, 4]);
+        assert_eq!(z.get_shape(), &vec![2, 2]);
+    }
+
+    #[test]
+    fn rem_broadcast() {
+        let x = Tensor::new([3], []).unwrap();
+        let y = Tensor::new([1, 2, 3, 4], [4]).unwrap();
+        let z = x % y;
+        assert_eq!(z.get_data(), &vec![0, 1, 0, 3]);
+        assert_eq!(z.get_shape(), &vec![4]);
+    }
+
     #[test]
     fn neg_normal() {
         let x = Tensor::new([0, 1, 2, 3, 4, 5], [3, 2]).unwrap();
@@ -834,6 +1184,28 @@ mod tests {
         x %= &y;
     }
 
+    #[test]
+    fn add_assign_broadcast() {
+        let mut x = Tensor::new([0, 1, 2, 3, 4, 5], [3, 2]).unwrap();
+        let y = Tensor::new([10, 20], [2]).unwrap();
+        x += &y;
+        assert_eq!(x.get_data(), &vec![10, 21, 12, 23, 14, 25]);
+        assert_eq!(x.get_shape(), &vec![3, 2]);
+    }
+
+    #[test]
+    fn try_mul_assign_error_broadcast_self() {
+        let mut x = Tensor::new([1, 2], [2]).unwrap();
+        let y = Tensor::new([0, 1, 2, 3, 4, 5], [3, 2]).unwrap();
+        match x.try_mul_assign(&y) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2], vec![3, 2]));
+            }
+        }
+    }
+
     #[test]
     fn add_scalar_normal() {
         let x = Tensor::new([0, 1, 2, 3, 4, 5], [3, 2]).unwrap();
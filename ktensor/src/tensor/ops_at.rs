@@ -1,5 +1,6 @@
 use anyhow::Result;
 use super::Tensor;
+use super::slice_elem::SliceElem;
 use crate::error::TensorError;
 
 impl<T> Tensor<T>
@@ -23,7 +24,7 @@ where
         }
         let shape = self.shape.clone();
         let size = rhs.size();
-        let mut data = self.data.clone();
+        let mut data = self.data.to_vec();
         for i in 0..size {
             data[index * size + i] = f(data[index * size + i].clone(), rhs.data[i].clone());
         }
@@ -52,7 +53,7 @@ where
         }
         let shape = self.shape.clone();
         let size = rhs.size() / rhs.shape[0];
-        let mut data = self.data.clone();
+        let mut data = self.data.to_vec();
         for (i, index) in indexes.iter().enumerate() {
             for j in 0..size {
                 data[index * size + j] = f(
@@ -106,7 +107,7 @@ where
         }
         let shape = self.shape.clone();
         let size = rhs.size() / rhs.shape[0];
-        let mut data = self.data.clone();
+        let mut data = self.data.to_vec();
         let zip_indexes = (0..len)
             .map(|i| indexes.iter().map(|v| v[i])
             .collect::<Vec<_>>());
@@ -122,6 +123,18 @@ where
         }
         Tensor::new(data, shape)
     }
+
+    fn ops_at_slice(&self, rhs: &Self, f: fn (T, T) -> T, elems: &[SliceElem]) -> Result<Self> {
+        let (shape, positions) = self.slice_positions(elems)?;
+        if shape != rhs.shape {
+            return Err(TensorError::ShapeError(shape, rhs.shape.clone()).into())
+        }
+        let mut data = self.data.to_vec();
+        for (position, value) in positions.into_iter().zip(rhs.data.iter()) {
+            data[position] = f(data[position].clone(), value.clone());
+        }
+        Tensor::new(data, self.shape.clone())
+    }
 }
 
 impl<T> Tensor<T>
@@ -190,6 +203,27 @@ where
     pub fn add_at_with_indexes(&self, rhs: &Self, indexes: Vec<Vec<usize>>) -> Result<Self> {
         self.ops_at_with_indexes(rhs, |x, y| x + y, indexes)
     }
+
+    /// Add a tensor at the slice
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The tensor to add, which has the shape of the slice
+    /// * `elems` - The slice elements like `Tensor::slice`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - Result of the addition
+    /// 
+    /// # Note
+    /// 
+    /// The values at the indexes selected several times by index arrays are accumulated.
+    /// 
+    /// If the shape of `rhs` is not the shape of the slice, `TensorError::ShapeError` is returned
+    /// If the slice is not correct, the errors of `Tensor::slice` are returned
+    pub fn add_at_slice<U: AsRef<[SliceElem]>>(&self, rhs: &Self, elems: U) -> Result<Self> {
+        self.ops_at_slice(rhs, |x, y| x + y, elems.as_ref())
+    }
 }
 
 #[cfg(test)]
@@ -444,4 +478,35 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn add_at_slice_accumulate() -> Result<()> {
+        let x = Tensor::<f64>::zeros([3, 2]);
+        let y = Tensor::<f64>::ones([3, 2]);
+        let z = x.add_at_slice(&y, crate::s![[0, 2, 0]])?;
+        assert_eq!(z.get_data(), &vec![2.0, 2.0, 0.0, 0.0, 1.0, 1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn add_at_slice_range() -> Result<()> {
+        let x = Tensor::<f64>::zeros([2, 4]);
+        let y = Tensor::<f64>::arrange([2, 2])?;
+        let z = x.add_at_slice(&y, crate::s![.., 1..;2])?;
+        assert_eq!(z.get_data(), &vec![0.0, 0.0, 0.0, 1.0, 0.0, 2.0, 0.0, 3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn add_at_slice_error_shape() {
+        let x = Tensor::<f64>::zeros([2, 4]);
+        let y = Tensor::<f64>::ones([2, 4]);
+        match x.add_at_slice(&y, crate::s![0]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![4], vec![2, 4]));
+            }
+        }
+    }
 }
@@ -1,6 +1,8 @@
+use std::sync::Arc;
 use anyhow::Result;
 use rand::seq::SliceRandom;
-use rand::{Rng, SeedableRng, rngs::StdRng};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use rand::distributions::{Distribution, Standard};
 use rand_distr::{Normal, StandardNormal};
 use num_traits::Float;
@@ -12,22 +14,92 @@ use crate::error::TensorError;
 /// # Fields
 /// 
 /// * `rng` - Random number generator.
+/// 
+/// # Note
+/// 
+/// The generator is ChaCha12, the same algorithm as `rand::rngs::StdRng`,
+/// so that its position can be saved with `state` and restored with `from_state`.
 pub struct TensorRng {
-    rng: StdRng,
+    rng: ChaCha12Rng,
+}
+
+/// Snapshot of a `TensorRng`
+/// 
+/// # Fields
+/// 
+/// * `seed` - The key of the generator
+/// * `stream` - The stream of the generator
+/// * `word_pos` - The number of 32-bit words already generated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TensorRngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+impl TensorRngState {
+    /// Encode the state into a tensor of shape `[7]` to store it with other tensors
+    pub fn to_tensor(&self) -> Tensor<u64> {
+        let mut data = self.seed.chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        data.push(self.stream);
+        data.push(self.word_pos as u64);
+        data.push((self.word_pos >> 64) as u64);
+        Tensor { data: Arc::new(data), shape: vec![7] }
+    }
+
+    /// Decode the state from a tensor created by `to_tensor`
+    /// 
+    /// # Note
+    /// 
+    /// If the shape of the tensor is not `[7]`, `TensorError::ShapeMismatchError` is returned.
+    pub fn from_tensor(tensor: &Tensor<u64>) -> Result<Self> {
+        if tensor.shape != [7] {
+            return Err(TensorError::ShapeMismatchError(tensor.shape.clone(), vec![7]).into())
+        }
+        let data = &tensor.data;
+        let mut seed = [0u8; 32];
+        for (i, word) in data[..4].iter().enumerate() {
+            seed[i * 8..(i + 1) * 8].copy_from_slice(&word.to_le_bytes());
+        }
+        Ok(Self {
+            seed,
+            stream: data[4],
+            word_pos: data[5] as u128 | (data[6] as u128) << 64,
+        })
+    }
 }
 
 impl TensorRng {
     /// Create a new TensorRng.
     pub fn new() -> Self {
         Self {
-            rng: StdRng::from_entropy(),
+            rng: ChaCha12Rng::from_entropy(),
         }
     }
 
     /// Create a new TensorRng with seed.
     pub fn new_from_seed(seed: u64) -> Self {
         Self {
-            rng: StdRng::seed_from_u64(seed),
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Create a TensorRng which continues from the state.
+    pub fn from_state(state: &TensorRngState) -> Self {
+        let mut rng = ChaCha12Rng::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        Self { rng }
+    }
+
+    /// Get the current state to resume the sequence later.
+    pub fn state(&self) -> TensorRngState {
+        TensorRngState {
+            seed: self.rng.get_seed(),
+            stream: self.rng.get_stream(),
+            word_pos: self.rng.get_word_pos(),
         }
     }
 
@@ -48,7 +120,7 @@ impl TensorRng {
         for i in 0..size {
             data[i] = self.rng.gen::<T>();
         }
-        Tensor { data, shape: shape }
+        Tensor { data: Arc::new(data), shape: shape }
     }
 
     /// Generate a random Tensor with normal distribution.
@@ -72,7 +144,7 @@ impl TensorRng {
         for i in 0..size {
             data[i] = self.rng.sample(normal).into();
         }
-        Ok(Tensor { data, shape: shape })
+        Ok(Tensor { data: Arc::new(data), shape: shape })
     }
 
     /// Generate a random Tensor with standard normal distribution.
@@ -125,6 +197,28 @@ mod tests {
         assert_eq!(x0, x1);
     }
 
+    #[test]
+    fn state_resume() {
+        let mut rng = TensorRng::new();
+        let _ = rng.gen::<f32, _>([5]);
+        let state = rng.state();
+        let x0 = rng.normal([3], 0.0f64, 1.0).unwrap();
+        let p0 = rng.permutation(10);
+        let mut rng = TensorRng::from_state(&state);
+        assert_eq!(rng.normal([3], 0.0f64, 1.0).unwrap(), x0);
+        assert_eq!(rng.permutation(10), p0);
+    }
+
+    #[test]
+    fn state_to_tensor() {
+        let mut rng = TensorRng::new_from_seed(3);
+        let _ = rng.gen::<u64, _>([3]);
+        let state = rng.state();
+        let tensor = state.to_tensor();
+        assert_eq!(tensor.get_shape(), &[7]);
+        assert_eq!(TensorRngState::from_tensor(&tensor).unwrap(), state);
+    }
+
     #[test]
     fn normal_normal() {
         let mut rng = TensorRng::new();
@@ -1,6 +1,5 @@
 use anyhow::Result;
-use super::Tensor;
-use crate::error::TensorError;
+use super::{Tensor, TensorView};
 
 impl<T> Tensor<T>
 where
@@ -18,6 +17,7 @@ where
     /// 
     /// # Note
     /// 
+    /// The data is shared with the original tensor without copying.
     /// If the shape is not correct, `TensorError::ShapeError` is returned
     pub fn reshape<U: Into<Vec<usize>>>(&self, shape: U) -> Result<Self> {
         let shape = shape.into();
@@ -28,45 +28,70 @@ where
         })
     }
 
-    /// Transpose the tensor
+    /// Flatten the tensor
     /// 
     /// # Returns
     /// 
-    /// * `Self` - Transposed tensor
-    pub fn transpose(&self) -> Self {
-        let mut shape = self.shape.clone();
-        shape.reverse();
-        let mut new_tensor = Self::new(
-            self.data.clone(), shape
-        ).unwrap();
-
-        let mut index = vec![0; self.ndim()];
-        for _ in 0..self.data.len() {
-            let value = self.at(&index).unwrap();
-            let mut new_index = index.clone();
-            new_index.reverse();
-            *new_tensor.at_mut(&new_index).unwrap() = value.clone();
-
-            for j in 0..self.ndim() {
-                index[j] += 1;
-                if index[j] < self.shape[j] {
-                    break;
-                }
-                index[j] = 0;
-            }
+    /// * `Self` - Flattened tensor
+    pub fn flatten(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            shape: vec![self.data.len()],
         }
-        new_tensor
     }
+}
 
-    /// Flatten the tensor
+impl<T> Tensor<T>
+{
+    /// Transpose the tensor
     /// 
     /// # Returns
     /// 
-    /// * `Self` - Flattened tensor
-    pub fn flatten(&self) -> Self {
-        Self::new(
-            self.data.clone(), vec![self.data.len()]
-        ).unwrap()
+    /// * `TensorView<T>` - The view with the axes reversed
+    /// 
+    /// # Note
+    /// 
+    /// The data is shared without copying. Use `contiguous` to materialize the view.
+    pub fn transpose(&self) -> TensorView<T> {
+        self.view().transpose()
+    }
+
+    /// Permute the axes of the tensor
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axes` - The new order of the axes
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<TensorView<T>>` - The view whose i-th axis is the `axes[i]`-th axis of the original
+    /// 
+    /// # Note
+    /// 
+    /// The data is shared without copying. Use `contiguous` to materialize the view.
+    /// If the length of `axes` is not the number of dimensions, `TensorError::DimensionError` is returned.
+    /// If `axes` is not a permutation, `TensorError::InvalidArgumentError` is returned.
+    pub fn permute<U: AsRef<[usize]>>(&self, axes: U) -> Result<TensorView<T>> {
+        self.view().permute(axes)
+    }
+
+    /// Swap two axes of the tensor
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis0` - The first axis
+    /// * `axis1` - The second axis
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<TensorView<T>>` - The view with the two axes swapped
+    /// 
+    /// # Note
+    /// 
+    /// The data is shared without copying. Use `contiguous` to materialize the view.
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn swapaxes(&self, axis0: usize, axis1: usize) -> Result<TensorView<T>> {
+        self.view().swapaxes(axis0, axis1)
     }
 
     /// Broadcast the tensor
@@ -77,77 +102,24 @@ where
     /// 
     /// # Returns
     /// 
-    /// * `Result<Self>` - Result of the broadcast
+    /// * `Result<TensorView<T>>` - Result of the broadcast
     /// 
     /// # Note
     /// 
+    /// The data is shared without copying, and the broadcast axes have the stride 0.
+    /// Use `contiguous` to materialize the view.
     /// If the shape is not correct, following errors are returned:
     /// 
     /// * `TensorError::ShapeError` - If the shape is not correct
     /// * `TensorError::ShapeSizeError` - If the shape size is not correct
-    pub fn broadcast_to<U: Into<Vec<usize>>>(&self, shape: U) -> Result<Self> {
-        fn in_zero(shape: &Vec<usize>) -> bool {
-            for i in shape {
-                if *i == 0 {
-                    return true
-                }
-            }
-            false
-        }
-
-        fn check_shape(shape: &Vec<usize>, target_shape: &Vec<usize>) -> Result<()> {
-            if in_zero(shape) || in_zero(target_shape) {
-                return Err(TensorError::ShapeError(
-                    shape.clone(), target_shape.clone()
-                ).into())
-            }
-            if target_shape.len() < shape.len() {
-                return Err(TensorError::ShapeSizeError(
-                    shape.len(), target_shape.len()
-                ).into())
-            }
-            Ok(())
-        }
-
-        fn update_data<V: Clone>(data: Vec<V>, shape: &Vec<usize>,
-                                 target_index: usize, size: usize) -> (Vec<V>, usize) {
-            let mut new_data = Vec::new();
-            let mut index = 0;
-            while index < data.len() {
-                for _ in 0..shape[target_index] {
-                    new_data.extend(data[index..index + size].iter().map(|x| x.clone()))
-                }
-                index += size;
-            }
-            (new_data, size * shape[target_index])
-        }
-
-        let shape = shape.into();
-        check_shape(&self.shape, &shape)?;
-        let mut self_shape= vec![1; shape.len() - self.ndim()];
-        self_shape.extend(self.get_shape().iter());
-
-        let mut data = self.data.clone();
-        let mut size = 1;
-        for i in (0..self_shape.len()).rev() {
-            if shape[i] == self_shape[i] {
-                size *= shape[i];
-                continue;
-            }
-            if self_shape[i] != 1 {
-                return Err(TensorError::ShapeError(
-                    self.shape.clone(), shape
-                ).into())
-            }
-            (data, size) = update_data(data, &shape, i, size);
-        }
-
-        Ok(Self { data, shape: shape.to_vec() })
+    pub fn broadcast_to<U: Into<Vec<usize>>>(&self, shape: U) -> Result<TensorView<T>> {
+        self.view().broadcast_to(shape)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
     use super::*;
     use crate::error::TensorError;
 
@@ -183,7 +155,7 @@ mod tests {
     #[test]
     fn transpose_normal() {
         let x = Tensor::new([0.0, 1.0, 2.0, 3.0, 4.0, 5.0], [2, 3]).unwrap();
-        let x = x.transpose();
+        let x = x.transpose().contiguous();
         assert_eq!(x.get_shape(), &[3, 2]);
         assert_eq!(x.get_data(), &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
     }
@@ -191,7 +163,7 @@ mod tests {
     #[test]
     fn transpose_zero_dim() {
         let x = Tensor::new([0.0], []).unwrap();
-        let x = x.transpose();
+        let x = x.transpose().contiguous();
         assert_eq!(x.get_shape(), &[]);
         assert_eq!(x.get_data(), &[0.0]);
     }
@@ -199,11 +171,68 @@ mod tests {
     #[test]
     fn transpose_1d() {
         let x = Tensor::new([0.0, 1.0, 2.0], [3,]).unwrap();
-        let x = x.transpose();
+        let x = x.transpose().contiguous();
         assert_eq!(x.get_shape(), &[3]);
         assert_eq!(x.get_data(), &[0.0, 1.0, 2.0]);
     }
 
+    #[test]
+    fn transpose_shares_data() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let y = x.transpose();
+        assert!(Arc::ptr_eq(&x.data, &y.data));
+        assert_eq!(y.get_strides(), &vec![1, 3]);
+    }
+
+    #[test]
+    fn permute_normal() {
+        let x = Tensor::<f64>::arrange([2, 3, 2]).unwrap();
+        let y = x.permute([1, 2, 0]).unwrap().contiguous();
+        assert_eq!(y.get_shape(), &[3, 2, 2]);
+        assert_eq!(y.get_data(), &[0.0, 6.0, 1.0, 7.0, 2.0, 8.0, 3.0, 9.0, 4.0, 10.0, 5.0, 11.0]);
+    }
+
+    #[test]
+    fn permute_reverse_is_transpose() {
+        let x = Tensor::<f64>::arrange([2, 3, 4]).unwrap();
+        assert_eq!(x.permute([2, 1, 0]).unwrap(), x.transpose());
+    }
+
+    #[test]
+    fn permute_error_dimension() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.permute([0]) {
+            Ok(_) => panic!("Should be error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionError(1, 2))
+            }
+        }
+    }
+
+    #[test]
+    fn permute_error_not_permutation() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.permute([1, 1]) {
+            Ok(_) => panic!("Should be error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(
+                    e,
+                    TensorError::InvalidArgumentError("axes is not a permutation: [1, 1]".to_string())
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn swapaxes_normal() {
+        let x = Tensor::<f64>::arrange([2, 1, 3]).unwrap();
+        let y = x.swapaxes(0, 2).unwrap().contiguous();
+        assert_eq!(y.get_shape(), &[3, 1, 2]);
+        assert_eq!(y.get_data(), &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+    }
+
     #[test]
     fn flatten_normal() {
         let x = Tensor::new([0.0, 1.0, 2.0, 3.0], [2, 2]).unwrap();
@@ -231,7 +260,7 @@ mod tests {
     #[test]
     fn broadcast_to_normal() {
         let x = Tensor::new([0.0, 1.0, 2.0], [3,]).unwrap();
-        let x = x.broadcast_to([2, 3]).unwrap();
+        let x = x.broadcast_to([2, 3]).unwrap().contiguous();
         assert_eq!(x.get_shape(), &[2, 3]);
         assert_eq!(x.get_data(), &[0.0, 1.0, 2.0, 0.0, 1.0, 2.0]);
     }
@@ -239,7 +268,7 @@ mod tests {
     #[test]
     fn broadcast_to_3d() {
         let x = Tensor::new([0.0, 1.0, 2.0, 3.0], [2, 1, 2]).unwrap();
-        let x = x.broadcast_to([2, 2, 2]).unwrap();
+        let x = x.broadcast_to([2, 2, 2]).unwrap().contiguous();
         assert_eq!(x.get_shape(), &[2, 2, 2]);
         assert_eq!(x.get_data(), &[0.0, 1.0, 0.0, 1.0,
                                     2.0, 3.0, 2.0, 3.0]);
@@ -248,7 +277,7 @@ mod tests {
     #[test]
     fn broadcast_to_add_left() {
         let x = Tensor::new([0.0, 1.0], [2,]).unwrap();
-        let x = x.broadcast_to([3, 2]).unwrap();
+        let x = x.broadcast_to([3, 2]).unwrap().contiguous();
         assert_eq!(x.get_shape(), &[3, 2]);
         assert_eq!(x.get_data(), &[0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
     }
@@ -256,7 +285,7 @@ mod tests {
     #[test]
     fn broadcast_to_scaler() {
         let x = Tensor::new([0.0], []).unwrap();
-        let x = x.broadcast_to([3, 2]).unwrap();
+        let x = x.broadcast_to([3, 2]).unwrap().contiguous();
         assert_eq!(x.get_shape(), &[3, 2]);
         assert_eq!(x.get_data(), &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
     }
@@ -264,11 +293,19 @@ mod tests {
     #[test]
     fn broadcast_to_0d() {
         let x = Tensor::new([0.0], []).unwrap();
-        let x = x.broadcast_to([]).unwrap();
+        let x = x.broadcast_to([]).unwrap().contiguous();
         assert_eq!(x.get_shape(), &[]);
         assert_eq!(x.get_data(), &[0.0]);
     }
 
+    #[test]
+    fn broadcast_to_shares_data() {
+        let x = Tensor::<f64>::arrange([3]).unwrap();
+        let y = x.broadcast_to([2, 3]).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &y.data));
+        assert_eq!(y.get_strides(), &vec![0, 1]);
+    }
+
     #[test]
     fn broadcast_to_error_mismatch_ndim() {
         let x = Tensor::new([0.0, 1.0], [2, 1]).unwrap();
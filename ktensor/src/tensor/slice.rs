@@ -1,10 +1,9 @@
 use anyhow::Result;
-use super::Tensor;
+use super::{Tensor, TensorView};
+use super::slice_elem::{SliceElem, ResolvedSlice, resolve_slice};
 use crate::error::TensorError;
 
 impl<T> Tensor<T>
-where
-    T: Clone,
 {
     /// Slice the Tensor with the given first dimension index
     ///
@@ -14,27 +13,22 @@ where
     /// 
     /// # Returns
     /// 
-    /// * `Result<Self>` - The sliced Tensor
+    /// * `Result<TensorView<T>>` - The view without the first dimension
     /// 
     /// # Note
     /// 
+    /// The data is shared without copying. Use `contiguous` to materialize the view.
     /// If tensor is 0 dimensional, `TensorError::DimensionSmallerError` is returned.
     /// If the index is out of range, `TensorError::IndexError` is returned.
-    pub fn slice_with_one_index(&self, index: usize) -> Result<Self> {
-        if self.ndim() == 0 {
-            return Err(TensorError::DimensionSmallerError(0, 1).into());
-        }
-        if index >= self.shape[0] {
-            return Err(TensorError::IndexError(self.shape.clone(), vec![index]).into());
-        }
-        let mut new_shape = self.shape.clone();
-        new_shape.remove(0);
-        let size = new_shape.iter().product();
-        let mut data = Vec::with_capacity(size);
-        data.extend_from_slice(&self.data[index * size..(index + 1) * size]);
-        Ok(Tensor::new(data, new_shape)?)
+    pub fn slice_with_one_index(&self, index: usize) -> Result<TensorView<T>> {
+        self.view().slice_with_one_index(index)
     }
+}
 
+impl<T> Tensor<T>
+where
+    T: Clone,
+{
     /// Slice the Tensor with the given first dimension indexes
     /// 
     /// # Arguments
@@ -124,11 +118,80 @@ where
         }
         Tensor::new(data, new_shape)
     }
+
+    /// Slice the Tensor with the slice elements
+    /// 
+    /// # Arguments
+    /// 
+    /// * `elems` - The slice elements for each axis, usually created with the `s!` macro
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The sliced Tensor
+    /// 
+    /// # Note
+    /// 
+    /// The axes not covered by `elems` are kept as they are.
+    /// Index arrays select along their own axis independently (outer indexing).
+    /// 
+    /// If `elems` has more axes than the tensor, `TensorError::DimensionLargerError` is returned.
+    /// If an index is out of range or a step is zero, `TensorError::InvalidArgumentError` is returned.
+    pub fn slice<U: AsRef<[SliceElem]>>(&self, elems: U) -> Result<Self> {
+        let (shape, positions) = self.slice_positions(elems.as_ref())?;
+        let data = positions.into_iter()
+            .map(|position| self.data[position].clone())
+            .collect::<Vec<_>>();
+        Tensor::new(data, shape)
+    }
+}
+
+impl<T> Tensor<T>
+{
+    /// Calculate the shape of the slice and the data index of each element of the slice
+    pub(crate) fn slice_positions(&self, elems: &[SliceElem]) -> Result<(Vec<usize>, Vec<usize>)> {
+        let resolved = resolve_slice(&self.shape, elems)?;
+        let mut offset = 0;
+        let mut axes = Vec::with_capacity(resolved.len());
+        let mut axis = 0;
+        for elem in resolved {
+            let stride = self.shape[(axis + 1).min(self.ndim())..].iter().product::<usize>();
+            match &elem {
+                ResolvedSlice::Index(i) => offset += i * stride,
+                &ResolvedSlice::Range { start, step, len } => axes.push((0..len)
+                    .map(|i| (start as isize + i as isize * step) as usize * stride)
+                    .collect::<Vec<_>>()),
+                ResolvedSlice::NewAxis => axes.push(vec![0]),
+                ResolvedSlice::Indexes(indexes) => axes.push(indexes.iter()
+                    .map(|i| i * stride)
+                    .collect::<Vec<_>>()),
+            }
+            if elem != ResolvedSlice::NewAxis {
+                axis += 1;
+            }
+        }
+        let shape = axes.iter().map(|v| v.len()).collect::<Vec<_>>();
+        let size = shape.iter().product::<usize>();
+        let mut positions = Vec::with_capacity(size);
+        let mut index = vec![0; shape.len()];
+        for _ in 0..size {
+            positions.push(offset + index.iter().zip(axes.iter()).map(|(&i, v)| v[i]).sum::<usize>());
+            for d in (0..shape.len()).rev() {
+                index[d] += 1;
+                if index[d] < shape[d] {
+                    break
+                }
+                index[d] = 0;
+            }
+        }
+        Ok((shape, positions))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::s;
+    use crate::tensor::NewAxis;
 
     #[test]
     fn slice_with_one_index_normal() {
@@ -137,6 +200,8 @@ mod tests {
             [3, 2]
         ).unwrap();
         let y = x.slice_with_one_index(1).unwrap();
+        assert_eq!(y.get_offset(), 2);
+        let y = y.contiguous();
         assert_eq!(y.get_data(), &vec![2.0, 3.0]);
         assert_eq!(y.get_shape(), &vec![2]);
     }
@@ -144,7 +209,7 @@ mod tests {
     #[test]
     fn slice_with_one_index_one_dimensional() {
         let x = Tensor::new([0.0, 1.0, 2.0], [3,]).unwrap();
-        let y = x.slice_with_one_index(1).unwrap();
+        let y = x.slice_with_one_index(1).unwrap().contiguous();
         assert_eq!(y.get_data(), &vec![1.0]);
         assert_eq!(y.get_shape(), &vec![]);
     }
@@ -367,4 +432,74 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn slice_range_step_index() {
+        let x = Tensor::<f64>::arrange([2, 6, 3]).unwrap();
+        let y = x.slice(s![.., 1..5;2, -1]).unwrap();
+        assert_eq!(y.get_shape(), &vec![2, 2]);
+        assert_eq!(y.get_data(), &vec![5.0, 11.0, 23.0, 29.0]);
+    }
+
+    #[test]
+    fn slice_negative_step() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let y = x.slice(s![..;-1, 2..0;-2]).unwrap();
+        assert_eq!(y.get_shape(), &vec![2, 1]);
+        assert_eq!(y.get_data(), &vec![5.0, 2.0]);
+        let y = x.slice(s![.., ..;-1]).unwrap();
+        assert_eq!(y.get_data(), &vec![2.0, 1.0, 0.0, 5.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn slice_negative_step_inclusive() {
+        let x = Tensor::<f64>::arrange([4]).unwrap();
+        assert_eq!(x.slice(s![2..=0;-1]).unwrap().get_data(), &vec![2.0, 1.0, 0.0]);
+        assert_eq!(x.slice(s![3..=1;-1]).unwrap().get_data(), &vec![3.0, 2.0, 1.0]);
+        assert_eq!(x.slice(s![..=1;-2]).unwrap().get_data(), &vec![3.0, 1.0]);
+        assert_eq!(x.slice(s![1..=-1]).unwrap().get_data(), &vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn slice_new_axis() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let y = x.slice(s![NewAxis, .., NewAxis]).unwrap();
+        assert_eq!(y.get_shape(), &vec![1, 2, 1, 3]);
+        assert_eq!(y.get_data(), x.get_data());
+    }
+
+    #[test]
+    fn slice_index_arrays() {
+        let x = Tensor::<f64>::arrange([3, 4]).unwrap();
+        let y = x.slice(s![[2, 0, 2], vec![-1isize, 1]]).unwrap();
+        assert_eq!(y.get_shape(), &vec![3, 2]);
+        assert_eq!(y.get_data(), &vec![11.0, 9.0, 3.0, 1.0, 11.0, 9.0]);
+    }
+
+    #[test]
+    fn slice_empty_range() {
+        let x = Tensor::<f64>::arrange([3, 4]).unwrap();
+        let y = x.slice(s![2..1]).unwrap();
+        assert_eq!(y.get_shape(), &vec![0, 4]);
+        assert_eq!(y.get_data(), &vec![]);
+    }
+
+    #[test]
+    fn slice_scalar_index() {
+        let x = Tensor::<f64>::arrange([3, 4]).unwrap();
+        let y = x.slice(s![-1, -2]).unwrap();
+        assert_eq!(y, Tensor::scalar(10.0));
+    }
+
+    #[test]
+    fn slice_error_too_many_axes() {
+        let x = Tensor::<f64>::arrange([3]).unwrap();
+        match x.slice(s![0, 0]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(2, 1));
+            }
+        }
+    }
 }
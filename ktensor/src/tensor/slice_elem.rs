@@ -0,0 +1,411 @@
+use std::ops::{Range, RangeFrom, RangeTo, RangeFull, RangeInclusive, RangeToInclusive};
+use anyhow::Result;
+use crate::error::TensorError;
+
+/// The element of a slice for one axis
+///
+/// # Variants
+///
+/// * `Index` - Select one index and remove the axis. Negative values count from the end.
+/// * `Range` - Select the indexes from `start` to `end` by `step`.
+///   `end` is exclusive unless `inclusive` is true.
+///   `None` means the end of the axis in the direction of `step`. Negative values count from the end.
+/// * `NewAxis` - Insert a new axis of size 1.
+/// * `Indexes` - Select the indexes along the axis independently of the other axes.
+///
+/// # Note
+///
+/// Slice elements are usually created with the `s!` macro.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SliceElem {
+    Index(isize),
+    Range { start: Option<isize>, end: Option<isize>, step: isize, inclusive: bool },
+    NewAxis,
+    Indexes(Vec<isize>),
+}
+
+/// The marker to insert a new axis of size 1 in the `s!` macro
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewAxis;
+
+/// The range types which can be used as a slice element
+pub trait SliceRange {
+    /// Get the start, the end and whether the end is inclusive
+    ///
+    /// # Note
+    ///
+    /// The inclusive end is resolved with the step in `resolve_slice`,
+    /// because the next index depends on the direction of the step.
+    fn bounds(self) -> (Option<isize>, Option<isize>, bool);
+}
+
+impl SliceRange for RangeFull {
+    fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+        (None, None, false)
+    }
+}
+
+macro_rules! impl_slice_range {
+    ($($t:ty),*) => {
+        $(
+            impl SliceRange for Range<$t> {
+                fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+                    (Some(self.start as isize), Some(self.end as isize), false)
+                }
+            }
+
+            impl SliceRange for RangeFrom<$t> {
+                fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+                    (Some(self.start as isize), None, false)
+                }
+            }
+
+            impl SliceRange for RangeTo<$t> {
+                fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+                    (None, Some(self.end as isize), false)
+                }
+            }
+
+            impl SliceRange for RangeInclusive<$t> {
+                fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+                    (Some(*self.start() as isize), Some(*self.end() as isize), true)
+                }
+            }
+
+            impl SliceRange for RangeToInclusive<$t> {
+                fn bounds(self) -> (Option<isize>, Option<isize>, bool) {
+                    (None, Some(self.end as isize), true)
+                }
+            }
+
+            impl From<Range<$t>> for SliceElem {
+                fn from(range: Range<$t>) -> Self {
+                    Self::range(range, 1)
+                }
+            }
+
+            impl From<RangeFrom<$t>> for SliceElem {
+                fn from(range: RangeFrom<$t>) -> Self {
+                    Self::range(range, 1)
+                }
+            }
+
+            impl From<RangeTo<$t>> for SliceElem {
+                fn from(range: RangeTo<$t>) -> Self {
+                    Self::range(range, 1)
+                }
+            }
+
+            impl From<RangeInclusive<$t>> for SliceElem {
+                fn from(range: RangeInclusive<$t>) -> Self {
+                    Self::range(range, 1)
+                }
+            }
+
+            impl From<RangeToInclusive<$t>> for SliceElem {
+                fn from(range: RangeToInclusive<$t>) -> Self {
+                    Self::range(range, 1)
+                }
+            }
+
+            impl From<$t> for SliceElem {
+                fn from(index: $t) -> Self {
+                    Self::Index(index as isize)
+                }
+            }
+        )*
+    };
+}
+
+impl_slice_range!(i32, isize, usize);
+
+impl From<RangeFull> for SliceElem {
+    fn from(range: RangeFull) -> Self {
+        Self::range(range, 1)
+    }
+}
+
+impl From<NewAxis> for SliceElem {
+    fn from(_: NewAxis) -> Self {
+        Self::NewAxis
+    }
+}
+
+impl From<Vec<usize>> for SliceElem {
+    fn from(indexes: Vec<usize>) -> Self {
+        Self::Indexes(indexes.into_iter().map(|i| i as isize).collect())
+    }
+}
+
+impl From<&[usize]> for SliceElem {
+    fn from(indexes: &[usize]) -> Self {
+        Self::Indexes(indexes.iter().map(|&i| i as isize).collect())
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for SliceElem {
+    fn from(indexes: [usize; N]) -> Self {
+        Self::Indexes(indexes.iter().map(|&i| i as isize).collect())
+    }
+}
+
+impl From<Vec<isize>> for SliceElem {
+    fn from(indexes: Vec<isize>) -> Self {
+        Self::Indexes(indexes)
+    }
+}
+
+impl SliceElem {
+    /// Create a range slice element with the step
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the indexes
+    /// * `step` - The step between the indexes. A negative step selects the indexes in reverse order.
+    pub fn range<R: SliceRange>(range: R, step: isize) -> Self {
+        let (start, end, inclusive) = range.bounds();
+        Self::Range { start, end, step, inclusive }
+    }
+}
+
+/// Create an array of slice elements
+///
+/// Each element is separated by a comma and is one of the following:
+///
+/// * An index like `1` or `-1`
+/// * A range like `..`, `1..5`, `..-1` or `2..=4`, optionally followed by `;step` like `1..5;2` or `5..0;-1`
+/// * `NewAxis` to insert a new axis of size 1
+/// * Index arrays like `[0, 2]` or `vec![0, 2]`
+///
+/// # Examples
+///
+/// ```
+/// use ktensor::{s, Tensor};
+///
+/// let x = Tensor::<f64>::arrange([2, 6, 3]).unwrap();
+/// let y = x.slice(s![.., 1..5;2, -1]).unwrap();
+/// assert_eq!(y.get_shape(), &vec![2, 2]);
+/// ```
+#[macro_export]
+macro_rules! s {
+    (@parse [$($acc:expr),*]) => {
+        [$($acc),*]
+    };
+    (@parse [$($acc:expr),*] $r:expr ; $step:expr , $($rest:tt)*) => {
+        $crate::s!(@parse [$($acc,)* {
+            #[allow(clippy::reversed_empty_ranges)]
+            let range = $r;
+            $crate::tensor::SliceElem::range(range, $step)
+        }] $($rest)*)
+    };
+    (@parse [$($acc:expr),*] $r:expr ; $step:expr) => {
+        $crate::s!(@parse [$($acc,)* {
+            #[allow(clippy::reversed_empty_ranges)]
+            let range = $r;
+            $crate::tensor::SliceElem::range(range, $step)
+        }])
+    };
+    (@parse [$($acc:expr),*] $e:expr , $($rest:tt)*) => {
+        $crate::s!(@parse [$($acc,)* {
+            #[allow(clippy::reversed_empty_ranges)]
+            let elem = $e;
+            $crate::tensor::SliceElem::from(elem)
+        }] $($rest)*)
+    };
+    (@parse [$($acc:expr),*] $e:expr) => {
+        $crate::s!(@parse [$($acc,)* {
+            #[allow(clippy::reversed_empty_ranges)]
+            let elem = $e;
+            $crate::tensor::SliceElem::from(elem)
+        }])
+    };
+    ($($t:tt)*) => {
+        $crate::s!(@parse [] $($t)*)
+    };
+}
+
+/// The slice element resolved for an axis of a shape
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ResolvedSlice {
+    Index(usize),
+    Range { start: usize, step: isize, len: usize },
+    NewAxis,
+    Indexes(Vec<usize>),
+}
+
+/// Resolve an index which may be negative
+fn resolve_index(index: isize, axis: usize, dim: usize) -> Result<usize> {
+    let resolved = if index < 0 { index + dim as isize } else { index };
+    if resolved < 0 || resolved >= dim as isize {
+        return Err(TensorError::InvalidArgumentError(
+            format!("index {} is out of range for axis {} with size {}", index, axis, dim)
+        ).into())
+    }
+    Ok(resolved as usize)
+}
+
+/// Resolve a range like Python's slice
+///
+/// The start and the end are clipped to the axis.
+/// An inclusive end is moved one index further in the direction of `step`,
+/// so a negative step through index 0 selects up to the start of the axis.
+fn resolve_range(start: Option<isize>, end: Option<isize>, step: isize, inclusive: bool, dim: usize) -> Result<ResolvedSlice> {
+    if step == 0 {
+        return Err(TensorError::InvalidArgumentError("step must not be zero".to_string()).into())
+    }
+    let dim = dim as isize;
+    let absolute = |i: isize| if i < 0 { i + dim } else { i };
+    let end = end.map(|i| if inclusive { absolute(i) + step.signum() } else { absolute(i) });
+    let (start, len) = if step > 0 {
+        let start = start.map_or(0, |i| absolute(i).clamp(0, dim));
+        let end = end.map_or(dim, |i| i.clamp(0, dim));
+        (start, if end > start { (end - start + step - 1) / step } else { 0 })
+    } else {
+        let start = start.map_or(dim - 1, |i| absolute(i).clamp(-1, dim - 1));
+        let end = end.map_or(-1, |i| i.clamp(-1, dim - 1));
+        (start, if start > end { (start - end - step - 1) / -step } else { 0 })
+    };
+    Ok(ResolvedSlice::Range { start: start.max(0) as usize, step, len: len as usize })
+}
+
+/// Resolve the slice elements for the shape
+///
+/// # Arguments
+///
+/// * `shape` - The shape to be sliced
+/// * `elems` - The slice elements
+///
+/// # Returns
+///
+/// * `Result<Vec<ResolvedSlice>>` - The resolved elements. The axes not covered by `elems` are full ranges.
+///
+/// # Note
+///
+/// If `elems` has more axes than `shape`, `TensorError::DimensionLargerError` is returned.
+/// If an index is out of range or a step is zero, `TensorError::InvalidArgumentError` is returned.
+pub(crate) fn resolve_slice(shape: &[usize], elems: &[SliceElem]) -> Result<Vec<ResolvedSlice>> {
+    let axis_count = elems.iter().filter(|elem| **elem != SliceElem::NewAxis).count();
+    if axis_count > shape.len() {
+        return Err(TensorError::DimensionLargerError(axis_count, shape.len()).into())
+    }
+    let mut resolved = Vec::with_capacity(elems.len() + shape.len() - axis_count);
+    let mut axis = 0;
+    for elem in elems {
+        resolved.push(match elem {
+            SliceElem::Index(i) => ResolvedSlice::Index(resolve_index(*i, axis, shape[axis])?),
+            SliceElem::Range { start, end, step, inclusive } => {
+                resolve_range(*start, *end, *step, *inclusive, shape[axis])?
+            },
+            SliceElem::NewAxis => ResolvedSlice::NewAxis,
+            SliceElem::Indexes(indexes) => ResolvedSlice::Indexes(indexes.iter()
+                .map(|&i| resolve_index(i, axis, shape[axis]))
+                .collect::<Result<Vec<_>>>()?),
+        });
+        if *elem != SliceElem::NewAxis {
+            axis += 1;
+        }
+    }
+    for &dim in &shape[axis..] {
+        resolved.push(ResolvedSlice::Range { start: 0, step: 1, len: dim });
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn s_macro() {
+        let elems = s![.., 1..5;2, -1, NewAxis, [0, 2], ..;-1, 2..];
+        assert_eq!(elems, [
+            SliceElem::Range { start: None, end: None, step: 1, inclusive: false },
+            SliceElem::Range { start: Some(1), end: Some(5), step: 2, inclusive: false },
+            SliceElem::Index(-1),
+            SliceElem::NewAxis,
+            SliceElem::Indexes(vec![0, 2]),
+            SliceElem::Range { start: None, end: None, step: -1, inclusive: false },
+            SliceElem::Range { start: Some(2), end: None, step: 1, inclusive: false },
+        ]);
+    }
+
+    #[test]
+    fn s_macro_inclusive() {
+        assert_eq!(s![1..=-1, ..=2], [
+            SliceElem::Range { start: Some(1), end: Some(-1), step: 1, inclusive: true },
+            SliceElem::Range { start: None, end: Some(2), step: 1, inclusive: true },
+        ]);
+    }
+
+    #[test]
+    fn resolve_range_inclusive() {
+        assert_eq!(resolve_range(Some(1), Some(-1), 1, true, 5).unwrap(),
+            ResolvedSlice::Range { start: 1, step: 1, len: 4 });
+        assert_eq!(resolve_range(Some(2), Some(0), -1, true, 5).unwrap(),
+            ResolvedSlice::Range { start: 2, step: -1, len: 3 });
+        assert_eq!(resolve_range(Some(3), Some(1), -1, true, 5).unwrap(),
+            ResolvedSlice::Range { start: 3, step: -1, len: 3 });
+        assert_eq!(resolve_range(None, Some(0), -2, true, 5).unwrap(),
+            ResolvedSlice::Range { start: 4, step: -2, len: 3 });
+        assert_eq!(resolve_range(Some(-1), Some(-3), -1, true, 5).unwrap(),
+            ResolvedSlice::Range { start: 4, step: -1, len: 3 });
+    }
+
+    #[test]
+    fn resolve_range_normal() {
+        assert_eq!(resolve_range(Some(1), Some(6), 2, false, 5).unwrap(),
+            ResolvedSlice::Range { start: 1, step: 2, len: 2 });
+        assert_eq!(resolve_range(None, Some(-1), 1, false, 5).unwrap(),
+            ResolvedSlice::Range { start: 0, step: 1, len: 4 });
+        assert_eq!(resolve_range(None, None, -2, false, 5).unwrap(),
+            ResolvedSlice::Range { start: 4, step: -2, len: 3 });
+        assert_eq!(resolve_range(Some(3), Some(3), 1, false, 5).unwrap(),
+            ResolvedSlice::Range { start: 3, step: 1, len: 0 });
+    }
+
+    #[test]
+    fn resolve_slice_pad() {
+        let resolved = resolve_slice(&[2, 3, 4], &s![NewAxis, -1]).unwrap();
+        assert_eq!(resolved, [
+            ResolvedSlice::NewAxis,
+            ResolvedSlice::Index(1),
+            ResolvedSlice::Range { start: 0, step: 1, len: 3 },
+            ResolvedSlice::Range { start: 0, step: 1, len: 4 },
+        ]);
+    }
+
+    #[test]
+    fn resolve_slice_error_too_many_axes() {
+        match resolve_slice(&[2, 3], &s![0, 0, 0]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(3, 2));
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_slice_error_index() {
+        match resolve_slice(&[2, 3], &s![-3]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "index -3 is out of range for axis 0 with size 2".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_slice_error_step_zero() {
+        match resolve_slice(&[2, 3], &s![..;0]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError("step must not be zero".to_string()));
+            }
+        }
+    }
+}
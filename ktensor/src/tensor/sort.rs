@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+use anyhow::Result;
+use super::Tensor;
+use crate::error::TensorError;
+
+/// Compare two values placing the values which are not comparable to themselves (like NaN) last
+fn compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    let a_nan = a.partial_cmp(a).is_none();
+    let b_nan = b.partial_cmp(b).is_none();
+    match (a_nan, b_nan) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+impl<T> Tensor<T>
+where
+    T: PartialOrd + Clone,
+{
+    /// Sort each lane along the axis and keep the first `k` elements
+    fn sort_lanes(&self, axis: usize, descending: bool, k: usize) -> Result<(Self, Tensor<usize>)> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        let size = self.shape[axis];
+        let before = self.shape[..axis].iter().product::<usize>();
+        let after = self.shape[axis + 1..].iter().product::<usize>();
+        let mut shape = self.shape.clone();
+        shape[axis] = k;
+        let mut values = Vec::with_capacity(before * k * after);
+        let mut indices = vec![0; before * k * after];
+        let mut lane = Vec::with_capacity(size);
+        for i in 0..before {
+            for j in 0..after {
+                lane.clear();
+                lane.extend(0..size);
+                let position = |p: usize| (i * size + p) * after + j;
+                lane.sort_by(|&a, &b| {
+                    let order = compare(&self.data[position(a)], &self.data[position(b)]);
+                    if descending { order.reverse() } else { order }
+                });
+                for (p, &index) in lane[..k].iter().enumerate() {
+                    indices[(i * k + p) * after + j] = index;
+                }
+            }
+        }
+        for i in 0..before {
+            for p in 0..k {
+                for j in 0..after {
+                    let index = indices[(i * k + p) * after + j];
+                    values.push(self.data[(i * size + index) * after + j].clone());
+                }
+            }
+        }
+        Ok((
+            Self { data: Arc::new(values), shape: shape.clone() },
+            Tensor { data: Arc::new(indices), shape },
+        ))
+    }
+
+    /// Sort the tensor along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis to sort
+    /// * `descending` - Whether to sort in descending order
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The sorted tensor
+    /// 
+    /// # Note
+    /// 
+    /// The sort is stable and NaN is placed last in ascending order.
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn sort(&self, axis: usize, descending: bool) -> Result<Self> {
+        let size = self.shape.get(axis).copied().unwrap_or(0);
+        Ok(self.sort_lanes(axis, descending, size)?.0)
+    }
+
+    /// Get the indices which sort the tensor along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis to sort
+    /// * `descending` - Whether to sort in descending order
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Tensor<usize>>` - The indices along the axis
+    /// 
+    /// # Note
+    /// 
+    /// The sort is stable and NaN is placed last in ascending order.
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn argsort(&self, axis: usize, descending: bool) -> Result<Tensor<usize>> {
+        let size = self.shape.get(axis).copied().unwrap_or(0);
+        Ok(self.sort_lanes(axis, descending, size)?.1)
+    }
+
+    /// Get the `k` largest elements along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `k` - The number of elements
+    /// * `axis` - The axis
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<(Self, Tensor<usize>)>` - The values in descending order and their indices along the axis
+    /// 
+    /// # Note
+    /// 
+    /// If the values are the same, the smaller index comes first.
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If `k` is larger than the size of the axis, `TensorError::InvalidArgumentError` is returned
+    pub fn topk(&self, k: usize, axis: usize) -> Result<(Self, Tensor<usize>)> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        if k > self.shape[axis] {
+            return Err(TensorError::InvalidArgumentError(
+                format!("k {} is larger than the size of axis {}: {}", k, axis, self.shape[axis])
+            ).into())
+        }
+        self.sort_lanes(axis, true, k)
+    }
+
+    /// Get the sorted unique elements of the tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Self` - The 1-D tensor of the unique elements
+    pub fn unique(&self) -> Self {
+        self.unique_with_counts().0
+    }
+
+    /// Get the sorted unique elements of the tensor and the number of their occurrences
+    /// 
+    /// # Returns
+    /// 
+    /// * `(Self, Tensor<usize>)` - The 1-D tensors of the unique elements and the counts
+    pub fn unique_with_counts(&self) -> (Self, Tensor<usize>) {
+        let mut sorted = self.data.iter().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| compare(*a, *b));
+        let mut values: Vec<T> = Vec::new();
+        let mut counts = Vec::new();
+        for x in sorted {
+            match values.last() {
+                Some(last) if last == x => *counts.last_mut().unwrap() += 1,
+                _ => {
+                    values.push(x.clone());
+                    counts.push(1);
+                }
+            }
+        }
+        let len = values.len();
+        (
+            Self { data: Arc::new(values), shape: vec![len] },
+            Tensor { data: Arc::new(counts), shape: vec![len] },
+        )
+    }
+}
+
+impl Tensor<usize> {
+    /// Count the number of occurrences of each value
+    /// 
+    /// # Arguments
+    /// 
+    /// * `minlength` - The minimum length of the result
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Tensor<usize>>` - The 1-D tensor whose `i`-th element is the count of `i`
+    /// 
+    /// # Note
+    /// 
+    /// If the tensor is not 1-D, `TensorError::DimensionError` is returned
+    pub fn bincount(&self, minlength: usize) -> Result<Tensor<usize>> {
+        if self.ndim() != 1 {
+            return Err(TensorError::DimensionError(self.ndim(), 1).into())
+        }
+        let len = self.data.iter()
+            .map(|&x| x + 1)
+            .max()
+            .unwrap_or(0)
+            .max(minlength);
+        let mut counts = vec![0; len];
+        for &x in self.data.iter() {
+            counts[x] += 1;
+        }
+        Ok(Tensor { data: Arc::new(counts), shape: vec![len] })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_normal() {
+        let x = Tensor::new([3, 1, 2, 0, 5, 4], [2, 3]).unwrap();
+        assert_eq!(x.sort(1, false).unwrap(), Tensor::new([1, 2, 3, 0, 4, 5], [2, 3]).unwrap());
+        assert_eq!(x.sort(0, true).unwrap(), Tensor::new([3, 5, 4, 0, 1, 2], [2, 3]).unwrap());
+    }
+
+    #[test]
+    fn sort_nan_last() {
+        let x = Tensor::new([2.0, f64::NAN, 1.0], [3]).unwrap();
+        let y = x.sort(0, false).unwrap();
+        assert_eq!(&y.get_data()[..2], &[1.0, 2.0]);
+        assert!(y.get_data()[2].is_nan());
+    }
+
+    #[test]
+    fn argsort_normal() {
+        let x = Tensor::new([3, 1, 2, 1], [4]).unwrap();
+        assert_eq!(x.argsort(0, false).unwrap(), Tensor::new([1, 3, 2, 0], [4]).unwrap());
+        assert_eq!(x.argsort(0, true).unwrap(), Tensor::new([0, 2, 1, 3], [4]).unwrap());
+    }
+
+    #[test]
+    fn argsort_error_axis() {
+        let x = Tensor::new([3, 1, 2, 1], [4]).unwrap();
+        match x.argsort(1, false) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(1, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn topk_normal() {
+        let x = Tensor::new([1.0, 4.0, 2.0, 3.0, 0.0, 5.0], [3, 2]).unwrap();
+        let (values, indices) = x.topk(2, 0).unwrap();
+        assert_eq!(values, Tensor::new([2.0, 5.0, 1.0, 4.0], [2, 2]).unwrap());
+        assert_eq!(indices, Tensor::new([1, 2, 0, 0], [2, 2]).unwrap());
+    }
+
+    #[test]
+    fn topk_error_k() {
+        let x = Tensor::new([1.0, 4.0, 2.0], [3]).unwrap();
+        match x.topk(4, 0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "k 4 is larger than the size of axis 0: 3".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn unique_normal() {
+        let x = Tensor::new([3, 1, 3, 0, 1, 3], [2, 3]).unwrap();
+        assert_eq!(x.unique(), Tensor::new([0, 1, 3], [3]).unwrap());
+        let (values, counts) = x.unique_with_counts();
+        assert_eq!(values, Tensor::new([0, 1, 3], [3]).unwrap());
+        assert_eq!(counts, Tensor::new([1, 2, 3], [3]).unwrap());
+    }
+
+    #[test]
+    fn bincount_normal() {
+        let x = Tensor::<usize>::new([1, 3, 1, 0], [4]).unwrap();
+        assert_eq!(x.bincount(0).unwrap(), Tensor::new([1, 2, 0, 1], [4]).unwrap());
+        assert_eq!(x.bincount(6).unwrap(), Tensor::new([1, 2, 0, 1, 0, 0], [6]).unwrap());
+        let empty = Tensor::<usize>::new([], [0]).unwrap();
+        assert_eq!(empty.bincount(2).unwrap(), Tensor::new([0, 0], [2]).unwrap());
+    }
+
+    #[test]
+    fn bincount_error_dimension() {
+        let x = Tensor::<usize>::new([1, 3, 1, 0], [2, 2]).unwrap();
+        match x.bincount(0) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionError(2, 1));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::collections::HashSet;
 use num_traits::NumAssign;
 use anyhow::Result;
@@ -72,7 +73,7 @@ where
             data[index] += value.clone();
         }
         let new_shape = make_shape(&self.shape, &axis, keepdims);
-        Self { data, shape: new_shape }
+        Self { data: Arc::new(data), shape: new_shape }
     }
 
     /// Sum the values for the given shape
@@ -109,6 +110,32 @@ where
         tensor.shape = shape.to_vec();
         Ok(tensor)
     }
+
+    /// Multiply all the elements of the tensor
+    pub fn prod_all(&self) -> T {
+        self.data.iter()
+            .fold(T::one(), |acc, x| acc * x.clone())
+    }
+
+    /// Multiply the elements of the tensor along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis along which the product is performed
+    /// * `keepdims` - Whether to keep the dimension of the tensor
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Self>` - The result of the product
+    /// 
+    /// # Note
+    /// 
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn prod_with_axis(&self, axis: usize, keepdims: bool) -> Result<Self> {
+        self.map_axis(axis, keepdims, |lane| {
+            lane.fold(T::one(), |acc, x| acc * x.clone())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -284,4 +311,12 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn prod_normal() {
+        let x = Tensor::new([1, 2, 3, 4, 5, 6], [2, 3]).unwrap();
+        assert_eq!(x.prod_all(), 720);
+        assert_eq!(x.prod_with_axis(0, false).unwrap(), Tensor::new([4, 10, 18], [3]).unwrap());
+        assert_eq!(x.prod_with_axis(1, true).unwrap(), Tensor::new([6, 120], [2, 1]).unwrap());
+    }
 }
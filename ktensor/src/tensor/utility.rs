@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::iter::{StepBy, Take};
+use std::slice::Iter;
 use anyhow::Result;
 use super::Tensor;
 use crate::error::TensorError;
@@ -99,6 +102,159 @@ impl<T> Tensor<T>
         }
         indexes.iter().rev().cloned().collect()
     }
+
+    /// Calculate the shape obtained by broadcasting two shapes
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lhs` - The shape of the left hand side
+    /// * `rhs` - The shape of the right hand side
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Vec<usize>>` - The broadcast shape
+    /// 
+    /// # Note
+    /// 
+    /// The shapes are aligned from the last dimension like NumPy.
+    /// Each pair of dimensions must be equal or one of them must be 1.
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub(crate) fn broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Vec<usize>> {
+        let ndim = lhs.len().max(rhs.len());
+        let mut shape = vec![0; ndim];
+        for i in 0..ndim {
+            let l = if i < lhs.len() { lhs[lhs.len() - 1 - i] } else { 1 };
+            let r = if i < rhs.len() { rhs[rhs.len() - 1 - i] } else { 1 };
+            shape[ndim - 1 - i] = if l == r || r == 1 {
+                l
+            } else if l == 1 {
+                r
+            } else {
+                return Err(TensorError::ShapeMismatchError(
+                    lhs.to_vec(), rhs.to_vec()
+                ).into())
+            };
+        }
+        Ok(shape)
+    }
+
+    /// Calculate the data strides for reading a tensor as the broadcast shape
+    /// 
+    /// # Arguments
+    /// 
+    /// * `shape` - The shape of the tensor
+    /// * `target_shape` - The broadcast shape
+    /// 
+    /// # Returns
+    /// 
+    /// * `Vec<usize>` - The strides for each dimension of `target_shape`
+    /// 
+    /// # Note
+    /// 
+    /// The stride of a broadcast dimension is 0.
+    /// `target_shape` must be a valid broadcast of `shape`.
+    pub(crate) fn broadcast_strides(shape: &[usize], target_shape: &[usize]) -> Vec<usize> {
+        let diff = target_shape.len() - shape.len();
+        let mut strides = vec![0; target_shape.len()];
+        let mut stride = 1;
+        for i in (0..shape.len()).rev() {
+            if shape[i] != 1 || target_shape[i + diff] == 1 {
+                strides[i + diff] = stride;
+            }
+            stride *= shape[i];
+        }
+        strides
+    }
+
+    /// Apply the function to each pair of elements of two broadcast tensors
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rhs` - The right hand side tensor
+    /// * `f` - The function applied to each pair of elements
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Tensor<V>>` - The tensor of the broadcast shape
+    /// 
+    /// # Note
+    /// 
+    /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+    pub(crate) fn broadcast_zip_with<U, V>(&self, rhs: &Tensor<U>, f: impl Fn(&T, &U) -> V) -> Result<Tensor<V>> {
+        let shape = Self::broadcast_shape(&self.shape, &rhs.shape)?;
+        let lhs = self.view().expand(shape.clone());
+        let rhs = rhs.view().expand(shape.clone());
+        let data = lhs.iter()
+            .zip(rhs.iter())
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Ok(Tensor { data: Arc::new(data), shape })
+    }
+
+    /// Apply the function to each lane of the tensor along the axis
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axis of the lanes
+    /// * `keepdims` - Whether to keep the axis with size 1
+    /// * `f` - The function which reduces the elements of a lane to a value
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Tensor<U>>` - The tensor of the reduced values
+    /// 
+    /// # Note
+    /// 
+    /// If axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub(crate) fn map_axis<U>(
+        &self,
+        axis: usize,
+        keepdims: bool,
+        mut f: impl FnMut(Take<StepBy<Iter<'_, T>>>) -> U,
+    ) -> Result<Tensor<U>> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim().saturating_sub(1)).into())
+        }
+        let mut shape = self.shape.clone();
+        if keepdims {
+            shape[axis] = 1;
+        } else {
+            shape.remove(axis);
+        }
+        let size = self.shape[axis];
+        let before = self.shape[..axis].iter().product::<usize>();
+        let after = self.shape[axis + 1..].iter().product::<usize>();
+        let mut data = Vec::with_capacity(before * after);
+        for i in 0..before {
+            for j in 0..after {
+                let start = i * size * after + j;
+                data.push(f(self.data[start.min(self.data.len())..].iter().step_by(after).take(size)));
+            }
+        }
+        Ok(Tensor { data: Arc::new(data), shape })
+    }
+
+    /// Get the number of elements reduced over the axes
+    /// 
+    /// # Arguments
+    /// 
+    /// * `axis` - The axes to reduce
+    /// 
+    /// # Note
+    /// 
+    /// If an axis is out of range, `TensorError::DimensionLargerError` is returned
+    /// If an axis is duplicated, `TensorError::InvalidArgumentError` is returned
+    pub(crate) fn reduced_size(&self, axis: &[usize]) -> Result<usize> {
+        for (i, &a) in axis.iter().enumerate() {
+            if a >= self.ndim() {
+                return Err(TensorError::DimensionLargerError(a, self.ndim().saturating_sub(1)).into())
+            }
+            if axis[..i].contains(&a) {
+                return Err(TensorError::InvalidArgumentError(format!("duplicate axis: {:?}", axis)).into())
+            }
+        }
+        Ok(axis.iter().map(|&a| self.shape[a]).product())
+    }
 }
 
 impl<T> Tensor<T>
@@ -112,10 +268,10 @@ where
     /// * `f` - The function to be adapted
     pub(crate) fn iter_func(&self, f: impl Fn(T) -> T) -> Self {
         Self {
-            data: self.data
+            data: Arc::new(self.data
                 .iter()
                 .map(|x| f(x.clone()))
-                .collect(),
+                .collect()),
             shape: self.shape.clone(),
         }
     }
@@ -243,4 +399,83 @@ mod tests {
         assert_eq!(Tensor::<f64>::data_index_to_indexes(4, &vec![2, 3]), vec![1, 1]);
         assert_eq!(Tensor::<f64>::data_index_to_indexes(5, &vec![2, 3]), vec![1, 2]);
     }
+
+    #[test]
+    fn broadcast_shape_normal() {
+        assert_eq!(Tensor::<f64>::broadcast_shape(&[2, 3], &[2, 3]).unwrap(), vec![2, 3]);
+        assert_eq!(Tensor::<f64>::broadcast_shape(&[2, 3], &[3]).unwrap(), vec![2, 3]);
+        assert_eq!(Tensor::<f64>::broadcast_shape(&[2, 1], &[1, 3]).unwrap(), vec![2, 3]);
+        assert_eq!(Tensor::<f64>::broadcast_shape(&[], &[4, 1, 3]).unwrap(), vec![4, 1, 3]);
+        assert_eq!(Tensor::<f64>::broadcast_shape(&[5, 1, 1], &[2, 0]).unwrap(), vec![5, 2, 0]);
+    }
+
+    #[test]
+    fn broadcast_shape_error_mismatch() {
+        match Tensor::<f64>::broadcast_shape(&[2, 3], &[3, 2]) {
+            Ok(_) => panic!("Should be error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2, 3], vec![3, 2]))
+            }
+        }
+    }
+
+    #[test]
+    fn broadcast_strides_normal() {
+        assert_eq!(Tensor::<f64>::broadcast_strides(&[2, 3], &[2, 3]), vec![3, 1]);
+        assert_eq!(Tensor::<f64>::broadcast_strides(&[3], &[2, 3]), vec![0, 1]);
+        assert_eq!(Tensor::<f64>::broadcast_strides(&[2, 1], &[2, 3]), vec![1, 0]);
+        assert_eq!(Tensor::<f64>::broadcast_strides(&[], &[2, 3]), vec![0, 0]);
+    }
+
+    #[test]
+    fn broadcast_zip_with_normal() {
+        let x = Tensor::new([0, 1, 2], [3, 1]).unwrap();
+        let y = Tensor::new([1.0, 2.0], [2]).unwrap();
+        let z = x.broadcast_zip_with(&y, |a, b| *a as f64 * b).unwrap();
+        assert_eq!(z.get_shape(), &vec![3, 2]);
+        assert_eq!(z.get_data(), &vec![0.0, 0.0, 1.0, 2.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn map_axis_normal() {
+        let x = Tensor::<i32>::arrange([2, 3, 2]).unwrap();
+        let y = x.map_axis(1, false, |lane| lane.sum::<i32>()).unwrap();
+        assert_eq!(y.get_shape(), &vec![2, 2]);
+        assert_eq!(y.get_data(), &vec![6, 9, 24, 27]);
+        let y = x.map_axis(2, true, |lane| lane.count()).unwrap();
+        assert_eq!(y.get_shape(), &vec![2, 3, 1]);
+        assert_eq!(y.get_data(), &vec![2; 6]);
+    }
+
+    #[test]
+    fn map_axis_error_axis() {
+        let x = Tensor::<i32>::arrange([2, 3]).unwrap();
+        match x.map_axis(2, false, |lane| lane.count()) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(2, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_size_normal() {
+        let x = Tensor::<i32>::arrange([2, 3, 4]).unwrap();
+        assert_eq!(x.reduced_size(&[0, 2]).unwrap(), 8);
+        assert_eq!(x.reduced_size(&[]).unwrap(), 1);
+    }
+
+    #[test]
+    fn reduced_size_error_duplicate() {
+        let x = Tensor::<i32>::arrange([2, 3, 4]).unwrap();
+        match x.reduced_size(&[1, 1]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError("duplicate axis: [1, 1]".to_string()));
+            }
+        }
+    }
 }
@@ -0,0 +1,740 @@
+use std::sync::Arc;
+use anyhow::Result;
+use super::{Tensor, TensorView};
+use super::slice_elem::{SliceElem, ResolvedSlice, resolve_slice};
+use crate::error::TensorError;
+
+/// Calculate the strides of a contiguous (row-major) tensor
+///
+/// # Arguments
+///
+/// * `shape` - The shape of the tensor
+pub(crate) fn contiguous_strides(shape: &[usize]) -> Vec<isize> {
+    let mut strides = vec![0; shape.len()];
+    let mut stride = 1;
+    for i in (0..shape.len()).rev() {
+        strides[i] = stride;
+        stride *= shape[i] as isize;
+    }
+    strides
+}
+
+impl<T> Tensor<T>
+{
+    /// Create a view of the tensor
+    ///
+    /// # Returns
+    ///
+    /// * `TensorView<T>` - The view sharing the data of the tensor
+    pub fn view(&self) -> TensorView<T> {
+        TensorView {
+            data: self.data.clone(),
+            shape: self.shape.clone(),
+            strides: contiguous_strides(&self.shape),
+            offset: 0,
+        }
+    }
+}
+
+impl<T> TensorView<T>
+{
+    /// Get the shape of the view
+    pub fn get_shape(&self) -> &Vec<usize> {
+        &self.shape
+    }
+
+    /// Get the strides of the view
+    pub fn get_strides(&self) -> &Vec<isize> {
+        &self.strides
+    }
+
+    /// Get the offset of the view in the shared data
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the number of dimensions of the view
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Get the size of the view
+    pub fn size(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Check if the elements of the view are laid out contiguously in row-major order
+    pub fn is_contiguous(&self) -> bool {
+        let strides = contiguous_strides(&self.shape);
+        self.shape.iter()
+            .zip(self.strides.iter().zip(strides.iter()))
+            .all(|(&dim, (stride, expected))| dim <= 1 || stride == expected)
+    }
+
+    /// Get the value of the view at the specified index
+    ///
+    /// # Arguments
+    ///
+    /// * `indexes` - The indexes of the view
+    ///
+    /// # Returns
+    ///
+    /// * `Result<&T>` - Result of the access
+    ///
+    /// # Note
+    ///
+    /// If the indexes are not correct, `TensorError::IndexError` is returned
+    pub fn at<U: AsRef<[usize]>>(&self, indexes: U) -> Result<&T> {
+        let indexes = indexes.as_ref();
+        if indexes.len() != self.ndim()
+            || indexes.iter().zip(self.shape.iter()).any(|(i, dim)| i >= dim) {
+            return Err(TensorError::IndexError(
+                self.shape.clone(), indexes.to_vec()
+            ).into())
+        }
+        let position = indexes.iter()
+            .zip(self.strides.iter())
+            .fold(self.offset as isize, |acc, (&i, &stride)| acc + i as isize * stride);
+        Ok(&self.data[position as usize])
+    }
+
+    /// Transpose the view
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The view with the axes reversed
+    pub fn transpose(&self) -> Self {
+        let mut shape = self.shape.clone();
+        shape.reverse();
+        let mut strides = self.strides.clone();
+        strides.reverse();
+        Self { data: self.data.clone(), shape, strides, offset: self.offset }
+    }
+
+    /// Permute the axes of the view
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - The new order of the axes
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The view whose i-th axis is the `axes[i]`-th axis of the original
+    ///
+    /// # Note
+    ///
+    /// If the length of `axes` is not the number of dimensions, `TensorError::DimensionError` is returned.
+    /// If `axes` is not a permutation, `TensorError::InvalidArgumentError` is returned.
+    pub fn permute<U: AsRef<[usize]>>(&self, axes: U) -> Result<Self> {
+        let axes = axes.as_ref();
+        if axes.len() != self.ndim() {
+            return Err(TensorError::DimensionError(axes.len(), self.ndim()).into())
+        }
+        let mut used = vec![false; self.ndim()];
+        for &axis in axes {
+            if axis >= self.ndim() || used[axis] {
+                return Err(TensorError::InvalidArgumentError(
+                    format!("axes is not a permutation: {:?}", axes)
+                ).into())
+            }
+            used[axis] = true;
+        }
+        Ok(Self {
+            data: self.data.clone(),
+            shape: axes.iter().map(|&axis| self.shape[axis]).collect(),
+            strides: axes.iter().map(|&axis| self.strides[axis]).collect(),
+            offset: self.offset,
+        })
+    }
+
+    /// Swap two axes of the view
+    ///
+    /// # Arguments
+    ///
+    /// * `axis0` - The first axis
+    /// * `axis1` - The second axis
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The view with the two axes swapped
+    ///
+    /// # Note
+    ///
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned
+    pub fn swapaxes(&self, axis0: usize, axis1: usize) -> Result<Self> {
+        for axis in [axis0, axis1] {
+            if axis >= self.ndim() {
+                return Err(TensorError::DimensionLargerError(axis, self.ndim()).into())
+            }
+        }
+        let mut shape = self.shape.clone();
+        shape.swap(axis0, axis1);
+        let mut strides = self.strides.clone();
+        strides.swap(axis0, axis1);
+        Ok(Self { data: self.data.clone(), shape, strides, offset: self.offset })
+    }
+
+    /// Broadcast the view
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The new shape of the view
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - Result of the broadcast
+    ///
+    /// # Note
+    ///
+    /// The broadcast dimensions have a stride of 0.
+    /// If the shape is not correct, following errors are returned:
+    ///
+    /// * `TensorError::ShapeError` - If the shape is not correct
+    /// * `TensorError::ShapeSizeError` - If the shape size is not correct
+    pub fn broadcast_to<U: Into<Vec<usize>>>(&self, shape: U) -> Result<Self> {
+        let shape = shape.into();
+        if self.shape.contains(&0) || shape.contains(&0) {
+            return Err(TensorError::ShapeError(
+                self.shape.clone(), shape
+            ).into())
+        }
+        if shape.len() < self.ndim() {
+            return Err(TensorError::ShapeSizeError(
+                self.ndim(), shape.len()
+            ).into())
+        }
+        let diff = shape.len() - self.ndim();
+        for i in 0..self.ndim() {
+            if self.shape[i] != 1 && self.shape[i] != shape[i + diff] {
+                return Err(TensorError::ShapeError(
+                    self.shape.clone(), shape
+                ).into())
+            }
+        }
+        Ok(self.expand(shape))
+    }
+
+    /// Broadcast the view without checking the shape
+    ///
+    /// # Note
+    ///
+    /// `shape` must be a valid broadcast of the shape of the view.
+    pub(crate) fn expand(&self, shape: Vec<usize>) -> Self {
+        let diff = shape.len() - self.ndim();
+        let mut strides = vec![0; shape.len()];
+        for i in 0..self.ndim() {
+            if self.shape[i] == shape[i + diff] {
+                strides[i + diff] = self.strides[i];
+            }
+        }
+        Self { data: self.data.clone(), shape, strides, offset: self.offset }
+    }
+
+    /// Slice the view with the given first dimension index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the first dimension
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The view without the first dimension
+    ///
+    /// # Note
+    ///
+    /// If the view is 0 dimensional, `TensorError::DimensionSmallerError` is returned.
+    /// If the index is out of range, `TensorError::IndexError` is returned.
+    pub fn slice_with_one_index(&self, index: usize) -> Result<Self> {
+        if self.ndim() == 0 {
+            return Err(TensorError::DimensionSmallerError(0, 1).into())
+        }
+        if index >= self.shape[0] {
+            return Err(TensorError::IndexError(self.shape.clone(), vec![index]).into())
+        }
+        let offset = (self.offset as isize + index as isize * self.strides[0]) as usize;
+        Ok(Self {
+            data: self.data.clone(),
+            shape: self.shape[1..].to_vec(),
+            strides: self.strides[1..].to_vec(),
+            offset,
+        })
+    }
+
+    /// Slice the view with the range of the given axis
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - The axis to be sliced
+    /// * `start` - The first index of the range
+    /// * `end` - The index after the last index of the range
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The sliced view
+    ///
+    /// # Note
+    ///
+    /// If the axis is out of range, `TensorError::DimensionLargerError` is returned.
+    /// If the range is out of range, `TensorError::IndexError` is returned.
+    pub fn slice_axis(&self, axis: usize, start: usize, end: usize) -> Result<Self> {
+        if axis >= self.ndim() {
+            return Err(TensorError::DimensionLargerError(axis, self.ndim()).into())
+        }
+        if start > end || end > self.shape[axis] {
+            return Err(TensorError::IndexError(self.shape.clone(), vec![start, end]).into())
+        }
+        let mut shape = self.shape.clone();
+        shape[axis] = end - start;
+        let offset = if start == end {
+            self.offset
+        } else {
+            (self.offset as isize + start as isize * self.strides[axis]) as usize
+        };
+        Ok(Self { data: self.data.clone(), shape, strides: self.strides.clone(), offset })
+    }
+
+    /// Slice the view with the slice elements
+    ///
+    /// # Arguments
+    ///
+    /// * `elems` - The slice elements for each axis, usually created with the `s!` macro
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - The sliced view sharing the data
+    ///
+    /// # Note
+    ///
+    /// Index arrays cannot be represented as a view, so `TensorError::InvalidArgumentError` is returned for them.
+    /// Use `Tensor::slice` instead.
+    /// The other errors are the same as `Tensor::slice`.
+    pub fn slice<U: AsRef<[SliceElem]>>(&self, elems: U) -> Result<Self> {
+        let resolved = resolve_slice(&self.shape, elems.as_ref())?;
+        let mut offset = self.offset as isize;
+        let mut shape = Vec::with_capacity(resolved.len());
+        let mut strides = Vec::with_capacity(resolved.len());
+        let mut axis = 0;
+        for elem in resolved {
+            match elem {
+                ResolvedSlice::Index(i) => offset += i as isize * self.strides[axis],
+                ResolvedSlice::Range { start, step, len } => {
+                    if len > 0 {
+                        offset += start as isize * self.strides[axis];
+                    }
+                    shape.push(len);
+                    strides.push(self.strides[axis] * step);
+                },
+                ResolvedSlice::NewAxis => {
+                    shape.push(1);
+                    strides.push(0);
+                    continue
+                },
+                ResolvedSlice::Indexes(_) => return Err(TensorError::InvalidArgumentError(
+                    "index arrays cannot be used for a view".to_string()
+                ).into()),
+            }
+            axis += 1;
+        }
+        Ok(Self { data: self.data.clone(), shape, strides, offset: offset as usize })
+    }
+
+    /// Map each element of the view into a new tensor
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function applied to each element
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Tensor<U> {
+        Tensor {
+            data: Arc::new(self.iter().map(f).collect()),
+            shape: self.shape.clone(),
+        }
+    }
+}
+
+impl<T> TensorView<T>
+where
+    T: Clone
+{
+    /// Reshape the view
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The new shape of the view
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self>` - Result of the reshape
+    ///
+    /// # Note
+    ///
+    /// The data is copied only when the view is not contiguous.
+    /// If the shape is not correct, `TensorError::ShapeSizeError` is returned
+    pub fn reshape<U: Into<Vec<usize>>>(&self, shape: U) -> Result<Self> {
+        let shape = shape.into();
+        let size: usize = shape.iter().product();
+        if size != self.size() {
+            return Err(TensorError::ShapeSizeError(self.size(), size).into())
+        }
+        if self.is_contiguous() {
+            let strides = contiguous_strides(&shape);
+            Ok(Self { data: self.data.clone(), shape, strides, offset: self.offset })
+        } else {
+            Ok(self.contiguous().reshape(shape)?.view())
+        }
+    }
+
+    /// Materialize the view as a tensor
+    ///
+    /// # Returns
+    ///
+    /// * `Tensor<T>` - The tensor with the elements of the view
+    ///
+    /// # Note
+    ///
+    /// The data is shared without copying when the view covers the whole data in order.
+    pub fn contiguous(&self) -> Tensor<T> {
+        if self.is_contiguous() && self.offset == 0 && self.size() == self.data.len() {
+            return Tensor { data: self.data.clone(), shape: self.shape.clone() }
+        }
+        self.map(|x| x.clone())
+    }
+
+    fn ref_ops(&self, rhs: &Self, f: fn(T, T) -> T) -> Result<Tensor<T>> {
+        let shape = Tensor::<T>::broadcast_shape(&self.shape, &rhs.shape)?;
+        let lhs = self.expand(shape.clone());
+        let rhs = rhs.expand(shape.clone());
+        let data = lhs.iter()
+            .zip(rhs.iter())
+            .map(|(x, y)| f(x.clone(), y.clone()))
+            .collect();
+        Ok(Tensor { data: Arc::new(data), shape })
+    }
+}
+
+/// A tensor or a view which the operations accepting views take
+///
+/// `as_view` does not copy the data,
+/// and `as_view().contiguous()` copies it only when the view is not contiguous.
+pub trait AsView<T> {
+    fn as_view(&self) -> TensorView<T>;
+}
+
+impl<T> AsView<T> for Tensor<T> {
+    fn as_view(&self) -> TensorView<T> {
+        self.view()
+    }
+}
+
+impl<T> AsView<T> for TensorView<T> {
+    fn as_view(&self) -> TensorView<T> {
+        TensorView {
+            data: self.data.clone(),
+            shape: self.shape.clone(),
+            strides: self.strides.clone(),
+            offset: self.offset,
+        }
+    }
+}
+
+impl<T, A: AsView<T> + ?Sized> AsView<T> for &A {
+    fn as_view(&self) -> TensorView<T> {
+        (**self).as_view()
+    }
+}
+
+impl<T> From<TensorView<T>> for Tensor<T>
+where
+    T: Clone
+{
+    fn from(view: TensorView<T>) -> Self {
+        view.contiguous()
+    }
+}
+
+impl<T> PartialEq for TensorView<T>
+where
+    T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape && self.iter().eq(other.iter())
+    }
+}
+
+macro_rules! def_view_ops {
+    ( $( ($trait: ident, $method: ident, $try_method: ident, $op: tt) ), + ) => {
+        $(
+            impl<T> TensorView<T>
+            where
+                T: std::ops::$trait<Output = T> + Clone
+            {
+                /// Apply the operator to two views with broadcasting
+                ///
+                /// # Note
+                ///
+                /// If the shapes cannot be broadcast, `TensorError::ShapeMismatchError` is returned
+                pub fn $try_method(&self, rhs: &Self) -> Result<Tensor<T>> {
+                    self.ref_ops(rhs, |x, y| x $op y)
+                }
+            }
+
+            impl<T> std::ops::$trait for &TensorView<T>
+            where
+                T: std::ops::$trait<Output = T> + Clone
+            {
+                type Output = Tensor<T>;
+
+                fn $method(self, rhs: Self) -> Self::Output {
+                    self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+                }
+            }
+
+            impl<T> std::ops::$trait<&Tensor<T>> for &TensorView<T>
+            where
+                T: std::ops::$trait<Output = T> + Clone
+            {
+                type Output = Tensor<T>;
+
+                fn $method(self, rhs: &Tensor<T>) -> Self::Output {
+                    self.$try_method(&rhs.view()).unwrap_or_else(|e| panic!("{}", e))
+                }
+            }
+
+            impl<T> std::ops::$trait<&TensorView<T>> for &Tensor<T>
+            where
+                T: std::ops::$trait<Output = T> + Clone
+            {
+                type Output = Tensor<T>;
+
+                fn $method(self, rhs: &TensorView<T>) -> Self::Output {
+                    self.view().$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+                }
+            }
+        )+
+    };
+}
+
+def_view_ops!(
+    (Add, add, try_add, +),
+    (Sub, sub, try_sub, -),
+    (Mul, mul, try_mul, *),
+    (Div, div, try_div, /),
+    (Rem, rem, try_rem, %)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view_normal() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let v = x.view();
+        assert_eq!(v.get_shape(), &vec![2, 3]);
+        assert_eq!(v.get_strides(), &vec![3, 1]);
+        assert_eq!(v.get_offset(), 0);
+        assert!(v.is_contiguous());
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+    }
+
+    #[test]
+    fn view_transpose_no_copy() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let v = x.view().transpose();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_shape(), &vec![3, 2]);
+        assert_eq!(v.get_strides(), &vec![1, 3]);
+        assert!(!v.is_contiguous());
+        assert_eq!(v.at([2, 1]).unwrap(), &5.0);
+        assert_eq!(v.contiguous(), Tensor::new([0.0, 3.0, 1.0, 4.0, 2.0, 5.0], [3, 2]).unwrap());
+    }
+
+    #[test]
+    fn view_permute_no_copy() {
+        let x = Tensor::<f64>::arrange([2, 3, 4]).unwrap();
+        let v = x.view().permute([2, 0, 1]).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_shape(), &vec![4, 2, 3]);
+        assert_eq!(v.get_strides(), &vec![1, 12, 4]);
+        assert_eq!(v.at([3, 1, 2]).unwrap(), x.at([1, 2, 3]).unwrap());
+    }
+
+    #[test]
+    fn view_swapaxes_error_axis() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.view().swapaxes(0, 2) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::DimensionLargerError(2, 2));
+            }
+        }
+    }
+
+    #[test]
+    fn view_slice_no_copy() {
+        let x = Tensor::<f64>::arrange([2, 6, 3]).unwrap();
+        let v = x.view().slice(crate::s![.., 5..0;-2, -1]).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_shape(), &vec![2, 3]);
+        assert_eq!(v.contiguous(), x.slice(crate::s![.., 5..0;-2, -1]).unwrap());
+    }
+
+    #[test]
+    fn view_slice_error_index_arrays() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.view().slice(crate::s![[0, 1]]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::InvalidArgumentError(
+                    "index arrays cannot be used for a view".to_string()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn view_reshape_contiguous() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let v = x.view().reshape([3, 2]).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_strides(), &vec![2, 1]);
+        let y = v.contiguous();
+        assert!(Arc::ptr_eq(&x.data, &y.data));
+        assert_eq!(y, x.reshape([3, 2]).unwrap());
+    }
+
+    #[test]
+    fn view_reshape_not_contiguous() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let v = x.view().transpose().reshape([6]).unwrap();
+        assert_eq!(v.contiguous().get_data(), &vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+    }
+
+    #[test]
+    fn view_reshape_error_mismatch() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.view().reshape([4]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeSizeError(6, 4));
+            }
+        }
+    }
+
+    #[test]
+    fn view_broadcast_to_no_copy() {
+        let x = Tensor::new([0.0, 1.0, 2.0], [3, 1]).unwrap();
+        let v = x.view().broadcast_to([2, 3, 2]).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_strides(), &vec![0, 1, 0]);
+        assert_eq!(v.contiguous().get_data(), &vec![
+            0.0, 0.0, 1.0, 1.0, 2.0, 2.0,
+            0.0, 0.0, 1.0, 1.0, 2.0, 2.0,
+        ]);
+    }
+
+    #[test]
+    fn view_broadcast_to_error_mismatch_shape() {
+        let x = Tensor::new([0.0, 1.0, 2.0], [3,]).unwrap();
+        match x.view().broadcast_to([3, 2]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeError(vec![3], vec![3, 2]));
+            }
+        }
+    }
+
+    #[test]
+    fn view_slice_with_one_index() {
+        let x = Tensor::<f64>::arrange([3, 2]).unwrap();
+        let v = x.view().slice_with_one_index(1).unwrap();
+        assert!(Arc::ptr_eq(&x.data, &v.data));
+        assert_eq!(v.get_offset(), 2);
+        assert_eq!(v.contiguous(), Tensor::new([2.0, 3.0], [2]).unwrap());
+    }
+
+    #[test]
+    fn view_slice_axis() {
+        let x = Tensor::<f64>::arrange([2, 4]).unwrap();
+        let v = x.view().slice_axis(1, 1, 3).unwrap();
+        assert_eq!(v.get_shape(), &vec![2, 2]);
+        assert_eq!(v.get_offset(), 1);
+        assert_eq!(v.contiguous().get_data(), &vec![1.0, 2.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn view_slice_axis_error_index() {
+        let x = Tensor::<f64>::arrange([2, 4]).unwrap();
+        match x.view().slice_axis(1, 3, 5) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::IndexError(vec![2, 4], vec![3, 5]));
+            }
+        }
+    }
+
+    #[test]
+    fn view_at_error() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        match x.view().transpose().at([2, 2]) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::IndexError(vec![3, 2], vec![2, 2]));
+            }
+        }
+    }
+
+    #[test]
+    fn view_ops() {
+        let x = Tensor::<i32>::arrange([2, 3]).unwrap();
+        let y = Tensor::<i32>::arrange([3, 2]).unwrap();
+        let z = &x.view() + &y.view().transpose();
+        assert_eq!(z.get_data(), &vec![0, 3, 6, 4, 7, 10]);
+        let z = &x - &y.view().transpose();
+        assert_eq!(z.get_data(), &vec![0, -1, -2, 2, 1, 0]);
+        let z = &y.view().transpose() * &Tensor::new([1, 2, 3], [3]).unwrap();
+        assert_eq!(z.get_data(), &vec![0, 4, 12, 1, 6, 15]);
+    }
+
+    #[test]
+    fn view_ops_error_mismatch_shape() {
+        let x = Tensor::<i32>::arrange([2, 3]).unwrap();
+        match x.view().try_div(&x.view().transpose()) {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::ShapeMismatchError(vec![2, 3], vec![3, 2]));
+            }
+        }
+    }
+
+    #[test]
+    fn view_eq() {
+        let x = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let y = Tensor::new([0, 2, 1, 3], [2, 2]).unwrap();
+        assert_eq!(x.view().transpose(), y.view());
+        assert!(x.view() != y.view());
+    }
+
+    #[test]
+    fn view_map() {
+        let x = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let y = x.view().transpose().map(|x| *x as f64 * 0.5);
+        assert_eq!(y.get_data(), &vec![0.0, 1.0, 0.5, 1.5]);
+    }
+
+    #[test]
+    fn tensor_clone_copy_on_write() {
+        let x = Tensor::<i32>::arrange([2, 2]).unwrap();
+        let mut y = x.clone();
+        assert!(Arc::ptr_eq(&x.data, &y.data));
+        *y.at_mut([0, 0]).unwrap() = 10;
+        assert_eq!(x.get_data(), &vec![0, 1, 2, 3]);
+        assert_eq!(y.get_data(), &vec![10, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::Result;
+use num_traits::{Float, NumAssign};
+use super::{Tensor, TensorView};
+use crate::error::TensorError;
+
+impl<T> TensorView<T>
+where
+    T: Float
+{
+    /// Calculate the power of the view
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The power to be calculated (integer)
+    pub fn powi(&self, n: i32) -> Tensor<T> {
+        self.map(|x| x.powi(n))
+    }
+
+    /// Calculate the power of the view
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The power to be calculated (floating point)
+    pub fn powf(&self, n: T) -> Tensor<T> {
+        self.map(|x| x.powf(n))
+    }
+
+    /// Calculate the exponential of the view
+    pub fn exp(&self) -> Tensor<T> {
+        self.map(|x| x.exp())
+    }
+
+    /// Calculate the natural logarithm of the view
+    pub fn log(&self) -> Tensor<T> {
+        self.map(|x| x.ln())
+    }
+
+    /// Calculate the sin of the view
+    pub fn sin(&self) -> Tensor<T> {
+        self.map(|x| x.sin())
+    }
+
+    /// Calculate the cos of the view
+    pub fn cos(&self) -> Tensor<T> {
+        self.map(|x| x.cos())
+    }
+
+    /// Calculate the tanh of the view
+    pub fn tanh(&self) -> Tensor<T> {
+        self.map(|x| x.tanh())
+    }
+
+    /// Calculate the absolute value of the view
+    pub fn abs(&self) -> Tensor<T> {
+        self.map(|x| x.abs())
+    }
+
+    /// Calculate the square root of the view
+    pub fn sqrt(&self) -> Tensor<T> {
+        self.map(|x| x.sqrt())
+    }
+}
+
+impl<T> TensorView<T>
+where
+    T: NumAssign + Clone
+{
+    /// Sum all the elements of the view
+    pub fn sum_all(&self) -> T {
+        self.iter().fold(T::zero(), |acc, x| acc + x.clone())
+    }
+
+    /// Sum the elements of the view along the specified axis
+    ///
+    /// # Note
+    ///
+    /// The view is materialized first, which copies the data only when it is not contiguous.
+    /// See `Tensor::sum` for the arguments.
+    pub fn sum<U: AsRef<[usize]>>(&self, axis: Option<U>, keepdims: bool) -> Tensor<T> {
+        self.contiguous().sum(axis, keepdims)
+    }
+
+    /// Sum the elements of the view to the given shape
+    ///
+    /// # Note
+    ///
+    /// The view is materialized first, which copies the data only when it is not contiguous.
+    /// See `Tensor::sum_to` for the arguments and the errors.
+    pub fn sum_to<U: AsRef<[usize]>>(&self, shape: U) -> Result<Tensor<T>> {
+        self.contiguous().sum_to(shape)
+    }
+}
+
+impl<T> TensorView<T>
+where
+    T: Float + NumAssign
+{
+    /// Calculate the mean of the view
+    pub fn mean(&self) -> Result<T> {
+        let len = T::from(self.size())
+            .ok_or(TensorError::CastError(
+                std::any::type_name::<T>().to_string()
+            ))?;
+        Ok(self.sum_all() / len)
+    }
+
+    /// Calculate the mean of the view along the specified axis
+    ///
+    /// # Note
+    ///
+    /// The view is materialized first, which copies the data only when it is not contiguous.
+    /// See `Tensor::mean_with_axis` for the arguments and the errors.
+    pub fn mean_with_axis<U: AsRef<[usize]>>(&self, axis: U, keepdims: bool) -> Result<Tensor<T>> {
+        self.contiguous().mean_with_axis(axis, keepdims)
+    }
+}
+
+impl<T> TensorView<T>
+where
+    T: PartialOrd + Clone
+{
+    /// Get the maximum value of the view
+    ///
+    /// # Note
+    ///
+    /// If the view is empty, `EmptyTensorError` is returned
+    pub fn max(&self) -> Result<T> {
+        self.iter()
+            .fold(None, |acc: Option<&T>, x| match acc {
+                Some(y) if y >= x => Some(y),
+                _ => Some(x),
+            })
+            .cloned()
+            .ok_or(TensorError::EmptyTensorError().into())
+    }
+
+    /// Get the maximum values of the view along the specified axis
+    ///
+    /// # Note
+    ///
+    /// The view is materialized first, which copies the data only when it is not contiguous.
+    /// See `Tensor::max_with_axis` for the arguments and the errors.
+    pub fn max_with_axis(&self, axis: usize, keepdims: bool) -> Result<Tensor<T>> {
+        self.contiguous().max_with_axis(axis, keepdims)
+    }
+
+    /// Get the minimum value of the view
+    ///
+    /// # Note
+    ///
+    /// If the view is empty, `EmptyTensorError` is returned
+    pub fn min(&self) -> Result<T> {
+        self.iter()
+            .fold(None, |acc: Option<&T>, x| match acc {
+                Some(y) if y <= x => Some(y),
+                _ => Some(x),
+            })
+            .cloned()
+            .ok_or(TensorError::EmptyTensorError().into())
+    }
+
+    /// Get the minimum values of the view along the specified axis
+    ///
+    /// # Note
+    ///
+    /// The view is materialized first, which copies the data only when it is not contiguous.
+    /// See `Tensor::min_with_axis` for the arguments and the errors.
+    pub fn min_with_axis(&self, axis: usize, keepdims: bool) -> Result<Tensor<T>> {
+        self.contiguous().min_with_axis(axis, keepdims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view_unary_math() {
+        let x = Tensor::<f64>::arrange([2, 3]).unwrap();
+        let view = x.transpose();
+        assert_eq!(view.exp(), x.transpose().contiguous().exp());
+        assert_eq!(view.powi(2), x.transpose().contiguous().powi(2));
+        assert_eq!(view.sqrt().get_shape(), &vec![3, 2]);
+    }
+
+    #[test]
+    fn view_reductions() {
+        let x = Tensor::<f64>::new(vec![3.0, 1.0, 4.0, 1.0, 5.0, 9.0], vec![2, 3]).unwrap();
+        let view = x.transpose();
+        assert_eq!(view.sum_all(), 23.0);
+        assert_eq!(view.sum(Some([1]), false), x.sum(Some([0]), false));
+        assert_eq!(view.mean().unwrap(), 23.0 / 6.0);
+        assert_eq!(view.max().unwrap(), 9.0);
+        assert_eq!(view.min().unwrap(), 1.0);
+        assert_eq!(view.max_with_axis(0, false).unwrap(), x.max_with_axis(1, false).unwrap());
+        let broadcast = Tensor::<f64>::arrange([3]).unwrap().broadcast_to([2, 3]).unwrap();
+        assert_eq!(broadcast.sum_to([3]).unwrap(), Tensor::new(vec![0.0, 2.0, 4.0], vec![3]).unwrap());
+    }
+
+    #[test]
+    fn view_max_error_empty() {
+        let x = Tensor::<f64>::new(Vec::new(), vec![0]).unwrap();
+        match x.view().max() {
+            Ok(_) => panic!("error"),
+            Err(e) => {
+                let e = e.downcast::<TensorError>().unwrap();
+                assert_eq!(e, TensorError::EmptyTensorError());
+            }
+        }
+    }
+}